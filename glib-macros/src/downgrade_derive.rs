@@ -0,0 +1,150 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_error::abort_call_site;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, Fields, Index, WhereClause};
+
+use crate::utils::crate_ident_new;
+
+pub fn impl_downgrade(input: &syn::DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let weak_name = format_ident!("{}Weak", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics;
+
+    let crate_ident = crate_ident_new();
+
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => abort_call_site!("derive(Downgrade) only supports structs"),
+    };
+
+    let (weak_struct, downgrade, upgrade, clone) = match fields {
+        Fields::Named(fields) => {
+            let idents: Vec<&Ident> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().unwrap())
+                .collect();
+            let decls = fields.named.iter().map(|f| {
+                let ident = &f.ident;
+                let field_vis = &f.vis;
+                let ty = &f.ty;
+                quote! {
+                    #field_vis #ident: <#ty as #crate_ident::clone::Downgrade>::Weak
+                }
+            });
+
+            (
+                quote! {
+                    #vis struct #weak_name #generics #where_clause {
+                        #(#decls,)*
+                    }
+                },
+                quote! {
+                    #weak_name {
+                        #(#idents: #crate_ident::clone::Downgrade::downgrade(&self.#idents),)*
+                    }
+                },
+                quote! {
+                    Some(#name {
+                        #(#idents: #crate_ident::clone::Upgrade::upgrade(&self.#idents)?,)*
+                    })
+                },
+                quote! {
+                    #weak_name {
+                        #(#idents: ::std::clone::Clone::clone(&self.#idents),)*
+                    }
+                },
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let indices: Vec<Index> = (0..fields.unnamed.len()).map(Index::from).collect();
+            let decls = fields.unnamed.iter().map(|f| {
+                let field_vis = &f.vis;
+                let ty = &f.ty;
+                quote! {
+                    #field_vis <#ty as #crate_ident::clone::Downgrade>::Weak
+                }
+            });
+
+            (
+                quote! {
+                    #vis struct #weak_name #generics (#(#decls,)*) #where_clause;
+                },
+                quote! {
+                    #weak_name(
+                        #(#crate_ident::clone::Downgrade::downgrade(&self.#indices),)*
+                    )
+                },
+                quote! {
+                    Some(#name(
+                        #(#crate_ident::clone::Upgrade::upgrade(&self.#indices)?,)*
+                    ))
+                },
+                quote! {
+                    #weak_name(
+                        #(::std::clone::Clone::clone(&self.#indices),)*
+                    )
+                },
+            )
+        }
+        Fields::Unit => (
+            quote! {
+                #vis struct #weak_name;
+            },
+            quote! { #weak_name },
+            quote! { Some(#name) },
+            quote! { #weak_name },
+        ),
+    };
+
+    // The weak struct is cloneable whenever all weak field types are, independent of whether
+    // the generic parameters themselves implement `Clone`
+    let mut clone_where_clause = where_clause.cloned().unwrap_or_else(|| WhereClause {
+        where_token: Default::default(),
+        predicates: Default::default(),
+    });
+    for field in fields.iter() {
+        let ty = &field.ty;
+        clone_where_clause
+            .predicates
+            .push(parse_quote!(<#ty as #crate_ident::clone::Downgrade>::Weak: ::std::clone::Clone));
+    }
+
+    let weak_doc = syn::LitStr::new(
+        &format!("Weak reference counterpart of [`{}`].", name),
+        Span::call_site(),
+    );
+
+    quote! {
+        #[doc = #weak_doc]
+        #weak_struct
+
+        impl #impl_generics #crate_ident::clone::Downgrade for #name #ty_generics #where_clause {
+            type Weak = #weak_name #ty_generics;
+
+            fn downgrade(&self) -> Self::Weak {
+                #downgrade
+            }
+        }
+
+        impl #impl_generics #crate_ident::clone::Upgrade for #weak_name #ty_generics #where_clause {
+            type Strong = #name #ty_generics;
+
+            fn upgrade(&self) -> Option<Self::Strong> {
+                #upgrade
+            }
+        }
+
+        impl #impl_generics ::std::clone::Clone for #weak_name #ty_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                #clone
+            }
+        }
+    }
+}
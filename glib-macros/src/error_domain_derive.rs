@@ -0,0 +1,64 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use proc_macro2::TokenStream;
+use proc_macro_error::abort_call_site;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data};
+
+use crate::utils::{crate_ident_new, parse_name};
+
+pub fn impl_error_domain(input: &syn::DeriveInput) -> TokenStream {
+    let name = &input.ident;
+
+    let enum_variants = match input.data {
+        Data::Enum(ref e) => &e.variants,
+        _ => abort_call_site!("ErrorDomain only supports enums"),
+    };
+
+    let domain_name = match parse_name(&input, "error_domain") {
+        Ok(v) => v,
+        Err(e) => abort_call_site!(
+            "{}: derive(ErrorDomain) requires #[error_domain(name = \"domain-name\")]",
+            e
+        ),
+    };
+    let domain_name = format!("{}\0", domain_name);
+
+    let crate_ident = crate_ident_new();
+
+    let from_code = enum_variants.iter().map(|v| {
+        let variant = &v.ident;
+        quote_spanned! {v.span()=>
+            if code == #name::#variant as i32 {
+                return Some(#name::#variant);
+            }
+        }
+    });
+
+    // By convention unknown codes map to the `Failed` variant, if any
+    let fallback = if enum_variants.iter().any(|v| v.ident == "Failed") {
+        quote! { Some(#name::Failed) }
+    } else {
+        quote! { None }
+    };
+
+    quote! {
+        impl #crate_ident::error::ErrorDomain for #name {
+            fn domain() -> #crate_ident::Quark {
+                static QUARK: #crate_ident::StaticQuark = #crate_ident::StaticQuark::new(#domain_name);
+                QUARK.get()
+            }
+
+            fn code(self) -> i32 {
+                self as i32
+            }
+
+            fn from(code: i32) -> Option<Self> {
+                #(#from_code)*
+                #fallback
+            }
+        }
+    }
+}
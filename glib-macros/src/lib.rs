@@ -4,9 +4,12 @@
 
 extern crate proc_macro;
 
+mod downgrade_derive;
+mod error_domain_derive;
 mod gboxed_derive;
 mod genum_derive;
 mod gflags_attribute;
+mod option_entries_derive;
 mod utils;
 
 use proc_macro::TokenStream;
@@ -85,3 +88,131 @@ pub fn gflags(attr: TokenStream, item: TokenStream) -> TokenStream {
     let gen = gflags_attribute::impl_gflags(&input, &gtype_name);
     gen.into()
 }
+
+/// Derive macro for using a struct holding several reference-counted fields
+/// as a single weak capture in [`clone!`].
+///
+/// This generates a `{StructName}Weak` struct with the same fields, each
+/// replaced by its weak counterpart, and implements [`Downgrade`] for the
+/// struct and [`Upgrade`] for the weak struct. Upgrading succeeds only if
+/// all fields can be upgraded.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use] extern crate glib;
+/// use glib::prelude::*;
+/// use glib::clone::Upgrade;
+/// use std::rc::Rc;
+///
+/// #[derive(Clone, Downgrade)]
+/// struct State {
+///     name: Rc<String>,
+///     object: glib::Object,
+/// }
+///
+/// let state = State {
+///     name: Rc::new(String::from("state")),
+///     object: glib::Object::new(glib::Object::static_type(), &[]).unwrap(),
+/// };
+///
+/// let closure = clone!(@weak state => move || {
+///     assert_eq!(*state.name, "state");
+/// });
+/// closure();
+/// ```
+///
+/// [`clone!`]: macro.clone.html
+/// [`Downgrade`]: clone/trait.Downgrade.html
+/// [`Upgrade`]: clone/trait.Upgrade.html
+#[proc_macro_derive(Downgrade)]
+#[proc_macro_error]
+pub fn downgrade_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = downgrade_derive::impl_downgrade(&input);
+    gen.into()
+}
+
+/// Derive macro for implementing [`ErrorDomain`] for an enum, which defines a
+/// new `GError` domain.
+///
+/// The domain name has to be passed with the `#[error_domain(name = "...")]`
+/// attribute. The error codes are the discriminants of the variants. If the
+/// enum has a `Failed` variant, unknown codes are mapped to it.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use] extern crate glib;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, ErrorDomain)]
+/// #[error_domain(name = "my-error")]
+/// enum MyError {
+///     NotFound,
+///     Failed,
+/// }
+///
+/// let err = glib::Error::new(MyError::NotFound, "Not found");
+/// assert_eq!(err.kind::<MyError>(), Some(MyError::NotFound));
+/// ```
+///
+/// [`ErrorDomain`]: error/trait.ErrorDomain.html
+#[proc_macro_derive(ErrorDomain, attributes(error_domain))]
+#[proc_macro_error]
+pub fn error_domain_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = error_domain_derive::impl_error_domain(&input);
+    gen.into()
+}
+
+/// Derive macro for implementing [`OptionEntries`] for a struct, which defines
+/// a command line option for each field.
+///
+/// The long name of an option is the field name with underscores replaced by
+/// dashes, and its default value is the value of the field when adding the
+/// options. The field type has to implement [`OptionType`], which determines
+/// the type of the argument.
+///
+/// The options can be customized with `#[option(...)]` attributes on the fields:
+///
+/// * `long = "name"`: the long name of the option.
+/// * `short = 'c'`: the short name of the option.
+/// * `description = "..."`: the description shown in the `--help` output.
+/// * `arg_description = "..."`: the placeholder for the argument in the
+///   `--help` output.
+/// * `hidden`: hides the option from the `--help` output.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use] extern crate glib;
+/// use glib::OptionEntries;
+/// use std::path::PathBuf;
+///
+/// #[derive(Default, OptionEntries)]
+/// struct Args {
+///     #[option(short = 'v', description = "Print more output")]
+///     verbose: bool,
+///     #[option(arg_description = "N")]
+///     count: i32,
+///     output_file: Option<PathBuf>,
+/// }
+///
+/// let mut args = Args::default();
+/// let rest = args
+///     .parse_args(Some("FILE"), &["prog", "-v", "--output-file=out.txt", "in.txt"])
+///     .unwrap();
+/// assert_eq!(rest, ["prog", "in.txt"]);
+/// assert!(args.verbose);
+/// assert_eq!(args.output_file, Some(PathBuf::from("out.txt")));
+/// ```
+///
+/// [`OptionEntries`]: trait.OptionEntries.html
+/// [`OptionType`]: trait.OptionType.html
+#[proc_macro_derive(OptionEntries, attributes(option))]
+#[proc_macro_error]
+pub fn option_entries_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = option_entries_derive::impl_option_entries(&input);
+    gen.into()
+}
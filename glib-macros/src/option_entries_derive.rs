@@ -0,0 +1,119 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, abort_call_site};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, Fields, Lit, Meta, NestedMeta};
+
+use crate::utils::{crate_ident_new, find_attribute_meta};
+
+#[derive(Default)]
+struct FieldAttributes {
+    long: Option<String>,
+    short: Option<char>,
+    description: Option<String>,
+    arg_description: Option<String>,
+    hidden: bool,
+}
+
+// Parse field attributes such as:
+// #[option(short = 'v', description = "Be verbose")]
+fn parse_field_attributes(field: &syn::Field) -> FieldAttributes {
+    let mut attrs = FieldAttributes::default();
+
+    let meta = match find_attribute_meta(&field.attrs, "option") {
+        Ok(Some(meta)) => meta,
+        Ok(None) => return attrs,
+        Err(e) => abort!(field, "{}", e),
+    };
+
+    for nested in &meta.nested {
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hidden") => attrs.hidden = true,
+            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                let name = match nv.path.get_ident() {
+                    Some(ident) => ident.to_string(),
+                    None => abort!(nv, "Unknown option meta"),
+                };
+                match (name.as_str(), &nv.lit) {
+                    ("long", Lit::Str(s)) => attrs.long = Some(s.value()),
+                    ("short", Lit::Char(c)) => attrs.short = Some(c.value()),
+                    ("description", Lit::Str(s)) => attrs.description = Some(s.value()),
+                    ("arg_description", Lit::Str(s)) => attrs.arg_description = Some(s.value()),
+                    _ => abort!(nv, "Unknown option meta {}", name),
+                }
+            }
+            _ => abort!(nested, "Unknown option meta"),
+        }
+    }
+
+    attrs
+}
+
+pub fn impl_option_entries(input: &syn::DeriveInput) -> TokenStream {
+    let name = &input.ident;
+
+    let fields = match input.data {
+        Data::Struct(ref s) => match s.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => abort_call_site!("OptionEntries only supports structs with named fields"),
+        },
+        _ => abort_call_site!("OptionEntries only supports structs"),
+    };
+
+    let crate_ident = crate_ident_new();
+
+    let mut add_entries = Vec::new();
+    let mut update_from = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let attrs = parse_field_attributes(field);
+
+        let long_name = attrs
+            .long
+            .unwrap_or_else(|| ident.to_string().replace('_', "-"));
+
+        let short_name = attrs.short.map(|c| quote! { .short_name(#c) });
+        let description = attrs.description.map(|d| quote! { .description(#d) });
+        let arg_description = attrs
+            .arg_description
+            .map(|d| quote! { .arg_description(#d) });
+        let flags = if attrs.hidden {
+            Some(quote! { .flags(#crate_ident::OptionFlags::HIDDEN) })
+        } else {
+            None
+        };
+
+        add_entries.push(quote_spanned! {field.span()=>
+            context.add_entry(
+                #crate_ident::OptionEntry::new(
+                    #long_name,
+                    #crate_ident::OptionType::to_option_value(&self.#ident),
+                )
+                #short_name
+                #description
+                #arg_description
+                #flags
+            );
+        });
+        update_from.push(quote_spanned! {field.span()=>
+            self.#ident = #crate_ident::OptionType::from_option_value(
+                context.value(#long_name).unwrap(),
+            );
+        });
+    }
+
+    quote! {
+        impl #crate_ident::OptionEntries for #name {
+            fn add_entries(&self, context: &mut #crate_ident::OptionContext) {
+                #(#add_entries)*
+            }
+
+            fn update_from(&mut self, context: &#crate_ident::OptionContext) {
+                #(#update_from)*
+            }
+        }
+    }
+}
@@ -82,6 +82,23 @@ pub fn parse_type_name(input: &DeriveInput, attr_name: &str) -> Result<String> {
     }
 }
 
+// Parse attribute such as:
+// #[error_domain(name = "my-error-domain")]
+pub fn parse_name(input: &DeriveInput, attr_name: &str) -> Result<String> {
+    let meta = match find_attribute_meta(&input.attrs, attr_name)? {
+        Some(meta) => meta,
+        _ => bail!("Missing '{}' attribute", attr_name),
+    };
+
+    let meta = match find_nested_meta(&meta, "name") {
+        Some(meta) => meta,
+        _ => bail!("Missing meta 'name'"),
+    };
+
+    let (_, value) = parse_attribute(&meta)?;
+    Ok(value)
+}
+
 #[derive(Debug)]
 pub enum ItemAttribute {
     Name(String),
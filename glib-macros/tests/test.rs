@@ -2,10 +2,12 @@
 // See the COPYRIGHT file at the top-level directory of this distribution.
 // Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
 
-use ::glib_macros::{gflags, GBoxed, GEnum};
+use ::glib_macros::{gflags, Downgrade, ErrorDomain, GBoxed, GEnum, OptionEntries};
+use glib::clone::{Downgrade as _, Upgrade};
 use glib::prelude::*;
 use glib::subclass::prelude::*;
 use glib::translate::{FromGlib, ToGlib};
+use glib::OptionEntries as _;
 
 #[test]
 fn derive_genum() {
@@ -158,3 +160,185 @@ fn attr_gflags() {
     assert!(e.get_value_by_nick("ab").is_none());
     assert!(e.get_value_by_nick("c").is_some());
 }
+
+#[test]
+fn clone_async() {
+    use glib::clone;
+    use std::rc::Rc;
+
+    let c = glib::MainContext::new();
+
+    let v = Rc::new(1);
+    let future = clone!(@weak v => @default-return 0, async move { *v + 1 });
+    assert_eq!(c.block_on(future), 2);
+
+    let future = clone!(@strong v as w => async move { *w + 2 });
+    assert_eq!(c.block_on(future), 3);
+
+    let closure = clone!(@weak v => @default-return 0, move |x: i32| async move { *v + x });
+    assert_eq!(c.block_on(closure(2)), 3);
+    assert_eq!(c.block_on(closure(3)), 4);
+
+    // The weak reference is only upgraded once the async block is polled
+    let future = clone!(@weak v => @default-return 0, async move { *v });
+    let pending = closure(1);
+    drop(v);
+    assert_eq!(c.block_on(future), 0);
+    assert_eq!(c.block_on(pending), 0);
+    assert_eq!(c.block_on(closure(1)), 0);
+}
+
+#[test]
+fn derive_downgrade() {
+    use std::rc::Rc;
+
+    #[derive(Clone, Downgrade)]
+    struct NamedState {
+        name: Rc<String>,
+        object: glib::Object,
+    }
+
+    #[derive(Clone, Downgrade)]
+    struct TupleState(Rc<u32>, glib::Object);
+
+    #[derive(Downgrade)]
+    struct UnitState;
+
+    let object = glib::Object::new(glib::Object::static_type(), &[]).unwrap();
+    let state = NamedState {
+        name: Rc::new(String::from("state")),
+        object: object.clone(),
+    };
+    let weak = state.downgrade();
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(*upgraded.name, "state");
+    assert_eq!(upgraded.object, object);
+    drop(upgraded);
+
+    let closure = glib::clone!(@weak state => @default-return false, move || {
+        *state.name == "state"
+    });
+    assert!(closure());
+
+    let c = glib::MainContext::new();
+    let async_closure = glib::clone!(@weak state => @default-return false, move || async move {
+        *state.name == "state"
+    });
+    assert!(c.block_on(async_closure()));
+
+    let weak_clone = weak.clone();
+    drop(state);
+    assert!(weak.upgrade().is_none());
+    assert!(weak_clone.upgrade().is_none());
+    assert!(!closure());
+    assert!(!c.block_on(async_closure()));
+
+    let tuple = TupleState(Rc::new(42), object);
+    let weak = tuple.downgrade();
+    assert_eq!(*weak.upgrade().unwrap().0, 42);
+    drop(tuple);
+    assert!(weak.upgrade().is_none());
+
+    assert!(UnitState.downgrade().upgrade().is_some());
+}
+
+#[test]
+fn derive_error_domain() {
+    #[derive(Debug, Eq, PartialEq, Clone, Copy, ErrorDomain)]
+    #[error_domain(name = "test-error-domain")]
+    enum TestError {
+        Invalid,
+        Bad = 5,
+        Failed,
+    }
+
+    assert_eq!(
+        <TestError as glib::error::ErrorDomain>::domain(),
+        glib::Quark::from_string("test-error-domain")
+    );
+
+    let err = glib::Error::new(TestError::Bad, "oh no");
+    assert!(err.is::<TestError>());
+    assert_eq!(err.kind::<TestError>(), Some(TestError::Bad));
+    assert_eq!(err.to_string(), "oh no");
+
+    let err = glib::Error::new(glib::FileError::Exist, "oh no");
+    assert_eq!(err.kind::<TestError>(), None);
+
+    assert_eq!(
+        <TestError as glib::error::ErrorDomain>::from(0),
+        Some(TestError::Invalid)
+    );
+    assert_eq!(
+        <TestError as glib::error::ErrorDomain>::from(6),
+        Some(TestError::Failed)
+    );
+    assert_eq!(
+        <TestError as glib::error::ErrorDomain>::from(42),
+        Some(TestError::Failed)
+    );
+}
+
+#[test]
+fn derive_option_entries() {
+    use std::path::PathBuf;
+
+    #[derive(Debug, Default, PartialEq, OptionEntries)]
+    struct Args {
+        #[option(short = 'v', description = "Be verbose")]
+        verbose: bool,
+        #[option(long = "num", arg_description = "N")]
+        count: i32,
+        name: String,
+        output_file: Option<PathBuf>,
+        #[option(short = 'I')]
+        include: Vec<String>,
+        #[option(hidden)]
+        debug: bool,
+    }
+
+    let mut args = Args {
+        count: 1,
+        name: String::from("default"),
+        ..Default::default()
+    };
+    let rest = args
+        .parse_args(
+            None,
+            &[
+                "prog",
+                "-v",
+                "--output-file",
+                "out.txt",
+                "-I",
+                "a",
+                "arg",
+                "-I",
+                "b",
+            ],
+        )
+        .unwrap();
+    assert_eq!(rest, ["prog", "arg"]);
+    assert_eq!(
+        args,
+        Args {
+            verbose: true,
+            count: 1,
+            name: String::from("default"),
+            output_file: Some(PathBuf::from("out.txt")),
+            include: vec![String::from("a"), String::from("b")],
+            debug: false,
+        }
+    );
+
+    let mut context = glib::OptionContext::new(None);
+    context.add_entries(&Args::default());
+    let help = context.help(true);
+    assert!(help.contains("-v, --verbose"));
+    assert!(help.contains("--num=N"));
+    assert!(!help.contains("--debug"));
+
+    assert!(Args::default()
+        .parse_args(None, &["prog", "--num=x"])
+        .is_err());
+}
@@ -0,0 +1,380 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! # Examples
+//!
+//! ```
+//! use glib::Array;
+//!
+//! let mut arr = Array::new();
+//! arr.push(1u32);
+//! arr.extend_from_slice(&[2, 3]);
+//! assert_eq!(arr[1], 2);
+//! assert_eq!(&arr[..], &[1, 2, 3]);
+//! ```
+
+use glib_sys;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+use std::vec;
+use translate::*;
+
+/// A growable array of `Copy` elements backed by a `GArray`.
+///
+/// Unlike [`ByteArray`](struct.ByteArray.html) the array is never shared: converting from or to
+/// the C representation with transfer none respectively full copies the elements.
+pub struct Array<T: Copy> {
+    ptr: ptr::NonNull<glib_sys::GArray>,
+    phantom: PhantomData<T>,
+}
+
+unsafe impl<T: Copy + Send> Send for Array<T> {}
+unsafe impl<T: Copy + Sync> Sync for Array<T> {}
+
+impl<T: Copy> Array<T> {
+    /// Creates a new empty array.
+    pub fn new() -> Array<T> {
+        Array::with_capacity(0)
+    }
+
+    /// Creates a new empty array with space for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Array<T> {
+        assert_ne!(
+            mem::size_of::<T>(),
+            0,
+            "zero-sized element types are not supported"
+        );
+        unsafe {
+            from_glib_full(glib_sys::g_array_sized_new(
+                glib_sys::GFALSE,
+                glib_sys::GFALSE,
+                mem::size_of::<T>().into_glib(),
+                capacity.into_glib(),
+            ))
+        }
+    }
+
+    /// Appends `val` to the end of the array.
+    pub fn push(&mut self, val: T) {
+        self.extend_from_slice(&[val]);
+    }
+
+    /// Appends all elements of `vals` to the end of the array.
+    pub fn extend_from_slice(&mut self, vals: &[T]) {
+        unsafe {
+            glib_sys::g_array_append_vals(
+                self.ptr.as_ptr(),
+                vals.as_ptr() as *const _,
+                vals.len().into_glib(),
+            );
+        }
+    }
+
+    /// Inserts `val` at position `index`, shifting all following elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, val: T) {
+        assert!(index <= self.len(), "index {} out of bounds", index);
+        unsafe {
+            glib_sys::g_array_insert_vals(
+                self.ptr.as_ptr(),
+                index.into_glib(),
+                &val as *const T as *const _,
+                1,
+            );
+        }
+    }
+
+    /// Removes and returns the element at position `index`, shifting all following elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        let val = self[index];
+        unsafe {
+            glib_sys::g_array_remove_index(self.ptr.as_ptr(), index.into_glib());
+        }
+        val
+    }
+
+    /// Shortens the array to `len` elements.
+    ///
+    /// Has no effect if `len` is greater than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            unsafe {
+                glib_sys::g_array_set_size(self.ptr.as_ptr(), len.into_glib());
+            }
+        }
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> usize {
+        unsafe { (*self.ptr.as_ptr()).len as usize }
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn into_raw(self) -> *mut glib_sys::GArray {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            let data = (*self.ptr.as_ptr()).data as *const T;
+            if data.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(data, self.len())
+            }
+        }
+    }
+
+    /// Returns the elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            let data = (*self.ptr.as_ptr()).data as *mut T;
+            if data.is_null() {
+                &mut []
+            } else {
+                slice::from_raw_parts_mut(data, self.len())
+            }
+        }
+    }
+}
+
+impl<T: Copy> Drop for Array<T> {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_array_unref(self.ptr.as_ptr()) }
+    }
+}
+
+impl<T: Copy> Default for Array<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy> Clone for Array<T> {
+    fn clone(&self) -> Self {
+        Array::from(self.as_slice())
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for Array<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Copy + PartialEq> PartialEq for Array<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Copy + Eq> Eq for Array<T> {}
+
+impl<T: Copy> Deref for Array<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Copy> DerefMut for Array<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Copy> AsRef<[T]> for Array<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Copy> Extend<T> for Array<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for Array<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T: Copy> FromIterator<T> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arr = Array::with_capacity(iter.size_hint().0);
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a Array<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a mut Array<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Copy> IntoIterator for Array<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> vec::IntoIter<T> {
+        Vec::from(self).into_iter()
+    }
+}
+
+impl<'a, T: Copy> From<&'a [T]> for Array<T> {
+    fn from(vals: &'a [T]) -> Self {
+        let mut arr = Array::with_capacity(vals.len());
+        arr.extend_from_slice(vals);
+        arr
+    }
+}
+
+impl<T: Copy> From<Vec<T>> for Array<T> {
+    fn from(vals: Vec<T>) -> Self {
+        Array::from(vals.as_slice())
+    }
+}
+
+impl<T: Copy> From<Array<T>> for Vec<T> {
+    fn from(arr: Array<T>) -> Self {
+        arr.as_slice().to_vec()
+    }
+}
+
+impl<T: Copy> GlibPtrDefault for Array<T> {
+    type GlibType = *mut glib_sys::GArray;
+}
+
+#[doc(hidden)]
+impl<'a, T: Copy> ToGlibPtr<'a, *mut glib_sys::GArray> for Array<T> {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut glib_sys::GArray, Self> {
+        Stash(self.ptr.as_ptr(), self)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut glib_sys::GArray {
+        self.clone().into_raw()
+    }
+}
+
+#[doc(hidden)]
+impl<T: Copy> FromGlibPtrFull<*mut glib_sys::GArray> for Array<T> {
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut glib_sys::GArray) -> Self {
+        assert!(!ptr.is_null());
+        assert_eq!(
+            glib_sys::g_array_get_element_size(ptr) as usize,
+            mem::size_of::<T>()
+        );
+        Array {
+            ptr: ptr::NonNull::new_unchecked(ptr),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T: Copy> FromGlibPtrNone<*mut glib_sys::GArray> for Array<T> {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut glib_sys::GArray) -> Self {
+        assert!(!ptr.is_null());
+        let borrowed: Borrowed<Array<T>> = from_glib_borrow(ptr);
+        Array::from(borrowed.as_slice())
+    }
+}
+
+#[doc(hidden)]
+impl<T: Copy> FromGlibPtrBorrow<*mut glib_sys::GArray> for Array<T> {
+    #[inline]
+    unsafe fn from_glib_borrow(ptr: *mut glib_sys::GArray) -> Borrowed<Self> {
+        Borrowed::new(from_glib_full(ptr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array() {
+        let mut arr: Array<i32> = (1..4).collect();
+        assert_eq!(arr.len(), 3);
+        arr.push(4);
+        arr.insert(0, 0);
+        assert_eq!(&arr[..], &[0, 1, 2, 3, 4]);
+        assert_eq!(arr.remove(2), 2);
+        arr[0] = 10;
+        assert_eq!(arr, Array::from(vec![10, 1, 3, 4]));
+        for val in &mut arr {
+            *val += 1;
+        }
+        arr.extend(&[5]);
+        assert_eq!(arr.into_iter().collect::<Vec<_>>(), [11, 2, 4, 5, 5]);
+
+        let mut arr: Array<i32> = vec![10, 1, 3, 4].into();
+        arr.truncate(2);
+        assert_eq!(Vec::from(arr.clone()), vec![10, 1]);
+        arr.clear();
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn test_array_translate() {
+        let arr = Array::from(&[1.0f64, 2.0][..]);
+        let ptr: *mut glib_sys::GArray = arr.to_glib_full();
+        let copy: Array<f64> = unsafe { from_glib_none(ptr) };
+        unsafe { glib_sys::g_array_unref(ptr) };
+        assert_eq!(copy, arr);
+
+        let v: Vec<f64> =
+            unsafe { FromGlibPtrArrayContainerAsVec::from_glib_none_as_vec(arr.to_glib_none().0) };
+        assert_eq!(v, [1.0, 2.0]);
+    }
+}
@@ -71,6 +71,101 @@ impl FromGlib<glib_sys::GChecksumType> for ChecksumType {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum ConvertError {
+    NoConversion,
+    IllegalSequence,
+    Failed,
+    PartialInput,
+    BadUri,
+    NotAbsolutePath,
+    NoMemory,
+    EmbeddedNul,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ConvertError::{}",
+            match *self {
+                ConvertError::NoConversion => "NoConversion",
+                ConvertError::IllegalSequence => "IllegalSequence",
+                ConvertError::Failed => "Failed",
+                ConvertError::PartialInput => "PartialInput",
+                ConvertError::BadUri => "BadUri",
+                ConvertError::NotAbsolutePath => "NotAbsolutePath",
+                ConvertError::NoMemory => "NoMemory",
+                ConvertError::EmbeddedNul => "EmbeddedNul",
+                _ => "Unknown",
+            }
+        )
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for ConvertError {
+    type GlibType = glib_sys::GConvertError;
+
+    fn to_glib(&self) -> glib_sys::GConvertError {
+        match *self {
+            ConvertError::NoConversion => glib_sys::G_CONVERT_ERROR_NO_CONVERSION,
+            ConvertError::IllegalSequence => glib_sys::G_CONVERT_ERROR_ILLEGAL_SEQUENCE,
+            ConvertError::Failed => glib_sys::G_CONVERT_ERROR_FAILED,
+            ConvertError::PartialInput => glib_sys::G_CONVERT_ERROR_PARTIAL_INPUT,
+            ConvertError::BadUri => glib_sys::G_CONVERT_ERROR_BAD_URI,
+            ConvertError::NotAbsolutePath => glib_sys::G_CONVERT_ERROR_NOT_ABSOLUTE_PATH,
+            ConvertError::NoMemory => glib_sys::G_CONVERT_ERROR_NO_MEMORY,
+            ConvertError::EmbeddedNul => glib_sys::G_CONVERT_ERROR_EMBEDDED_NUL,
+            ConvertError::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GConvertError> for ConvertError {
+    fn from_glib(value: glib_sys::GConvertError) -> Self {
+        match value {
+            0 => ConvertError::NoConversion,
+            1 => ConvertError::IllegalSequence,
+            2 => ConvertError::Failed,
+            3 => ConvertError::PartialInput,
+            4 => ConvertError::BadUri,
+            5 => ConvertError::NotAbsolutePath,
+            6 => ConvertError::NoMemory,
+            7 => ConvertError::EmbeddedNul,
+            value => ConvertError::__Unknown(value),
+        }
+    }
+}
+
+impl ErrorDomain for ConvertError {
+    fn domain() -> Quark {
+        unsafe { from_glib(glib_sys::g_convert_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(ConvertError::NoConversion),
+            1 => Some(ConvertError::IllegalSequence),
+            2 => Some(ConvertError::Failed),
+            3 => Some(ConvertError::PartialInput),
+            4 => Some(ConvertError::BadUri),
+            5 => Some(ConvertError::NotAbsolutePath),
+            6 => Some(ConvertError::NoMemory),
+            7 => Some(ConvertError::EmbeddedNul),
+            value => Some(ConvertError::__Unknown(value)),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 pub enum DateMonth {
@@ -318,6 +413,151 @@ impl ErrorDomain for KeyFileError {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum MarkupError {
+    BadUtf8,
+    Empty,
+    Parse,
+    UnknownElement,
+    UnknownAttribute,
+    InvalidContent,
+    MissingAttribute,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MarkupError::{}",
+            match *self {
+                MarkupError::BadUtf8 => "BadUtf8",
+                MarkupError::Empty => "Empty",
+                MarkupError::Parse => "Parse",
+                MarkupError::UnknownElement => "UnknownElement",
+                MarkupError::UnknownAttribute => "UnknownAttribute",
+                MarkupError::InvalidContent => "InvalidContent",
+                MarkupError::MissingAttribute => "MissingAttribute",
+                _ => "Unknown",
+            }
+        )
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MarkupError {
+    type GlibType = glib_sys::GMarkupError;
+
+    fn to_glib(&self) -> glib_sys::GMarkupError {
+        match *self {
+            MarkupError::BadUtf8 => glib_sys::G_MARKUP_ERROR_BAD_UTF8,
+            MarkupError::Empty => glib_sys::G_MARKUP_ERROR_EMPTY,
+            MarkupError::Parse => glib_sys::G_MARKUP_ERROR_PARSE,
+            MarkupError::UnknownElement => glib_sys::G_MARKUP_ERROR_UNKNOWN_ELEMENT,
+            MarkupError::UnknownAttribute => glib_sys::G_MARKUP_ERROR_UNKNOWN_ATTRIBUTE,
+            MarkupError::InvalidContent => glib_sys::G_MARKUP_ERROR_INVALID_CONTENT,
+            MarkupError::MissingAttribute => glib_sys::G_MARKUP_ERROR_MISSING_ATTRIBUTE,
+            MarkupError::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GMarkupError> for MarkupError {
+    fn from_glib(value: glib_sys::GMarkupError) -> Self {
+        match value {
+            0 => MarkupError::BadUtf8,
+            1 => MarkupError::Empty,
+            2 => MarkupError::Parse,
+            3 => MarkupError::UnknownElement,
+            4 => MarkupError::UnknownAttribute,
+            5 => MarkupError::InvalidContent,
+            6 => MarkupError::MissingAttribute,
+            value => MarkupError::__Unknown(value),
+        }
+    }
+}
+
+impl ErrorDomain for MarkupError {
+    fn domain() -> Quark {
+        unsafe { from_glib(glib_sys::g_markup_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        self.to_glib()
+    }
+
+    fn from(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(MarkupError::BadUtf8),
+            1 => Some(MarkupError::Empty),
+            2 => Some(MarkupError::Parse),
+            3 => Some(MarkupError::UnknownElement),
+            4 => Some(MarkupError::UnknownAttribute),
+            5 => Some(MarkupError::InvalidContent),
+            6 => Some(MarkupError::MissingAttribute),
+            value => Some(MarkupError::__Unknown(value)),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum NormalizeMode {
+    Default,
+    DefaultCompose,
+    All,
+    AllCompose,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for NormalizeMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NormalizeMode::{}",
+            match *self {
+                NormalizeMode::Default => "Default",
+                NormalizeMode::DefaultCompose => "DefaultCompose",
+                NormalizeMode::All => "All",
+                NormalizeMode::AllCompose => "AllCompose",
+                _ => "Unknown",
+            }
+        )
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for NormalizeMode {
+    type GlibType = glib_sys::GNormalizeMode;
+
+    fn to_glib(&self) -> glib_sys::GNormalizeMode {
+        match *self {
+            NormalizeMode::Default => glib_sys::G_NORMALIZE_DEFAULT,
+            NormalizeMode::DefaultCompose => glib_sys::G_NORMALIZE_DEFAULT_COMPOSE,
+            NormalizeMode::All => glib_sys::G_NORMALIZE_ALL,
+            NormalizeMode::AllCompose => glib_sys::G_NORMALIZE_ALL_COMPOSE,
+            NormalizeMode::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GNormalizeMode> for NormalizeMode {
+    fn from_glib(value: glib_sys::GNormalizeMode) -> Self {
+        match value {
+            0 => NormalizeMode::Default,
+            1 => NormalizeMode::DefaultCompose,
+            2 => NormalizeMode::All,
+            3 => NormalizeMode::AllCompose,
+            value => NormalizeMode::__Unknown(value),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 pub enum OptionArg {
@@ -495,6 +735,964 @@ impl FromGlib<glib_sys::GTimeType> for TimeType {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum UnicodeScript {
+    InvalidCode,
+    Common,
+    Inherited,
+    Arabic,
+    Armenian,
+    Bengali,
+    Bopomofo,
+    Cherokee,
+    Coptic,
+    Cyrillic,
+    Deseret,
+    Devanagari,
+    Ethiopic,
+    Georgian,
+    Gothic,
+    Greek,
+    Gujarati,
+    Gurmukhi,
+    Han,
+    Hangul,
+    Hebrew,
+    Hiragana,
+    Kannada,
+    Katakana,
+    Khmer,
+    Lao,
+    Latin,
+    Malayalam,
+    Mongolian,
+    Myanmar,
+    Ogham,
+    OldItalic,
+    Oriya,
+    Runic,
+    Sinhala,
+    Syriac,
+    Tamil,
+    Telugu,
+    Thaana,
+    Thai,
+    Tibetan,
+    CanadianAboriginal,
+    Yi,
+    Tagalog,
+    Hanunoo,
+    Buhid,
+    Tagbanwa,
+    Braille,
+    Cypriot,
+    Limbu,
+    Osmanya,
+    Shavian,
+    LinearB,
+    TaiLe,
+    Ugaritic,
+    NewTaiLue,
+    Buginese,
+    Glagolitic,
+    Tifinagh,
+    SylotiNagri,
+    OldPersian,
+    Kharoshthi,
+    Unknown,
+    Balinese,
+    Cuneiform,
+    Phoenician,
+    PhagsPa,
+    Nko,
+    KayahLi,
+    Lepcha,
+    Rejang,
+    Sundanese,
+    Saurashtra,
+    Cham,
+    OlChiki,
+    Vai,
+    Carian,
+    Lycian,
+    Lydian,
+    Avestan,
+    Bamum,
+    EgyptianHieroglyphs,
+    ImperialAramaic,
+    InscriptionalPahlavi,
+    InscriptionalParthian,
+    Javanese,
+    Kaithi,
+    Lisu,
+    MeeteiMayek,
+    OldSouthArabian,
+    OldTurkic,
+    Samaritan,
+    TaiTham,
+    TaiViet,
+    Batak,
+    Brahmi,
+    Mandaic,
+    Chakma,
+    MeroiticCursive,
+    MeroiticHieroglyphs,
+    Miao,
+    Sharada,
+    SoraSompeng,
+    Takri,
+    BassaVah,
+    CaucasianAlbanian,
+    Duployan,
+    Elbasan,
+    Grantha,
+    Khojki,
+    Khudawadi,
+    LinearA,
+    Mahajani,
+    Manichaean,
+    MendeKikakui,
+    Modi,
+    Mro,
+    Nabataean,
+    OldNorthArabian,
+    OldPermic,
+    PahawhHmong,
+    Palmyrene,
+    PauCinHau,
+    PsalterPahlavi,
+    Siddham,
+    Tirhuta,
+    WarangCiti,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    Ahom,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    AnatolianHieroglyphs,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    Hatran,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    Multani,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    OldHungarian,
+    #[cfg(any(feature = "v2_48", feature = "dox"))]
+    Signwriting,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Adlam,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Bhaiksuki,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Marchen,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Newa,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Osage,
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    Tangut,
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    MasaramGondi,
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    Nushu,
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    Soyombo,
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    ZanabazarSquare,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    Dogra,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    GunjalaGondi,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    HanifiRohingya,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    Makasar,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    Medefaidrin,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    OldSogdian,
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    Sogdian,
+    #[cfg(any(feature = "v2_62", feature = "dox"))]
+    Elymaic,
+    #[cfg(any(feature = "v2_62", feature = "dox"))]
+    Nandinagari,
+    #[cfg(any(feature = "v2_62", feature = "dox"))]
+    NyiakengPuachueHmong,
+    #[cfg(any(feature = "v2_62", feature = "dox"))]
+    Wancho,
+    #[cfg(any(feature = "v2_66", feature = "dox"))]
+    Chorasmian,
+    #[cfg(any(feature = "v2_66", feature = "dox"))]
+    DivesAkuru,
+    #[cfg(any(feature = "v2_66", feature = "dox"))]
+    KhitanSmallScript,
+    #[cfg(any(feature = "v2_66", feature = "dox"))]
+    Yezidi,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for UnicodeScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UnicodeScript::{}",
+            match *self {
+                UnicodeScript::InvalidCode => "InvalidCode",
+                UnicodeScript::Common => "Common",
+                UnicodeScript::Inherited => "Inherited",
+                UnicodeScript::Arabic => "Arabic",
+                UnicodeScript::Armenian => "Armenian",
+                UnicodeScript::Bengali => "Bengali",
+                UnicodeScript::Bopomofo => "Bopomofo",
+                UnicodeScript::Cherokee => "Cherokee",
+                UnicodeScript::Coptic => "Coptic",
+                UnicodeScript::Cyrillic => "Cyrillic",
+                UnicodeScript::Deseret => "Deseret",
+                UnicodeScript::Devanagari => "Devanagari",
+                UnicodeScript::Ethiopic => "Ethiopic",
+                UnicodeScript::Georgian => "Georgian",
+                UnicodeScript::Gothic => "Gothic",
+                UnicodeScript::Greek => "Greek",
+                UnicodeScript::Gujarati => "Gujarati",
+                UnicodeScript::Gurmukhi => "Gurmukhi",
+                UnicodeScript::Han => "Han",
+                UnicodeScript::Hangul => "Hangul",
+                UnicodeScript::Hebrew => "Hebrew",
+                UnicodeScript::Hiragana => "Hiragana",
+                UnicodeScript::Kannada => "Kannada",
+                UnicodeScript::Katakana => "Katakana",
+                UnicodeScript::Khmer => "Khmer",
+                UnicodeScript::Lao => "Lao",
+                UnicodeScript::Latin => "Latin",
+                UnicodeScript::Malayalam => "Malayalam",
+                UnicodeScript::Mongolian => "Mongolian",
+                UnicodeScript::Myanmar => "Myanmar",
+                UnicodeScript::Ogham => "Ogham",
+                UnicodeScript::OldItalic => "OldItalic",
+                UnicodeScript::Oriya => "Oriya",
+                UnicodeScript::Runic => "Runic",
+                UnicodeScript::Sinhala => "Sinhala",
+                UnicodeScript::Syriac => "Syriac",
+                UnicodeScript::Tamil => "Tamil",
+                UnicodeScript::Telugu => "Telugu",
+                UnicodeScript::Thaana => "Thaana",
+                UnicodeScript::Thai => "Thai",
+                UnicodeScript::Tibetan => "Tibetan",
+                UnicodeScript::CanadianAboriginal => "CanadianAboriginal",
+                UnicodeScript::Yi => "Yi",
+                UnicodeScript::Tagalog => "Tagalog",
+                UnicodeScript::Hanunoo => "Hanunoo",
+                UnicodeScript::Buhid => "Buhid",
+                UnicodeScript::Tagbanwa => "Tagbanwa",
+                UnicodeScript::Braille => "Braille",
+                UnicodeScript::Cypriot => "Cypriot",
+                UnicodeScript::Limbu => "Limbu",
+                UnicodeScript::Osmanya => "Osmanya",
+                UnicodeScript::Shavian => "Shavian",
+                UnicodeScript::LinearB => "LinearB",
+                UnicodeScript::TaiLe => "TaiLe",
+                UnicodeScript::Ugaritic => "Ugaritic",
+                UnicodeScript::NewTaiLue => "NewTaiLue",
+                UnicodeScript::Buginese => "Buginese",
+                UnicodeScript::Glagolitic => "Glagolitic",
+                UnicodeScript::Tifinagh => "Tifinagh",
+                UnicodeScript::SylotiNagri => "SylotiNagri",
+                UnicodeScript::OldPersian => "OldPersian",
+                UnicodeScript::Kharoshthi => "Kharoshthi",
+                UnicodeScript::Unknown => "Unknown",
+                UnicodeScript::Balinese => "Balinese",
+                UnicodeScript::Cuneiform => "Cuneiform",
+                UnicodeScript::Phoenician => "Phoenician",
+                UnicodeScript::PhagsPa => "PhagsPa",
+                UnicodeScript::Nko => "Nko",
+                UnicodeScript::KayahLi => "KayahLi",
+                UnicodeScript::Lepcha => "Lepcha",
+                UnicodeScript::Rejang => "Rejang",
+                UnicodeScript::Sundanese => "Sundanese",
+                UnicodeScript::Saurashtra => "Saurashtra",
+                UnicodeScript::Cham => "Cham",
+                UnicodeScript::OlChiki => "OlChiki",
+                UnicodeScript::Vai => "Vai",
+                UnicodeScript::Carian => "Carian",
+                UnicodeScript::Lycian => "Lycian",
+                UnicodeScript::Lydian => "Lydian",
+                UnicodeScript::Avestan => "Avestan",
+                UnicodeScript::Bamum => "Bamum",
+                UnicodeScript::EgyptianHieroglyphs => "EgyptianHieroglyphs",
+                UnicodeScript::ImperialAramaic => "ImperialAramaic",
+                UnicodeScript::InscriptionalPahlavi => "InscriptionalPahlavi",
+                UnicodeScript::InscriptionalParthian => "InscriptionalParthian",
+                UnicodeScript::Javanese => "Javanese",
+                UnicodeScript::Kaithi => "Kaithi",
+                UnicodeScript::Lisu => "Lisu",
+                UnicodeScript::MeeteiMayek => "MeeteiMayek",
+                UnicodeScript::OldSouthArabian => "OldSouthArabian",
+                UnicodeScript::OldTurkic => "OldTurkic",
+                UnicodeScript::Samaritan => "Samaritan",
+                UnicodeScript::TaiTham => "TaiTham",
+                UnicodeScript::TaiViet => "TaiViet",
+                UnicodeScript::Batak => "Batak",
+                UnicodeScript::Brahmi => "Brahmi",
+                UnicodeScript::Mandaic => "Mandaic",
+                UnicodeScript::Chakma => "Chakma",
+                UnicodeScript::MeroiticCursive => "MeroiticCursive",
+                UnicodeScript::MeroiticHieroglyphs => "MeroiticHieroglyphs",
+                UnicodeScript::Miao => "Miao",
+                UnicodeScript::Sharada => "Sharada",
+                UnicodeScript::SoraSompeng => "SoraSompeng",
+                UnicodeScript::Takri => "Takri",
+                UnicodeScript::BassaVah => "BassaVah",
+                UnicodeScript::CaucasianAlbanian => "CaucasianAlbanian",
+                UnicodeScript::Duployan => "Duployan",
+                UnicodeScript::Elbasan => "Elbasan",
+                UnicodeScript::Grantha => "Grantha",
+                UnicodeScript::Khojki => "Khojki",
+                UnicodeScript::Khudawadi => "Khudawadi",
+                UnicodeScript::LinearA => "LinearA",
+                UnicodeScript::Mahajani => "Mahajani",
+                UnicodeScript::Manichaean => "Manichaean",
+                UnicodeScript::MendeKikakui => "MendeKikakui",
+                UnicodeScript::Modi => "Modi",
+                UnicodeScript::Mro => "Mro",
+                UnicodeScript::Nabataean => "Nabataean",
+                UnicodeScript::OldNorthArabian => "OldNorthArabian",
+                UnicodeScript::OldPermic => "OldPermic",
+                UnicodeScript::PahawhHmong => "PahawhHmong",
+                UnicodeScript::Palmyrene => "Palmyrene",
+                UnicodeScript::PauCinHau => "PauCinHau",
+                UnicodeScript::PsalterPahlavi => "PsalterPahlavi",
+                UnicodeScript::Siddham => "Siddham",
+                UnicodeScript::Tirhuta => "Tirhuta",
+                UnicodeScript::WarangCiti => "WarangCiti",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::Ahom => "Ahom",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::AnatolianHieroglyphs => "AnatolianHieroglyphs",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::Hatran => "Hatran",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::Multani => "Multani",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::OldHungarian => "OldHungarian",
+                #[cfg(any(feature = "v2_48", feature = "dox"))]
+                UnicodeScript::Signwriting => "Signwriting",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Adlam => "Adlam",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Bhaiksuki => "Bhaiksuki",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Marchen => "Marchen",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Newa => "Newa",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Osage => "Osage",
+                #[cfg(any(feature = "v2_50", feature = "dox"))]
+                UnicodeScript::Tangut => "Tangut",
+                #[cfg(any(feature = "v2_54", feature = "dox"))]
+                UnicodeScript::MasaramGondi => "MasaramGondi",
+                #[cfg(any(feature = "v2_54", feature = "dox"))]
+                UnicodeScript::Nushu => "Nushu",
+                #[cfg(any(feature = "v2_54", feature = "dox"))]
+                UnicodeScript::Soyombo => "Soyombo",
+                #[cfg(any(feature = "v2_54", feature = "dox"))]
+                UnicodeScript::ZanabazarSquare => "ZanabazarSquare",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::Dogra => "Dogra",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::GunjalaGondi => "GunjalaGondi",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::HanifiRohingya => "HanifiRohingya",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::Makasar => "Makasar",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::Medefaidrin => "Medefaidrin",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::OldSogdian => "OldSogdian",
+                #[cfg(any(feature = "v2_58", feature = "dox"))]
+                UnicodeScript::Sogdian => "Sogdian",
+                #[cfg(any(feature = "v2_62", feature = "dox"))]
+                UnicodeScript::Elymaic => "Elymaic",
+                #[cfg(any(feature = "v2_62", feature = "dox"))]
+                UnicodeScript::Nandinagari => "Nandinagari",
+                #[cfg(any(feature = "v2_62", feature = "dox"))]
+                UnicodeScript::NyiakengPuachueHmong => "NyiakengPuachueHmong",
+                #[cfg(any(feature = "v2_62", feature = "dox"))]
+                UnicodeScript::Wancho => "Wancho",
+                #[cfg(any(feature = "v2_66", feature = "dox"))]
+                UnicodeScript::Chorasmian => "Chorasmian",
+                #[cfg(any(feature = "v2_66", feature = "dox"))]
+                UnicodeScript::DivesAkuru => "DivesAkuru",
+                #[cfg(any(feature = "v2_66", feature = "dox"))]
+                UnicodeScript::KhitanSmallScript => "KhitanSmallScript",
+                #[cfg(any(feature = "v2_66", feature = "dox"))]
+                UnicodeScript::Yezidi => "Yezidi",
+                _ => "Unknown",
+            }
+        )
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for UnicodeScript {
+    type GlibType = glib_sys::GUnicodeScript;
+
+    fn to_glib(&self) -> glib_sys::GUnicodeScript {
+        match *self {
+            UnicodeScript::InvalidCode => glib_sys::G_UNICODE_SCRIPT_INVALID_CODE,
+            UnicodeScript::Common => glib_sys::G_UNICODE_SCRIPT_COMMON,
+            UnicodeScript::Inherited => glib_sys::G_UNICODE_SCRIPT_INHERITED,
+            UnicodeScript::Arabic => glib_sys::G_UNICODE_SCRIPT_ARABIC,
+            UnicodeScript::Armenian => glib_sys::G_UNICODE_SCRIPT_ARMENIAN,
+            UnicodeScript::Bengali => glib_sys::G_UNICODE_SCRIPT_BENGALI,
+            UnicodeScript::Bopomofo => glib_sys::G_UNICODE_SCRIPT_BOPOMOFO,
+            UnicodeScript::Cherokee => glib_sys::G_UNICODE_SCRIPT_CHEROKEE,
+            UnicodeScript::Coptic => glib_sys::G_UNICODE_SCRIPT_COPTIC,
+            UnicodeScript::Cyrillic => glib_sys::G_UNICODE_SCRIPT_CYRILLIC,
+            UnicodeScript::Deseret => glib_sys::G_UNICODE_SCRIPT_DESERET,
+            UnicodeScript::Devanagari => glib_sys::G_UNICODE_SCRIPT_DEVANAGARI,
+            UnicodeScript::Ethiopic => glib_sys::G_UNICODE_SCRIPT_ETHIOPIC,
+            UnicodeScript::Georgian => glib_sys::G_UNICODE_SCRIPT_GEORGIAN,
+            UnicodeScript::Gothic => glib_sys::G_UNICODE_SCRIPT_GOTHIC,
+            UnicodeScript::Greek => glib_sys::G_UNICODE_SCRIPT_GREEK,
+            UnicodeScript::Gujarati => glib_sys::G_UNICODE_SCRIPT_GUJARATI,
+            UnicodeScript::Gurmukhi => glib_sys::G_UNICODE_SCRIPT_GURMUKHI,
+            UnicodeScript::Han => glib_sys::G_UNICODE_SCRIPT_HAN,
+            UnicodeScript::Hangul => glib_sys::G_UNICODE_SCRIPT_HANGUL,
+            UnicodeScript::Hebrew => glib_sys::G_UNICODE_SCRIPT_HEBREW,
+            UnicodeScript::Hiragana => glib_sys::G_UNICODE_SCRIPT_HIRAGANA,
+            UnicodeScript::Kannada => glib_sys::G_UNICODE_SCRIPT_KANNADA,
+            UnicodeScript::Katakana => glib_sys::G_UNICODE_SCRIPT_KATAKANA,
+            UnicodeScript::Khmer => glib_sys::G_UNICODE_SCRIPT_KHMER,
+            UnicodeScript::Lao => glib_sys::G_UNICODE_SCRIPT_LAO,
+            UnicodeScript::Latin => glib_sys::G_UNICODE_SCRIPT_LATIN,
+            UnicodeScript::Malayalam => glib_sys::G_UNICODE_SCRIPT_MALAYALAM,
+            UnicodeScript::Mongolian => glib_sys::G_UNICODE_SCRIPT_MONGOLIAN,
+            UnicodeScript::Myanmar => glib_sys::G_UNICODE_SCRIPT_MYANMAR,
+            UnicodeScript::Ogham => glib_sys::G_UNICODE_SCRIPT_OGHAM,
+            UnicodeScript::OldItalic => glib_sys::G_UNICODE_SCRIPT_OLD_ITALIC,
+            UnicodeScript::Oriya => glib_sys::G_UNICODE_SCRIPT_ORIYA,
+            UnicodeScript::Runic => glib_sys::G_UNICODE_SCRIPT_RUNIC,
+            UnicodeScript::Sinhala => glib_sys::G_UNICODE_SCRIPT_SINHALA,
+            UnicodeScript::Syriac => glib_sys::G_UNICODE_SCRIPT_SYRIAC,
+            UnicodeScript::Tamil => glib_sys::G_UNICODE_SCRIPT_TAMIL,
+            UnicodeScript::Telugu => glib_sys::G_UNICODE_SCRIPT_TELUGU,
+            UnicodeScript::Thaana => glib_sys::G_UNICODE_SCRIPT_THAANA,
+            UnicodeScript::Thai => glib_sys::G_UNICODE_SCRIPT_THAI,
+            UnicodeScript::Tibetan => glib_sys::G_UNICODE_SCRIPT_TIBETAN,
+            UnicodeScript::CanadianAboriginal => glib_sys::G_UNICODE_SCRIPT_CANADIAN_ABORIGINAL,
+            UnicodeScript::Yi => glib_sys::G_UNICODE_SCRIPT_YI,
+            UnicodeScript::Tagalog => glib_sys::G_UNICODE_SCRIPT_TAGALOG,
+            UnicodeScript::Hanunoo => glib_sys::G_UNICODE_SCRIPT_HANUNOO,
+            UnicodeScript::Buhid => glib_sys::G_UNICODE_SCRIPT_BUHID,
+            UnicodeScript::Tagbanwa => glib_sys::G_UNICODE_SCRIPT_TAGBANWA,
+            UnicodeScript::Braille => glib_sys::G_UNICODE_SCRIPT_BRAILLE,
+            UnicodeScript::Cypriot => glib_sys::G_UNICODE_SCRIPT_CYPRIOT,
+            UnicodeScript::Limbu => glib_sys::G_UNICODE_SCRIPT_LIMBU,
+            UnicodeScript::Osmanya => glib_sys::G_UNICODE_SCRIPT_OSMANYA,
+            UnicodeScript::Shavian => glib_sys::G_UNICODE_SCRIPT_SHAVIAN,
+            UnicodeScript::LinearB => glib_sys::G_UNICODE_SCRIPT_LINEAR_B,
+            UnicodeScript::TaiLe => glib_sys::G_UNICODE_SCRIPT_TAI_LE,
+            UnicodeScript::Ugaritic => glib_sys::G_UNICODE_SCRIPT_UGARITIC,
+            UnicodeScript::NewTaiLue => glib_sys::G_UNICODE_SCRIPT_NEW_TAI_LUE,
+            UnicodeScript::Buginese => glib_sys::G_UNICODE_SCRIPT_BUGINESE,
+            UnicodeScript::Glagolitic => glib_sys::G_UNICODE_SCRIPT_GLAGOLITIC,
+            UnicodeScript::Tifinagh => glib_sys::G_UNICODE_SCRIPT_TIFINAGH,
+            UnicodeScript::SylotiNagri => glib_sys::G_UNICODE_SCRIPT_SYLOTI_NAGRI,
+            UnicodeScript::OldPersian => glib_sys::G_UNICODE_SCRIPT_OLD_PERSIAN,
+            UnicodeScript::Kharoshthi => glib_sys::G_UNICODE_SCRIPT_KHAROSHTHI,
+            UnicodeScript::Unknown => glib_sys::G_UNICODE_SCRIPT_UNKNOWN,
+            UnicodeScript::Balinese => glib_sys::G_UNICODE_SCRIPT_BALINESE,
+            UnicodeScript::Cuneiform => glib_sys::G_UNICODE_SCRIPT_CUNEIFORM,
+            UnicodeScript::Phoenician => glib_sys::G_UNICODE_SCRIPT_PHOENICIAN,
+            UnicodeScript::PhagsPa => glib_sys::G_UNICODE_SCRIPT_PHAGS_PA,
+            UnicodeScript::Nko => glib_sys::G_UNICODE_SCRIPT_NKO,
+            UnicodeScript::KayahLi => glib_sys::G_UNICODE_SCRIPT_KAYAH_LI,
+            UnicodeScript::Lepcha => glib_sys::G_UNICODE_SCRIPT_LEPCHA,
+            UnicodeScript::Rejang => glib_sys::G_UNICODE_SCRIPT_REJANG,
+            UnicodeScript::Sundanese => glib_sys::G_UNICODE_SCRIPT_SUNDANESE,
+            UnicodeScript::Saurashtra => glib_sys::G_UNICODE_SCRIPT_SAURASHTRA,
+            UnicodeScript::Cham => glib_sys::G_UNICODE_SCRIPT_CHAM,
+            UnicodeScript::OlChiki => glib_sys::G_UNICODE_SCRIPT_OL_CHIKI,
+            UnicodeScript::Vai => glib_sys::G_UNICODE_SCRIPT_VAI,
+            UnicodeScript::Carian => glib_sys::G_UNICODE_SCRIPT_CARIAN,
+            UnicodeScript::Lycian => glib_sys::G_UNICODE_SCRIPT_LYCIAN,
+            UnicodeScript::Lydian => glib_sys::G_UNICODE_SCRIPT_LYDIAN,
+            UnicodeScript::Avestan => glib_sys::G_UNICODE_SCRIPT_AVESTAN,
+            UnicodeScript::Bamum => glib_sys::G_UNICODE_SCRIPT_BAMUM,
+            UnicodeScript::EgyptianHieroglyphs => glib_sys::G_UNICODE_SCRIPT_EGYPTIAN_HIEROGLYPHS,
+            UnicodeScript::ImperialAramaic => glib_sys::G_UNICODE_SCRIPT_IMPERIAL_ARAMAIC,
+            UnicodeScript::InscriptionalPahlavi => glib_sys::G_UNICODE_SCRIPT_INSCRIPTIONAL_PAHLAVI,
+            UnicodeScript::InscriptionalParthian => {
+                glib_sys::G_UNICODE_SCRIPT_INSCRIPTIONAL_PARTHIAN
+            }
+            UnicodeScript::Javanese => glib_sys::G_UNICODE_SCRIPT_JAVANESE,
+            UnicodeScript::Kaithi => glib_sys::G_UNICODE_SCRIPT_KAITHI,
+            UnicodeScript::Lisu => glib_sys::G_UNICODE_SCRIPT_LISU,
+            UnicodeScript::MeeteiMayek => glib_sys::G_UNICODE_SCRIPT_MEETEI_MAYEK,
+            UnicodeScript::OldSouthArabian => glib_sys::G_UNICODE_SCRIPT_OLD_SOUTH_ARABIAN,
+            UnicodeScript::OldTurkic => glib_sys::G_UNICODE_SCRIPT_OLD_TURKIC,
+            UnicodeScript::Samaritan => glib_sys::G_UNICODE_SCRIPT_SAMARITAN,
+            UnicodeScript::TaiTham => glib_sys::G_UNICODE_SCRIPT_TAI_THAM,
+            UnicodeScript::TaiViet => glib_sys::G_UNICODE_SCRIPT_TAI_VIET,
+            UnicodeScript::Batak => glib_sys::G_UNICODE_SCRIPT_BATAK,
+            UnicodeScript::Brahmi => glib_sys::G_UNICODE_SCRIPT_BRAHMI,
+            UnicodeScript::Mandaic => glib_sys::G_UNICODE_SCRIPT_MANDAIC,
+            UnicodeScript::Chakma => glib_sys::G_UNICODE_SCRIPT_CHAKMA,
+            UnicodeScript::MeroiticCursive => glib_sys::G_UNICODE_SCRIPT_MEROITIC_CURSIVE,
+            UnicodeScript::MeroiticHieroglyphs => glib_sys::G_UNICODE_SCRIPT_MEROITIC_HIEROGLYPHS,
+            UnicodeScript::Miao => glib_sys::G_UNICODE_SCRIPT_MIAO,
+            UnicodeScript::Sharada => glib_sys::G_UNICODE_SCRIPT_SHARADA,
+            UnicodeScript::SoraSompeng => glib_sys::G_UNICODE_SCRIPT_SORA_SOMPENG,
+            UnicodeScript::Takri => glib_sys::G_UNICODE_SCRIPT_TAKRI,
+            UnicodeScript::BassaVah => glib_sys::G_UNICODE_SCRIPT_BASSA_VAH,
+            UnicodeScript::CaucasianAlbanian => glib_sys::G_UNICODE_SCRIPT_CAUCASIAN_ALBANIAN,
+            UnicodeScript::Duployan => glib_sys::G_UNICODE_SCRIPT_DUPLOYAN,
+            UnicodeScript::Elbasan => glib_sys::G_UNICODE_SCRIPT_ELBASAN,
+            UnicodeScript::Grantha => glib_sys::G_UNICODE_SCRIPT_GRANTHA,
+            UnicodeScript::Khojki => glib_sys::G_UNICODE_SCRIPT_KHOJKI,
+            UnicodeScript::Khudawadi => glib_sys::G_UNICODE_SCRIPT_KHUDAWADI,
+            UnicodeScript::LinearA => glib_sys::G_UNICODE_SCRIPT_LINEAR_A,
+            UnicodeScript::Mahajani => glib_sys::G_UNICODE_SCRIPT_MAHAJANI,
+            UnicodeScript::Manichaean => glib_sys::G_UNICODE_SCRIPT_MANICHAEAN,
+            UnicodeScript::MendeKikakui => glib_sys::G_UNICODE_SCRIPT_MENDE_KIKAKUI,
+            UnicodeScript::Modi => glib_sys::G_UNICODE_SCRIPT_MODI,
+            UnicodeScript::Mro => glib_sys::G_UNICODE_SCRIPT_MRO,
+            UnicodeScript::Nabataean => glib_sys::G_UNICODE_SCRIPT_NABATAEAN,
+            UnicodeScript::OldNorthArabian => glib_sys::G_UNICODE_SCRIPT_OLD_NORTH_ARABIAN,
+            UnicodeScript::OldPermic => glib_sys::G_UNICODE_SCRIPT_OLD_PERMIC,
+            UnicodeScript::PahawhHmong => glib_sys::G_UNICODE_SCRIPT_PAHAWH_HMONG,
+            UnicodeScript::Palmyrene => glib_sys::G_UNICODE_SCRIPT_PALMYRENE,
+            UnicodeScript::PauCinHau => glib_sys::G_UNICODE_SCRIPT_PAU_CIN_HAU,
+            UnicodeScript::PsalterPahlavi => glib_sys::G_UNICODE_SCRIPT_PSALTER_PAHLAVI,
+            UnicodeScript::Siddham => glib_sys::G_UNICODE_SCRIPT_SIDDHAM,
+            UnicodeScript::Tirhuta => glib_sys::G_UNICODE_SCRIPT_TIRHUTA,
+            UnicodeScript::WarangCiti => glib_sys::G_UNICODE_SCRIPT_WARANG_CITI,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::Ahom => glib_sys::G_UNICODE_SCRIPT_AHOM,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::AnatolianHieroglyphs => glib_sys::G_UNICODE_SCRIPT_ANATOLIAN_HIEROGLYPHS,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::Hatran => glib_sys::G_UNICODE_SCRIPT_HATRAN,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::Multani => glib_sys::G_UNICODE_SCRIPT_MULTANI,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::OldHungarian => glib_sys::G_UNICODE_SCRIPT_OLD_HUNGARIAN,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            UnicodeScript::Signwriting => glib_sys::G_UNICODE_SCRIPT_SIGNWRITING,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Adlam => glib_sys::G_UNICODE_SCRIPT_ADLAM,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Bhaiksuki => glib_sys::G_UNICODE_SCRIPT_BHAIKSUKI,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Marchen => glib_sys::G_UNICODE_SCRIPT_MARCHEN,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Newa => glib_sys::G_UNICODE_SCRIPT_NEWA,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Osage => glib_sys::G_UNICODE_SCRIPT_OSAGE,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            UnicodeScript::Tangut => glib_sys::G_UNICODE_SCRIPT_TANGUT,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            UnicodeScript::MasaramGondi => glib_sys::G_UNICODE_SCRIPT_MASARAM_GONDI,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            UnicodeScript::Nushu => glib_sys::G_UNICODE_SCRIPT_NUSHU,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            UnicodeScript::Soyombo => glib_sys::G_UNICODE_SCRIPT_SOYOMBO,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            UnicodeScript::ZanabazarSquare => glib_sys::G_UNICODE_SCRIPT_ZANABAZAR_SQUARE,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::Dogra => glib_sys::G_UNICODE_SCRIPT_DOGRA,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::GunjalaGondi => glib_sys::G_UNICODE_SCRIPT_GUNJALA_GONDI,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::HanifiRohingya => glib_sys::G_UNICODE_SCRIPT_HANIFI_ROHINGYA,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::Makasar => glib_sys::G_UNICODE_SCRIPT_MAKASAR,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::Medefaidrin => glib_sys::G_UNICODE_SCRIPT_MEDEFAIDRIN,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::OldSogdian => glib_sys::G_UNICODE_SCRIPT_OLD_SOGDIAN,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            UnicodeScript::Sogdian => glib_sys::G_UNICODE_SCRIPT_SOGDIAN,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            UnicodeScript::Elymaic => glib_sys::G_UNICODE_SCRIPT_ELYMAIC,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            UnicodeScript::Nandinagari => glib_sys::G_UNICODE_SCRIPT_NANDINAGARI,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            UnicodeScript::NyiakengPuachueHmong => {
+                glib_sys::G_UNICODE_SCRIPT_NYIAKENG_PUACHUE_HMONG
+            }
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            UnicodeScript::Wancho => glib_sys::G_UNICODE_SCRIPT_WANCHO,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            UnicodeScript::Chorasmian => glib_sys::G_UNICODE_SCRIPT_CHORASMIAN,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            UnicodeScript::DivesAkuru => glib_sys::G_UNICODE_SCRIPT_DIVES_AKURU,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            UnicodeScript::KhitanSmallScript => glib_sys::G_UNICODE_SCRIPT_KHITAN_SMALL_SCRIPT,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            UnicodeScript::Yezidi => glib_sys::G_UNICODE_SCRIPT_YEZIDI,
+            UnicodeScript::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GUnicodeScript> for UnicodeScript {
+    fn from_glib(value: glib_sys::GUnicodeScript) -> Self {
+        match value {
+            -1 => UnicodeScript::InvalidCode,
+            0 => UnicodeScript::Common,
+            1 => UnicodeScript::Inherited,
+            2 => UnicodeScript::Arabic,
+            3 => UnicodeScript::Armenian,
+            4 => UnicodeScript::Bengali,
+            5 => UnicodeScript::Bopomofo,
+            6 => UnicodeScript::Cherokee,
+            7 => UnicodeScript::Coptic,
+            8 => UnicodeScript::Cyrillic,
+            9 => UnicodeScript::Deseret,
+            10 => UnicodeScript::Devanagari,
+            11 => UnicodeScript::Ethiopic,
+            12 => UnicodeScript::Georgian,
+            13 => UnicodeScript::Gothic,
+            14 => UnicodeScript::Greek,
+            15 => UnicodeScript::Gujarati,
+            16 => UnicodeScript::Gurmukhi,
+            17 => UnicodeScript::Han,
+            18 => UnicodeScript::Hangul,
+            19 => UnicodeScript::Hebrew,
+            20 => UnicodeScript::Hiragana,
+            21 => UnicodeScript::Kannada,
+            22 => UnicodeScript::Katakana,
+            23 => UnicodeScript::Khmer,
+            24 => UnicodeScript::Lao,
+            25 => UnicodeScript::Latin,
+            26 => UnicodeScript::Malayalam,
+            27 => UnicodeScript::Mongolian,
+            28 => UnicodeScript::Myanmar,
+            29 => UnicodeScript::Ogham,
+            30 => UnicodeScript::OldItalic,
+            31 => UnicodeScript::Oriya,
+            32 => UnicodeScript::Runic,
+            33 => UnicodeScript::Sinhala,
+            34 => UnicodeScript::Syriac,
+            35 => UnicodeScript::Tamil,
+            36 => UnicodeScript::Telugu,
+            37 => UnicodeScript::Thaana,
+            38 => UnicodeScript::Thai,
+            39 => UnicodeScript::Tibetan,
+            40 => UnicodeScript::CanadianAboriginal,
+            41 => UnicodeScript::Yi,
+            42 => UnicodeScript::Tagalog,
+            43 => UnicodeScript::Hanunoo,
+            44 => UnicodeScript::Buhid,
+            45 => UnicodeScript::Tagbanwa,
+            46 => UnicodeScript::Braille,
+            47 => UnicodeScript::Cypriot,
+            48 => UnicodeScript::Limbu,
+            49 => UnicodeScript::Osmanya,
+            50 => UnicodeScript::Shavian,
+            51 => UnicodeScript::LinearB,
+            52 => UnicodeScript::TaiLe,
+            53 => UnicodeScript::Ugaritic,
+            54 => UnicodeScript::NewTaiLue,
+            55 => UnicodeScript::Buginese,
+            56 => UnicodeScript::Glagolitic,
+            57 => UnicodeScript::Tifinagh,
+            58 => UnicodeScript::SylotiNagri,
+            59 => UnicodeScript::OldPersian,
+            60 => UnicodeScript::Kharoshthi,
+            61 => UnicodeScript::Unknown,
+            62 => UnicodeScript::Balinese,
+            63 => UnicodeScript::Cuneiform,
+            64 => UnicodeScript::Phoenician,
+            65 => UnicodeScript::PhagsPa,
+            66 => UnicodeScript::Nko,
+            67 => UnicodeScript::KayahLi,
+            68 => UnicodeScript::Lepcha,
+            69 => UnicodeScript::Rejang,
+            70 => UnicodeScript::Sundanese,
+            71 => UnicodeScript::Saurashtra,
+            72 => UnicodeScript::Cham,
+            73 => UnicodeScript::OlChiki,
+            74 => UnicodeScript::Vai,
+            75 => UnicodeScript::Carian,
+            76 => UnicodeScript::Lycian,
+            77 => UnicodeScript::Lydian,
+            78 => UnicodeScript::Avestan,
+            79 => UnicodeScript::Bamum,
+            80 => UnicodeScript::EgyptianHieroglyphs,
+            81 => UnicodeScript::ImperialAramaic,
+            82 => UnicodeScript::InscriptionalPahlavi,
+            83 => UnicodeScript::InscriptionalParthian,
+            84 => UnicodeScript::Javanese,
+            85 => UnicodeScript::Kaithi,
+            86 => UnicodeScript::Lisu,
+            87 => UnicodeScript::MeeteiMayek,
+            88 => UnicodeScript::OldSouthArabian,
+            89 => UnicodeScript::OldTurkic,
+            90 => UnicodeScript::Samaritan,
+            91 => UnicodeScript::TaiTham,
+            92 => UnicodeScript::TaiViet,
+            93 => UnicodeScript::Batak,
+            94 => UnicodeScript::Brahmi,
+            95 => UnicodeScript::Mandaic,
+            96 => UnicodeScript::Chakma,
+            97 => UnicodeScript::MeroiticCursive,
+            98 => UnicodeScript::MeroiticHieroglyphs,
+            99 => UnicodeScript::Miao,
+            100 => UnicodeScript::Sharada,
+            101 => UnicodeScript::SoraSompeng,
+            102 => UnicodeScript::Takri,
+            103 => UnicodeScript::BassaVah,
+            104 => UnicodeScript::CaucasianAlbanian,
+            105 => UnicodeScript::Duployan,
+            106 => UnicodeScript::Elbasan,
+            107 => UnicodeScript::Grantha,
+            108 => UnicodeScript::Khojki,
+            109 => UnicodeScript::Khudawadi,
+            110 => UnicodeScript::LinearA,
+            111 => UnicodeScript::Mahajani,
+            112 => UnicodeScript::Manichaean,
+            113 => UnicodeScript::MendeKikakui,
+            114 => UnicodeScript::Modi,
+            115 => UnicodeScript::Mro,
+            116 => UnicodeScript::Nabataean,
+            117 => UnicodeScript::OldNorthArabian,
+            118 => UnicodeScript::OldPermic,
+            119 => UnicodeScript::PahawhHmong,
+            120 => UnicodeScript::Palmyrene,
+            121 => UnicodeScript::PauCinHau,
+            122 => UnicodeScript::PsalterPahlavi,
+            123 => UnicodeScript::Siddham,
+            124 => UnicodeScript::Tirhuta,
+            125 => UnicodeScript::WarangCiti,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            126 => UnicodeScript::Ahom,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            127 => UnicodeScript::AnatolianHieroglyphs,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            128 => UnicodeScript::Hatran,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            129 => UnicodeScript::Multani,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            130 => UnicodeScript::OldHungarian,
+            #[cfg(any(feature = "v2_48", feature = "dox"))]
+            131 => UnicodeScript::Signwriting,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            132 => UnicodeScript::Adlam,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            133 => UnicodeScript::Bhaiksuki,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            134 => UnicodeScript::Marchen,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            135 => UnicodeScript::Newa,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            136 => UnicodeScript::Osage,
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
+            137 => UnicodeScript::Tangut,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            138 => UnicodeScript::MasaramGondi,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            139 => UnicodeScript::Nushu,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            140 => UnicodeScript::Soyombo,
+            #[cfg(any(feature = "v2_54", feature = "dox"))]
+            141 => UnicodeScript::ZanabazarSquare,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            142 => UnicodeScript::Dogra,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            143 => UnicodeScript::GunjalaGondi,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            144 => UnicodeScript::HanifiRohingya,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            145 => UnicodeScript::Makasar,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            146 => UnicodeScript::Medefaidrin,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            147 => UnicodeScript::OldSogdian,
+            #[cfg(any(feature = "v2_58", feature = "dox"))]
+            148 => UnicodeScript::Sogdian,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            149 => UnicodeScript::Elymaic,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            150 => UnicodeScript::Nandinagari,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            151 => UnicodeScript::NyiakengPuachueHmong,
+            #[cfg(any(feature = "v2_62", feature = "dox"))]
+            152 => UnicodeScript::Wancho,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            153 => UnicodeScript::Chorasmian,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            154 => UnicodeScript::DivesAkuru,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            155 => UnicodeScript::KhitanSmallScript,
+            #[cfg(any(feature = "v2_66", feature = "dox"))]
+            156 => UnicodeScript::Yezidi,
+            value => UnicodeScript::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum UnicodeType {
+    Control,
+    Format,
+    Unassigned,
+    PrivateUse,
+    Surrogate,
+    LowercaseLetter,
+    ModifierLetter,
+    OtherLetter,
+    TitlecaseLetter,
+    UppercaseLetter,
+    SpacingMark,
+    EnclosingMark,
+    NonSpacingMark,
+    DecimalNumber,
+    LetterNumber,
+    OtherNumber,
+    ConnectPunctuation,
+    DashPunctuation,
+    ClosePunctuation,
+    FinalPunctuation,
+    InitialPunctuation,
+    OtherPunctuation,
+    OpenPunctuation,
+    CurrencySymbol,
+    ModifierSymbol,
+    MathSymbol,
+    OtherSymbol,
+    LineSeparator,
+    ParagraphSeparator,
+    SpaceSeparator,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+impl fmt::Display for UnicodeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UnicodeType::{}",
+            match *self {
+                UnicodeType::Control => "Control",
+                UnicodeType::Format => "Format",
+                UnicodeType::Unassigned => "Unassigned",
+                UnicodeType::PrivateUse => "PrivateUse",
+                UnicodeType::Surrogate => "Surrogate",
+                UnicodeType::LowercaseLetter => "LowercaseLetter",
+                UnicodeType::ModifierLetter => "ModifierLetter",
+                UnicodeType::OtherLetter => "OtherLetter",
+                UnicodeType::TitlecaseLetter => "TitlecaseLetter",
+                UnicodeType::UppercaseLetter => "UppercaseLetter",
+                UnicodeType::SpacingMark => "SpacingMark",
+                UnicodeType::EnclosingMark => "EnclosingMark",
+                UnicodeType::NonSpacingMark => "NonSpacingMark",
+                UnicodeType::DecimalNumber => "DecimalNumber",
+                UnicodeType::LetterNumber => "LetterNumber",
+                UnicodeType::OtherNumber => "OtherNumber",
+                UnicodeType::ConnectPunctuation => "ConnectPunctuation",
+                UnicodeType::DashPunctuation => "DashPunctuation",
+                UnicodeType::ClosePunctuation => "ClosePunctuation",
+                UnicodeType::FinalPunctuation => "FinalPunctuation",
+                UnicodeType::InitialPunctuation => "InitialPunctuation",
+                UnicodeType::OtherPunctuation => "OtherPunctuation",
+                UnicodeType::OpenPunctuation => "OpenPunctuation",
+                UnicodeType::CurrencySymbol => "CurrencySymbol",
+                UnicodeType::ModifierSymbol => "ModifierSymbol",
+                UnicodeType::MathSymbol => "MathSymbol",
+                UnicodeType::OtherSymbol => "OtherSymbol",
+                UnicodeType::LineSeparator => "LineSeparator",
+                UnicodeType::ParagraphSeparator => "ParagraphSeparator",
+                UnicodeType::SpaceSeparator => "SpaceSeparator",
+                _ => "Unknown",
+            }
+        )
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for UnicodeType {
+    type GlibType = glib_sys::GUnicodeType;
+
+    fn to_glib(&self) -> glib_sys::GUnicodeType {
+        match *self {
+            UnicodeType::Control => glib_sys::G_UNICODE_CONTROL,
+            UnicodeType::Format => glib_sys::G_UNICODE_FORMAT,
+            UnicodeType::Unassigned => glib_sys::G_UNICODE_UNASSIGNED,
+            UnicodeType::PrivateUse => glib_sys::G_UNICODE_PRIVATE_USE,
+            UnicodeType::Surrogate => glib_sys::G_UNICODE_SURROGATE,
+            UnicodeType::LowercaseLetter => glib_sys::G_UNICODE_LOWERCASE_LETTER,
+            UnicodeType::ModifierLetter => glib_sys::G_UNICODE_MODIFIER_LETTER,
+            UnicodeType::OtherLetter => glib_sys::G_UNICODE_OTHER_LETTER,
+            UnicodeType::TitlecaseLetter => glib_sys::G_UNICODE_TITLECASE_LETTER,
+            UnicodeType::UppercaseLetter => glib_sys::G_UNICODE_UPPERCASE_LETTER,
+            UnicodeType::SpacingMark => glib_sys::G_UNICODE_SPACING_MARK,
+            UnicodeType::EnclosingMark => glib_sys::G_UNICODE_ENCLOSING_MARK,
+            UnicodeType::NonSpacingMark => glib_sys::G_UNICODE_NON_SPACING_MARK,
+            UnicodeType::DecimalNumber => glib_sys::G_UNICODE_DECIMAL_NUMBER,
+            UnicodeType::LetterNumber => glib_sys::G_UNICODE_LETTER_NUMBER,
+            UnicodeType::OtherNumber => glib_sys::G_UNICODE_OTHER_NUMBER,
+            UnicodeType::ConnectPunctuation => glib_sys::G_UNICODE_CONNECT_PUNCTUATION,
+            UnicodeType::DashPunctuation => glib_sys::G_UNICODE_DASH_PUNCTUATION,
+            UnicodeType::ClosePunctuation => glib_sys::G_UNICODE_CLOSE_PUNCTUATION,
+            UnicodeType::FinalPunctuation => glib_sys::G_UNICODE_FINAL_PUNCTUATION,
+            UnicodeType::InitialPunctuation => glib_sys::G_UNICODE_INITIAL_PUNCTUATION,
+            UnicodeType::OtherPunctuation => glib_sys::G_UNICODE_OTHER_PUNCTUATION,
+            UnicodeType::OpenPunctuation => glib_sys::G_UNICODE_OPEN_PUNCTUATION,
+            UnicodeType::CurrencySymbol => glib_sys::G_UNICODE_CURRENCY_SYMBOL,
+            UnicodeType::ModifierSymbol => glib_sys::G_UNICODE_MODIFIER_SYMBOL,
+            UnicodeType::MathSymbol => glib_sys::G_UNICODE_MATH_SYMBOL,
+            UnicodeType::OtherSymbol => glib_sys::G_UNICODE_OTHER_SYMBOL,
+            UnicodeType::LineSeparator => glib_sys::G_UNICODE_LINE_SEPARATOR,
+            UnicodeType::ParagraphSeparator => glib_sys::G_UNICODE_PARAGRAPH_SEPARATOR,
+            UnicodeType::SpaceSeparator => glib_sys::G_UNICODE_SPACE_SEPARATOR,
+            UnicodeType::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GUnicodeType> for UnicodeType {
+    fn from_glib(value: glib_sys::GUnicodeType) -> Self {
+        match value {
+            0 => UnicodeType::Control,
+            1 => UnicodeType::Format,
+            2 => UnicodeType::Unassigned,
+            3 => UnicodeType::PrivateUse,
+            4 => UnicodeType::Surrogate,
+            5 => UnicodeType::LowercaseLetter,
+            6 => UnicodeType::ModifierLetter,
+            7 => UnicodeType::OtherLetter,
+            8 => UnicodeType::TitlecaseLetter,
+            9 => UnicodeType::UppercaseLetter,
+            10 => UnicodeType::SpacingMark,
+            11 => UnicodeType::EnclosingMark,
+            12 => UnicodeType::NonSpacingMark,
+            13 => UnicodeType::DecimalNumber,
+            14 => UnicodeType::LetterNumber,
+            15 => UnicodeType::OtherNumber,
+            16 => UnicodeType::ConnectPunctuation,
+            17 => UnicodeType::DashPunctuation,
+            18 => UnicodeType::ClosePunctuation,
+            19 => UnicodeType::FinalPunctuation,
+            20 => UnicodeType::InitialPunctuation,
+            21 => UnicodeType::OtherPunctuation,
+            22 => UnicodeType::OpenPunctuation,
+            23 => UnicodeType::CurrencySymbol,
+            24 => UnicodeType::ModifierSymbol,
+            25 => UnicodeType::MathSymbol,
+            26 => UnicodeType::OtherSymbol,
+            27 => UnicodeType::LineSeparator,
+            28 => UnicodeType::ParagraphSeparator,
+            29 => UnicodeType::SpaceSeparator,
+            value => UnicodeType::__Unknown(value),
+        }
+    }
+}
+
 #[cfg(any(feature = "v2_66", feature = "dox"))]
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
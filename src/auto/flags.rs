@@ -196,6 +196,31 @@ impl FromGlib<glib_sys::GLogLevelFlags> for LogLevelFlags {
     }
 }
 
+bitflags! {
+    pub struct MarkupParseFlags: u32 {
+        const DO_NOT_USE_THIS_UNSUPPORTED_FLAG = 1;
+        const TREAT_CDATA_AS_TEXT = 2;
+        const PREFIX_ERROR_POSITION = 4;
+        const IGNORE_QUALIFIED = 8;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MarkupParseFlags {
+    type GlibType = glib_sys::GMarkupParseFlags;
+
+    fn to_glib(&self) -> glib_sys::GMarkupParseFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<glib_sys::GMarkupParseFlags> for MarkupParseFlags {
+    fn from_glib(value: glib_sys::GMarkupParseFlags) -> MarkupParseFlags {
+        MarkupParseFlags::from_bits_truncate(value)
+    }
+}
+
 bitflags! {
     pub struct OptionFlags: u32 {
         const NONE = 0;
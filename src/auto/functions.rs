@@ -128,10 +128,6 @@ pub fn bit_storage(number: libc::c_ulong) -> u32 {
     unsafe { glib_sys::g_bit_storage(number) }
 }
 
-//pub fn build_filename<P: AsRef<std::path::Path>>(first_element: P, : /*Unknown conversion*//*Unimplemented*/Fundamental: VarArgs) -> std::path::PathBuf {
-//    unsafe { TODO: call glib_sys:g_build_filename() }
-//}
-
 //#[cfg(any(feature = "v2_56", feature = "dox"))]
 //pub fn build_filename_valist<P: AsRef<std::path::Path>>(first_element: P, args: /*Unknown conversion*//*Unimplemented*/Unsupported) -> std::path::PathBuf {
 //    unsafe { TODO: call glib_sys:g_build_filename_valist() }
@@ -141,10 +137,6 @@ pub fn build_filenamev(args: &[&std::path::Path]) -> std::path::PathBuf {
     unsafe { from_glib_full(glib_sys::g_build_filenamev(args.to_glib_none().0)) }
 }
 
-//pub fn build_path<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(separator: P, first_element: Q, : /*Unknown conversion*//*Unimplemented*/Fundamental: VarArgs) -> std::path::PathBuf {
-//    unsafe { TODO: call glib_sys:g_build_path() }
-//}
-
 pub fn build_pathv(separator: &str, args: &[&std::path::Path]) -> std::path::PathBuf {
     unsafe {
         from_glib_full(glib_sys::g_build_pathv(
@@ -157,12 +149,12 @@ pub fn build_pathv(separator: &str, args: &[&std::path::Path]) -> std::path::Pat
 #[cfg(any(feature = "v2_58", feature = "dox"))]
 pub fn canonicalize_filename<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(
     filename: P,
-    relative_to: Q,
+    relative_to: Option<Q>,
 ) -> std::path::PathBuf {
     unsafe {
         from_glib_full(glib_sys::g_canonicalize_filename(
             filename.as_ref().to_glib_none().0,
-            relative_to.as_ref().to_glib_none().0,
+            relative_to.as_ref().map(|p| p.as_ref()).to_glib_none().0,
         ))
     }
 }
@@ -675,7 +667,7 @@ pub fn get_user_runtime_dir() -> std::path::PathBuf {
     unsafe { from_glib_none(glib_sys::g_get_user_runtime_dir()) }
 }
 
-pub fn get_user_special_dir(directory: UserDirectory) -> std::path::PathBuf {
+pub fn get_user_special_dir(directory: UserDirectory) -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(glib_sys::g_get_user_special_dir(directory.to_glib())) }
 }
 
@@ -699,11 +691,11 @@ pub fn hostname_is_non_ascii(hostname: &str) -> bool {
     unsafe { from_glib(glib_sys::g_hostname_is_non_ascii(hostname.to_glib_none().0)) }
 }
 
-pub fn hostname_to_ascii(hostname: &str) -> GString {
+pub fn hostname_to_ascii(hostname: &str) -> Option<GString> {
     unsafe { from_glib_full(glib_sys::g_hostname_to_ascii(hostname.to_glib_none().0)) }
 }
 
-pub fn hostname_to_unicode(hostname: &str) -> GString {
+pub fn hostname_to_unicode(hostname: &str) -> Option<GString> {
     unsafe { from_glib_full(glib_sys::g_hostname_to_unicode(hostname.to_glib_none().0)) }
 }
 
@@ -731,18 +723,6 @@ pub fn hostname_to_unicode(hostname: &str) -> GString {
 //    unsafe { TODO: call glib_sys:g_int_hash() }
 //}
 
-//pub fn io_add_watch(channel: /*Ignored*/&IOChannel, condition: IOCondition, func: /*Unimplemented*/Fn(/*Ignored*/IOChannel, &IOCondition, /*Unimplemented*/Option<Fundamental: Pointer>) -> bool, user_data: /*Unimplemented*/Option<Fundamental: Pointer>) -> u32 {
-//    unsafe { TODO: call glib_sys:g_io_add_watch() }
-//}
-
-//pub fn io_add_watch_full(channel: /*Ignored*/&IOChannel, priority: i32, condition: IOCondition, func: /*Unimplemented*/Fn(/*Ignored*/IOChannel, &IOCondition, /*Unimplemented*/Option<Fundamental: Pointer>) -> bool, user_data: /*Unimplemented*/Option<Fundamental: Pointer>) -> u32 {
-//    unsafe { TODO: call glib_sys:g_io_add_watch_full() }
-//}
-
-//pub fn io_create_watch(channel: /*Ignored*/&IOChannel, condition: IOCondition) -> Source {
-//    unsafe { TODO: call glib_sys:g_io_create_watch() }
-//}
-
 pub fn listenv() -> Vec<std::ffi::OsString> {
     unsafe { FromGlibPtrContainer::from_glib_full(glib_sys::g_listenv()) }
 }
@@ -884,10 +864,6 @@ pub fn on_error_stack_trace(prg_name: &str) {
     }
 }
 
-//pub fn parse_debug_string(string: Option<&str>, keys: /*Ignored*/&[&DebugKey]) -> u32 {
-//    unsafe { TODO: call glib_sys:g_parse_debug_string() }
-//}
-
 pub fn path_get_basename<P: AsRef<std::path::Path>>(file_name: P) -> std::path::PathBuf {
     unsafe {
         from_glib_full(glib_sys::g_path_get_basename(
@@ -1211,14 +1187,6 @@ pub fn spawn_command_line_async<P: AsRef<std::ffi::OsStr>>(command_line: P) -> R
     }
 }
 
-//pub fn spawn_command_line_sync<P: AsRef<std::path::Path>>(command_line: P, standard_output: Vec<u8>, standard_error: Vec<u8>) -> Result<i32, Error> {
-//    unsafe { TODO: call glib_sys:g_spawn_command_line_sync() }
-//}
-
-//pub fn spawn_sync<P: AsRef<std::path::Path>>(working_directory: P, argv: &[&std::path::Path], envp: &[&std::path::Path], flags: SpawnFlags, child_setup: Option<Box_<dyn FnOnce() + 'static>>, standard_output: Vec<u8>, standard_error: Vec<u8>) -> Result<i32, Error> {
-//    unsafe { TODO: call glib_sys:g_spawn_sync() }
-//}
-
 //pub fn sprintf(string: &str, format: &str, : /*Unknown conversion*//*Unimplemented*/Fundamental: VarArgs) -> i32 {
 //    unsafe { TODO: call glib_sys:g_sprintf() }
 //}
@@ -21,10 +21,6 @@ impl MainContext {
         unsafe { from_glib_full(glib_sys::g_main_context_new()) }
     }
 
-    pub fn acquire(&self) -> bool {
-        unsafe { from_glib(glib_sys::g_main_context_acquire(self.to_glib_none().0)) }
-    }
-
     //pub fn add_poll(&self, fd: /*Ignored*/&mut PollFD, priority: i32) {
     //    unsafe { TODO: call glib_sys:g_main_context_add_poll() }
     //}
@@ -68,28 +64,10 @@ impl MainContext {
         unsafe { from_glib(glib_sys::g_main_context_pending(self.to_glib_none().0)) }
     }
 
-    pub fn pop_thread_default(&self) {
-        unsafe {
-            glib_sys::g_main_context_pop_thread_default(self.to_glib_none().0);
-        }
-    }
-
-    pub fn push_thread_default(&self) {
-        unsafe {
-            glib_sys::g_main_context_push_thread_default(self.to_glib_none().0);
-        }
-    }
-
     //pub fn query(&self, max_priority: i32, fds: /*Ignored*/Vec<PollFD>) -> (i32, i32) {
     //    unsafe { TODO: call glib_sys:g_main_context_query() }
     //}
 
-    pub fn release(&self) {
-        unsafe {
-            glib_sys::g_main_context_release(self.to_glib_none().0);
-        }
-    }
-
     //pub fn remove_poll(&self, fd: /*Ignored*/&mut PollFD) {
     //    unsafe { TODO: call glib_sys:g_main_context_remove_poll() }
     //}
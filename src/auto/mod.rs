@@ -30,12 +30,17 @@ pub use self::uri::Uri;
 
 mod enums;
 pub use self::enums::ChecksumType;
+pub use self::enums::ConvertError;
 pub use self::enums::DateMonth;
 pub use self::enums::DateWeekday;
 pub use self::enums::KeyFileError;
+pub use self::enums::MarkupError;
+pub use self::enums::NormalizeMode;
 pub use self::enums::OptionArg;
 pub use self::enums::SeekType;
 pub use self::enums::TimeType;
+pub use self::enums::UnicodeScript;
+pub use self::enums::UnicodeType;
 #[cfg(any(feature = "v2_66", feature = "dox"))]
 pub use self::enums::UriError;
 
@@ -47,6 +52,7 @@ pub use self::flags::FormatSizeFlags;
 pub use self::flags::IOCondition;
 pub use self::flags::KeyFileFlags;
 pub use self::flags::LogLevelFlags;
+pub use self::flags::MarkupParseFlags;
 pub use self::flags::OptionFlags;
 pub use self::flags::SpawnFlags;
 #[cfg(any(feature = "v2_66", feature = "dox"))]
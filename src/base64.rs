@@ -0,0 +1,194 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Incremental Base64 encoding and decoding.
+//!
+//! For data that is available in one piece [`base64_encode`](../fn.base64_encode.html) and
+//! [`base64_decode`](../fn.base64_decode.html) are simpler to use.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::Base64Encoder;
+//! use std::io::Write;
+//!
+//! let mut encoder = Base64Encoder::new(Vec::new(), false);
+//! encoder.write_all(b"hello ").unwrap();
+//! encoder.write_all(b"world").unwrap();
+//! assert_eq!(encoder.finish().unwrap(), b"aGVsbG8gd29ybGQ=");
+//! ```
+
+use glib_sys;
+use std::io;
+use translate::*;
+
+/// Encodes all data written to it as Base64 and writes the result to an inner writer.
+///
+/// [`finish`](#method.finish) must be called after all data was written, otherwise the end of
+/// the encoded data is lost.
+#[derive(Debug)]
+pub struct Base64Encoder<W: io::Write> {
+    inner: W,
+    break_lines: bool,
+    state: i32,
+    save: i32,
+    buf: Vec<u8>,
+}
+
+impl<W: io::Write> Base64Encoder<W> {
+    /// Creates a new encoder writing to `inner`.
+    ///
+    /// If `break_lines` is `true` a newline is inserted after every 76 characters.
+    pub fn new(inner: W, break_lines: bool) -> Self {
+        Base64Encoder {
+            inner,
+            break_lines,
+            state: 0,
+            save: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Writes the remaining encoded data including padding, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.buf.clear();
+        // At most 4 characters plus a newline
+        self.buf.reserve(5);
+        unsafe {
+            let len = glib_sys::g_base64_encode_close(
+                self.break_lines.to_glib(),
+                self.buf.as_mut_ptr() as *mut _,
+                &mut self.state,
+                &mut self.save,
+            );
+            self.buf.set_len(len);
+        }
+        self.inner.write_all(&self.buf)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: io::Write> io::Write for Base64Encoder<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        self.buf.clear();
+        let max_len = (data.len() / 3 + 1) * 4 + 4;
+        self.buf.reserve(max_len + max_len / 72 + 1);
+        unsafe {
+            let len = glib_sys::g_base64_encode_step(
+                data.as_ptr(),
+                data.len(),
+                self.break_lines.to_glib(),
+                self.buf.as_mut_ptr() as *mut _,
+                &mut self.state,
+                &mut self.save,
+            );
+            self.buf.set_len(len);
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decodes all Base64 data written to it and writes the result to an inner writer.
+///
+/// Invalid characters in the input, e.g. whitespace or newlines, are skipped.
+#[derive(Debug)]
+pub struct Base64Decoder<W: io::Write> {
+    inner: W,
+    state: i32,
+    save: u32,
+    buf: Vec<u8>,
+}
+
+impl<W: io::Write> Base64Decoder<W> {
+    /// Creates a new decoder writing to `inner`.
+    pub fn new(inner: W) -> Self {
+        Base64Decoder {
+            inner,
+            state: 0,
+            save: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: io::Write> io::Write for Base64Decoder<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        self.buf.clear();
+        self.buf.reserve((data.len() / 4) * 3 + 3);
+        unsafe {
+            let len = glib_sys::g_base64_decode_step(
+                data.as_ptr() as *const _,
+                data.len(),
+                self.buf.as_mut_ptr(),
+                &mut self.state,
+                &mut self.save,
+            );
+            self.buf.set_len(len);
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use {base64_decode, base64_encode};
+
+    #[test]
+    fn encode() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        let mut encoder = Base64Encoder::new(Vec::new(), false);
+        for chunk in data.chunks(7) {
+            encoder.write_all(chunk).unwrap();
+        }
+        let encoded = encoder.finish().unwrap();
+        assert_eq!(encoded, base64_encode(&data).as_bytes());
+
+        let mut encoder = Base64Encoder::new(Vec::new(), true);
+        encoder.write_all(&data).unwrap();
+        let encoded = encoder.finish().unwrap();
+        assert!(encoded.split(|&c| c == b'\n').all(|line| line.len() <= 76));
+        assert_eq!(base64_decode(&String::from_utf8(encoded).unwrap()), data);
+    }
+
+    #[test]
+    fn decode() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let encoded = base64_encode(&data);
+
+        let mut decoder = Base64Decoder::new(Vec::new());
+        for chunk in encoded.as_bytes().chunks(5) {
+            decoder.write_all(chunk).unwrap();
+            decoder.write_all(b"\n").unwrap();
+        }
+        assert_eq!(decoder.finish().unwrap(), data);
+    }
+}
@@ -49,6 +49,9 @@ macro_rules! glib_boxed_wrapper {
         $(#[$attr])*
         #[derive(Clone)]
         pub struct $name($crate::boxed::Boxed<$ffi_name, $name>);
+
+        $crate::glib_wrapper_raw_ptr_impl!($name, $ffi_name, |this| this.0.into_glib_ptr());
+
         #[doc(hidden)]
         impl $crate::translate::GlibPtrDefault for $name {
             type GlibType = *mut $ffi_name;
@@ -488,6 +491,21 @@ impl<T: 'static, MM: BoxedMemoryManager<T>> FromGlibPtrBorrow<*mut T> for Boxed<
     }
 }
 
+impl<T: 'static, MM: BoxedMemoryManager<T>> Boxed<T, MM> {
+    /// Consumes the box and returns an owned pointer to the value.
+    ///
+    /// A foreign-owned value is handed out as is, only a value owned by Rust is copied.
+    #[inline]
+    pub fn into_glib_ptr(self) -> *mut T {
+        let ptr = match self.inner {
+            AnyBox::Foreign(ptr) => ptr.as_ptr(),
+            AnyBox::Native(_) => return ToGlibPtr::<*const T>::to_glib_full(&self) as *mut T,
+        };
+        mem::forget(self);
+        ptr
+    }
+}
+
 impl<T: 'static, MM: BoxedMemoryManager<T>> Drop for Boxed<T, MM> {
     #[inline]
     fn drop(&mut self) {
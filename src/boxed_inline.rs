@@ -0,0 +1,442 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! `IMPL` Inline boxed wrapper implementation.
+
+/// Wrapper implementations for inline Boxed types. See `glib_wrapper!`.
+#[macro_export]
+macro_rules! glib_boxed_inline_wrapper {
+    ([$($attr:meta)*] $name:ident, $ffi_name:ty) => {
+        glib_boxed_inline_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name);
+        glib_boxed_inline_wrapper!(@memory_manager_impl $name, $ffi_name);
+    };
+
+    ([$($attr:meta)*] $name:ident, $ffi_name:ty, @get_type $get_type_expr:expr) => {
+        glib_boxed_inline_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name);
+        glib_boxed_inline_wrapper!(@memory_manager_impl $name, $ffi_name);
+        glib_boxed_inline_wrapper!(@value_impl $name, $ffi_name, @get_type $get_type_expr);
+    };
+
+    ([$($attr:meta)*] $name:ident, $ffi_name:ty, @init $init_arg:ident $init_expr:expr,
+     @copy_into $copy_into_dest_arg:ident $copy_into_src_arg:ident $copy_into_expr:expr,
+     @clear $clear_arg:ident $clear_expr:expr) => {
+        glib_boxed_inline_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name);
+        glib_boxed_inline_wrapper!(@memory_manager_impl $name, $ffi_name, @init $init_arg $init_expr,
+                                   @copy_into $copy_into_dest_arg $copy_into_src_arg $copy_into_expr,
+                                   @clear $clear_arg $clear_expr);
+    };
+
+    ([$($attr:meta)*] $name:ident, $ffi_name:ty, @init $init_arg:ident $init_expr:expr,
+     @copy_into $copy_into_dest_arg:ident $copy_into_src_arg:ident $copy_into_expr:expr,
+     @clear $clear_arg:ident $clear_expr:expr, @get_type $get_type_expr:expr) => {
+        glib_boxed_inline_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name);
+        glib_boxed_inline_wrapper!(@memory_manager_impl $name, $ffi_name, @init $init_arg $init_expr,
+                                   @copy_into $copy_into_dest_arg $copy_into_src_arg $copy_into_expr,
+                                   @clear $clear_arg $clear_expr);
+        glib_boxed_inline_wrapper!(@value_impl $name, $ffi_name, @get_type $get_type_expr);
+    };
+
+    (@generic_impl [$($attr:meta)*] $name:ident, $ffi_name:ty) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        pub struct $name($ffi_name);
+
+        $crate::glib_wrapper_raw_ptr_impl!($name, $ffi_name, |this| {
+            $crate::translate::ToGlibPtr::<*const $ffi_name>::to_glib_full(&this) as *mut $ffi_name
+        });
+
+        #[doc(hidden)]
+        impl $crate::translate::Uninitialized for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn uninitialized() -> Self {
+                let mut v = ::std::mem::MaybeUninit::<$ffi_name>::zeroed();
+                <$name as $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name>>::init(v.as_mut_ptr());
+                $name(v.assume_init())
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::GlibPtrDefault for $name {
+            type GlibType = *mut $ffi_name;
+        }
+
+        #[doc(hidden)]
+        impl<'a> $crate::translate::ToGlibPtr<'a, *const $ffi_name> for $name {
+            type Storage = &'a Self;
+
+            #[inline]
+            fn to_glib_none(&'a self) -> $crate::translate::Stash<'a, *const $ffi_name, Self> {
+                $crate::translate::Stash(&self.0 as *const $ffi_name, self)
+            }
+
+            #[inline]
+            fn to_glib_full(&self) -> *const $ffi_name {
+                unsafe {
+                    let ptr = $crate::glib_sys::g_malloc0(::std::mem::size_of::<$ffi_name>()) as *mut $ffi_name;
+                    <$name as $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name>>::copy_into(ptr, &self.0);
+                    ptr
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        impl<'a> $crate::translate::ToGlibPtrMut<'a, *mut $ffi_name> for $name {
+            type Storage = &'a mut Self;
+
+            #[inline]
+            fn to_glib_none_mut(&'a mut self) -> $crate::translate::StashMut<'a, *mut $ffi_name, Self> {
+                let ptr = &mut self.0 as *mut $ffi_name;
+                $crate::translate::StashMut(ptr, self)
+            }
+        }
+
+        #[doc(hidden)]
+        impl<'a> $crate::translate::ToGlibContainerFromSlice<'a, *const $ffi_name> for $name {
+            type Storage = &'a [$name];
+
+            fn to_glib_none_from_slice(t: &'a [$name]) -> (*const $ffi_name, &'a [$name]) {
+                (t.as_ptr() as *const $ffi_name, t)
+            }
+
+            fn to_glib_container_from_slice(_: &'a [$name]) -> (*const $ffi_name, &'a [$name]) {
+                // Can't have consumer free a *const pointer
+                unimplemented!()
+            }
+
+            fn to_glib_full_from_slice(_: &[$name]) -> *const $ffi_name {
+                // Can't have consumer free a *const pointer
+                unimplemented!()
+            }
+        }
+
+        #[doc(hidden)]
+        impl<'a> $crate::translate::ToGlibContainerFromSlice<'a, *mut $ffi_name> for $name {
+            type Storage = &'a [$name];
+
+            fn to_glib_none_from_slice(t: &'a [$name]) -> (*mut $ffi_name, &'a [$name]) {
+                (t.as_ptr() as *mut $ffi_name, t)
+            }
+
+            fn to_glib_container_from_slice(t: &'a [$name]) -> (*mut $ffi_name, &'a [$name]) {
+                unsafe {
+                    // The items are only borrowed, so a plain copy of their memory is enough
+                    let v_ptr = $crate::glib_sys::g_malloc0(::std::mem::size_of::<$ffi_name>() * (t.len() + 1)) as *mut $ffi_name;
+                    ::std::ptr::copy_nonoverlapping(t.as_ptr() as *const $ffi_name, v_ptr, t.len());
+
+                    (v_ptr, t)
+                }
+            }
+
+            fn to_glib_full_from_slice(t: &[$name]) -> *mut $ffi_name {
+                unsafe {
+                    let v_ptr = $crate::glib_sys::g_malloc0(::std::mem::size_of::<$ffi_name>() * (t.len() + 1)) as *mut $ffi_name;
+
+                    for (i, s) in t.iter().enumerate() {
+                        <$name as $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name>>::copy_into(v_ptr.add(i), &s.0);
+                    }
+
+                    v_ptr
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrNone<*mut $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_none(ptr: *mut $ffi_name) -> Self {
+                $crate::translate::from_glib_none(ptr as *const $ffi_name)
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrNone<*const $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_none(ptr: *const $ffi_name) -> Self {
+                assert!(!ptr.is_null());
+                let mut v = ::std::mem::MaybeUninit::<$ffi_name>::zeroed();
+                <$name as $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name>>::copy_into(v.as_mut_ptr(), ptr);
+                $name(v.assume_init())
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrFull<*mut $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_full(ptr: *mut $ffi_name) -> Self {
+                assert!(!ptr.is_null());
+                // Take over the contents and only free the memory of the struct itself
+                let v = $name(::std::ptr::read(ptr));
+                $crate::glib_sys::g_free(ptr as *mut _);
+                v
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrFull<*const $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_full(ptr: *const $ffi_name) -> Self {
+                $crate::translate::from_glib_full(ptr as *mut $ffi_name)
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrBorrow<*mut $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_borrow(ptr: *mut $ffi_name) -> $crate::translate::Borrowed<Self> {
+                assert!(!ptr.is_null());
+                $crate::translate::Borrowed::new($name(::std::ptr::read(ptr)))
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibPtrBorrow<*const $ffi_name> for $name {
+            #[inline]
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_borrow(ptr: *const $ffi_name) -> $crate::translate::Borrowed<Self> {
+                $crate::translate::from_glib_borrow::<_, $name>(ptr as *mut $ffi_name)
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::translate::FromGlibContainerAsVec<*mut $ffi_name, *mut $ffi_name> for $name {
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_none_num_as_vec(ptr: *mut $ffi_name, num: usize) -> Vec<Self> {
+                if num == 0 || ptr.is_null() {
+                    return Vec::new();
+                }
+
+                let mut res = Vec::with_capacity(num);
+                for i in 0..num {
+                    res.push($crate::translate::from_glib_none(ptr.add(i) as *const $ffi_name));
+                }
+                res
+            }
+
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_container_num_as_vec(ptr: *mut $ffi_name, num: usize) -> Vec<Self> {
+                let res = $crate::translate::FromGlibContainerAsVec::from_glib_none_num_as_vec(ptr, num);
+                $crate::glib_sys::g_free(ptr as *mut _);
+                res
+            }
+
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_glib_full_num_as_vec(ptr: *mut $ffi_name, num: usize) -> Vec<Self> {
+                if num == 0 || ptr.is_null() {
+                    $crate::glib_sys::g_free(ptr as *mut _);
+                    return Vec::new();
+                }
+
+                let mut res = Vec::with_capacity(num);
+                for i in 0..num {
+                    res.push($name(::std::ptr::read(ptr.add(i))));
+                }
+                $crate::glib_sys::g_free(ptr as *mut _);
+                res
+            }
+        }
+    };
+
+    (@value_impl $name:ident, $ffi_name:ty, @get_type $get_type_expr:expr) => {
+        impl $crate::types::StaticType for $name {
+            fn static_type() -> $crate::types::Type {
+                #[allow(unused_unsafe)]
+                unsafe { $crate::translate::from_glib($get_type_expr) }
+            }
+        }
+
+        #[doc(hidden)]
+        impl<'a> $crate::value::FromValueOptional<'a> for $name {
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn from_value_optional(value: &$crate::Value) -> Option<Self> {
+                let ptr = $crate::gobject_sys::g_value_get_boxed($crate::translate::ToGlibPtr::to_glib_none(value).0) as *const $ffi_name;
+                if ptr.is_null() {
+                    None
+                } else {
+                    Some($crate::translate::from_glib_none(ptr))
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::value::SetValue for $name {
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn set_value(value: &mut $crate::Value, this: &Self) {
+                $crate::gobject_sys::g_value_set_boxed($crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0, $crate::translate::ToGlibPtr::<*const $ffi_name>::to_glib_none(this).0 as $crate::glib_sys::gpointer)
+            }
+        }
+
+        #[doc(hidden)]
+        impl $crate::value::SetValueOptional for $name {
+            #[allow(clippy::missing_safety_doc)]
+            unsafe fn set_value_optional(value: &mut $crate::Value, this: Option<&Self>) {
+                let ptr = match this {
+                    Some(this) => $crate::translate::ToGlibPtr::<*const $ffi_name>::to_glib_none(this).0,
+                    None => ::std::ptr::null(),
+                };
+                $crate::gobject_sys::g_value_set_boxed($crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0, ptr as $crate::glib_sys::gpointer)
+            }
+        }
+    };
+
+    (@memory_manager_impl $name:ident, $ffi_name:ty) => {
+        #[doc(hidden)]
+        impl $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name> for $name {
+            #[inline]
+            unsafe fn init(_: *mut $ffi_name) {}
+
+            #[inline]
+            unsafe fn copy_into(dest: *mut $ffi_name, src: *const $ffi_name) {
+                ::std::ptr::copy_nonoverlapping(src, dest, 1);
+            }
+
+            #[inline]
+            unsafe fn clear(_: *mut $ffi_name) {}
+        }
+
+        impl Clone for $name {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl Copy for $name {}
+    };
+
+    (@memory_manager_impl $name:ident, $ffi_name:ty, @init $init_arg:ident $init_expr:expr,
+     @copy_into $copy_into_dest_arg:ident $copy_into_src_arg:ident $copy_into_expr:expr,
+     @clear $clear_arg:ident $clear_expr:expr) => {
+        #[doc(hidden)]
+        impl $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name> for $name {
+            #[inline]
+            unsafe fn init($init_arg: *mut $ffi_name) {
+                $init_expr;
+            }
+
+            #[inline]
+            unsafe fn copy_into($copy_into_dest_arg: *mut $ffi_name, $copy_into_src_arg: *const $ffi_name) {
+                $copy_into_expr;
+            }
+
+            #[inline]
+            unsafe fn clear($clear_arg: *mut $ffi_name) {
+                $clear_expr;
+            }
+        }
+
+        impl Clone for $name {
+            #[inline]
+            fn clone(&self) -> Self {
+                unsafe { $crate::translate::from_glib_none(&self.0 as *const $ffi_name) }
+            }
+        }
+
+        impl Drop for $name {
+            #[inline]
+            fn drop(&mut self) {
+                unsafe {
+                    <$name as $crate::boxed_inline::BoxedInlineMemoryManager<$ffi_name>>::clear(&mut self.0);
+                }
+            }
+        }
+    };
+}
+
+// The safety docs really belong in the glib_wrapper!() macro for BoxedInline<T>
+#[allow(clippy::missing_safety_doc)]
+/// Memory management functions for an inline boxed type.
+pub trait BoxedInlineMemoryManager<T>: 'static {
+    /// Initializes already allocated, zeroed memory.
+    unsafe fn init(ptr: *mut T);
+    /// Copies `src` into uninitialized memory at `dest`.
+    unsafe fn copy_into(dest: *mut T, src: *const T);
+    /// Clears and frees all memory of the value, but not the value itself.
+    unsafe fn clear(ptr: *mut T);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use translate::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct TestPoint {
+        x: i32,
+        y: i32,
+    }
+
+    glib_wrapper! {
+        pub struct Point(BoxedInline<TestPoint>);
+    }
+
+    #[repr(C)]
+    pub struct TestCounted {
+        count: *mut AtomicUsize,
+    }
+
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    glib_wrapper! {
+        pub struct Counted(BoxedInline<TestCounted>);
+
+        match fn {
+            init => |ptr| {
+                (*ptr).count = &COUNT as *const AtomicUsize as *mut AtomicUsize;
+                (*(*ptr).count).fetch_add(1, Ordering::SeqCst);
+            },
+            copy_into => |dest, src| {
+                (*dest).count = (*src).count;
+                (*(*dest).count).fetch_add(1, Ordering::SeqCst);
+            },
+            clear => |ptr| {
+                (*(*ptr).count).fetch_sub(1, Ordering::SeqCst);
+            },
+        }
+    }
+
+    #[test]
+    fn copy() {
+        let mut point = TestPoint { x: 1, y: 2 };
+        let p: Point = unsafe { from_glib_none(&mut point as *mut TestPoint) };
+        let q = p;
+        assert_eq!(p.0, q.0);
+
+        unsafe {
+            let full = p.to_glib_full() as *mut TestPoint;
+            assert_eq!(*full, point);
+            let r: Point = from_glib_full(full);
+            assert_eq!(r.0, point);
+
+            let mut uninit = Point::uninitialized();
+            (*uninit.to_glib_none_mut().0).x = 3;
+            assert_eq!(uninit.0, TestPoint { x: 3, y: 0 });
+        }
+    }
+
+    #[test]
+    fn hooks() {
+        assert_eq!(COUNT.load(Ordering::SeqCst), 0);
+        let c = unsafe { Counted::uninitialized() };
+        assert_eq!(COUNT.load(Ordering::SeqCst), 1);
+        let d = c.clone();
+        assert_eq!(COUNT.load(Ordering::SeqCst), 2);
+
+        let v: Vec<Counted> = unsafe {
+            FromGlibContainerAsVec::from_glib_full_num_as_vec(
+                ToGlibContainerFromSlice::<*mut TestCounted>::to_glib_full_from_slice(&[c, d]),
+                2,
+            )
+        };
+        assert_eq!(COUNT.load(Ordering::SeqCst), 2);
+        drop(v);
+        assert_eq!(COUNT.load(Ordering::SeqCst), 0);
+    }
+}
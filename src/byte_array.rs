@@ -12,6 +12,9 @@
 //! ba.append(b"ghi").prepend(b"abc");
 //! ba.remove_range(3, 3);
 //! assert_eq!(ba, "abcghi".as_bytes());
+//!
+//! let bytes = ba.into_gbytes();
+//! assert_eq!(bytes, "abcghi".as_bytes());
 //! ```
 
 use glib_sys;
@@ -19,6 +22,8 @@ use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::io;
+use std::iter::FromIterator;
 use std::mem;
 use std::ops::Deref;
 use std::ptr::NonNull;
@@ -140,6 +145,63 @@ impl AsRef<[u8]> for ByteArray {
     }
 }
 
+impl Extend<u8> for ByteArray {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let bytes: Vec<u8> = iter.into_iter().collect();
+        self.append(&bytes);
+    }
+}
+
+impl<'a> Extend<&'a u8> for ByteArray {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl FromIterator<u8> for ByteArray {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut ba = ByteArray::new();
+        ba.extend(iter);
+        ba
+    }
+}
+
+impl<'a> IntoIterator for &'a ByteArray {
+    type Item = &'a u8;
+    type IntoIter = slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> slice::Iter<'a, u8> {
+        self.iter()
+    }
+}
+
+impl From<ByteArray> for Bytes {
+    fn from(value: ByteArray) -> Bytes {
+        value.into_gbytes()
+    }
+}
+
+impl io::Write for ByteArray {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> io::Write for &'a ByteArray {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl<'a, T: ?Sized + Borrow<[u8]> + 'a> From<&'a T> for ByteArray {
     fn from(value: &'a T) -> ByteArray {
         let ba = ByteArray::new();
@@ -260,4 +322,25 @@ mod tests {
         assert!(set.contains(&b2));
         assert!(!set.contains(&b3));
     }
+
+    #[test]
+    fn write() {
+        use std::io::Write;
+
+        let mut ba = ByteArray::new();
+        write!(ba, "{}-{}", 1, 2).unwrap();
+        ba.write_all(b"abc").unwrap();
+        assert_eq!(ba, b"1-2abc" as &[u8]);
+
+        ba.extend(b"de");
+        let bytes = Bytes::from(ba);
+        assert_eq!(bytes, b"1-2abcde" as &[u8]);
+    }
+
+    #[test]
+    fn iter() {
+        let ba: ByteArray = (1..4).collect();
+        assert_eq!(ba, [1u8, 2, 3].as_ref());
+        assert_eq!((&ba).into_iter().sum::<u8>(), 6);
+    }
 }
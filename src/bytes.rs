@@ -7,7 +7,7 @@ use std::borrow::Borrow;
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
 use std::slice;
 use translate::*;
 
@@ -15,7 +15,8 @@ glib_wrapper! {
     /// A shared immutable byte slice (the equivalent of `Rc<[u8]>`).
     ///
     /// `From` implementations that take references (e.g. `&[u8]`) copy the
-    /// data. The `from_static` constructor avoids copying static data.
+    /// data. The `from_static` and `from_owned` constructors and `slice` avoid
+    /// copying.
     ///
     /// ```
     /// use glib::Bytes;
@@ -27,6 +28,9 @@ glib_wrapper! {
     /// let s = b"xyz";
     /// let b = Bytes::from_static(s);
     /// assert_eq!(&s[..], b);
+    ///
+    /// let b = Bytes::from_owned(vec![1, 2, 3]);
+    /// assert_eq!(b.slice(1..), [2u8, 3].as_ref());
     /// ```
     pub struct Bytes(Shared<glib_sys::GBytes>);
 
@@ -75,6 +79,42 @@ impl Bytes {
             ))
         }
     }
+
+    /// Creates a view into `range` of this slice without copying.
+    ///
+    /// The returned `Bytes` keeps a reference to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Bytes {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "range start {} is greater than range end {}",
+            start,
+            end
+        );
+        assert!(end <= len, "range end {} is out of bounds {}", end, len);
+
+        unsafe {
+            from_glib_full(glib_sys::g_bytes_new_from_bytes(
+                self.to_glib_none().0,
+                start,
+                end - start,
+            ))
+        }
+    }
 }
 
 unsafe impl Send for Bytes {}
@@ -251,4 +291,22 @@ mod tests {
         let b = Bytes::from_owned(vec![1, 2, 3]);
         assert_eq!(b, [1u8, 2u8, 3u8].as_ref());
     }
+
+    #[test]
+    fn slice() {
+        let b = Bytes::from_owned(vec![1u8, 2, 3, 4, 5]);
+        assert_eq!(b.slice(1..3), [2u8, 3].as_ref());
+        assert_eq!(b.slice(..=1), [1u8, 2].as_ref());
+        assert_eq!(b.slice(3..), [4u8, 5].as_ref());
+        assert_eq!(b.slice(..), b);
+        assert!(b.slice(5..).is_empty());
+        assert_eq!(b.slice(1..4).slice(1..), [3u8, 4].as_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_out_of_bounds() {
+        let b = Bytes::from_static(b"abc");
+        b.slice(2..4);
+    }
 }
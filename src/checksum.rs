@@ -4,11 +4,13 @@
 
 use glib_sys;
 use libc::size_t;
+use std::io;
 use std::vec::Vec;
 use translate::*;
 use Checksum;
 
 impl Checksum {
+    /// Finalizes the checksum and returns its raw digest.
     pub fn get_digest(self) -> Vec<u8> {
         unsafe {
             //Don't forget update when `ChecksumType` contains type bigger that Sha512.
@@ -26,6 +28,7 @@ impl Checksum {
         }
     }
 
+    /// Finalizes the checksum and returns its digest as a hexadecimal string.
     pub fn get_string(self) -> Option<String> {
         unsafe {
             from_glib_none(glib_sys::g_checksum_get_string(mut_override(
@@ -35,8 +38,21 @@ impl Checksum {
     }
 }
 
+/// Feeds all written data into the checksum, e.g. for use with `std::io::copy()`.
+impl io::Write for Checksum {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::{self, Write};
     use {Checksum, ChecksumType};
 
     const CS_TYPE: ChecksumType = ChecksumType::Md5;
@@ -68,4 +84,15 @@ mod tests {
         let vec = cs.get_digest();
         assert_eq!(vec, CS_SLICE);
     }
+
+    #[test]
+    fn write() {
+        let mut cs = Checksum::new(CS_TYPE);
+        write!(cs, "hello {}!", "world").unwrap();
+        assert_eq!(cs.get_string().unwrap(), CS_VALUE);
+
+        let mut cs = Checksum::new(CS_TYPE);
+        io::copy(&mut &b"hello world!"[..], &mut cs).unwrap();
+        assert_eq!(cs.get_digest(), CS_SLICE);
+    }
 }
@@ -97,6 +97,9 @@ macro_rules! to_type_before {
     (@weak $($variable:ident).+ as $rename:ident) => (
         let $rename = $crate::clone::Downgrade::downgrade(&$($variable).+);
     );
+    (@weak-allow-none $($variable:ident).+ as $rename:ident) => (
+        let $rename = $crate::clone::Downgrade::downgrade(&$($variable).+);
+    );
     // The three following cases are just here so "@strong", "@weak-allow-none" and "@weak" aren't
     // detected as invalid when passing an expression (like "@default-return" => "-return" is the
     // start of an expression there).
@@ -175,9 +178,8 @@ macro_rules! to_type_after {
             None => {
                 $crate::g_debug!(
                     $crate::CLONE_MACRO_LOG_DOMAIN,
-                    "Failed to upgrade {} {}",
-                    stringify!($rename),
-                    "yolo",
+                    "Failed to upgrade {}",
+                    stringify!($rename)
                 );
                 return ($return_value)();
             }
@@ -187,6 +189,17 @@ macro_rules! to_type_after {
     ($(as $rename:ident)? @ $keyword:ident $($variable:ident).+, $return_value:expr) => {};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! to_type_async_clone {
+    ($($variable:ident).+ as $rename:ident) => {
+        let $rename = $rename.clone();
+    };
+    ($variable:ident) => {
+        let $variable = $variable.clone();
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! to_return_value {
@@ -330,6 +343,33 @@ macro_rules! to_return_value {
 /// # assert_eq!(closure(2), false);
 /// ```
 ///
+/// ### Async blocks and closures
+///
+/// `async move` blocks are supported, as well as closures returning an `async move` block. The
+/// weak references are only upgraded once the async block is polled for the first time, and the
+/// async block resolves to the default return value if that fails. For closures, each call
+/// creates a new async block with its own copy of the references.
+///
+/// ```edition2018
+/// use glib::clone;
+/// use std::rc::Rc;
+///
+/// let v = Rc::new(1);
+/// let future = clone!(@weak v => @default-return 0, async move {
+///     *v + 1
+/// });
+/// let closure = clone!(@weak v => @default-return 0, move |x| async move {
+///     *v + x
+/// });
+///
+/// let c = glib::MainContext::default();
+/// assert_eq!(c.block_on(future), 2);
+/// assert_eq!(c.block_on(closure(2)), 3);
+///
+/// drop(v);
+/// assert_eq!(c.block_on(closure(2)), 0);
+/// ```
+///
 /// ### Errors
 ///
 /// Here is a list of errors you might encounter:
@@ -447,6 +487,88 @@ macro_rules! clone {
     ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $up:ident.$($variables:ident).+),+ => $($_:tt)* ) => (
         compile_error!("Field accesses are not allowed as is, you must rename it!");
     );
+    // The `async` token of the async arms is passed through from the caller instead of being
+    // written literally: this crate uses edition 2015, where a literal `async move` in the
+    // expansion is parsed as an identifier even if the caller uses edition 2018.
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => @default-panic, $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            $async move {
+                $( $crate::to_type_after!($(as $rename)? @default-panic, $(@ $strength$(-$var-$var2)?)? $($variables).+);)*
+                $body
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => @default-return $return_value:expr, $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            $async move {
+                let _return_value = || $crate::to_return_value!($($return_value)?);
+                $( $crate::to_type_after!($(as $rename)? $(@ $strength$(-$var-$var2)?)? $($variables).+, _return_value );)*
+                $body
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            $async move {
+                let _return_value = || $crate::to_return_value!();
+                $( $crate::to_type_after!($(as $rename)? $(@ $strength$(-$var-$var2)?)? $($variables).+, _return_value );)*
+                $body
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => @default-panic, move || $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            move || {
+                $( $crate::to_type_async_clone!($($variables).+ $(as $rename)?); )*
+                $async move {
+                    $( $crate::to_type_after!($(as $rename)? @default-panic, $(@ $strength$(-$var-$var2)?)? $($variables).+);)*
+                    $body
+                }
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => $(@default-return $return_value:expr,)? move || $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            move || {
+                $( $crate::to_type_async_clone!($($variables).+ $(as $rename)?); )*
+                $async move {
+                    let _return_value = || $crate::to_return_value!($($return_value)?);
+                    $( $crate::to_type_after!($(as $rename)? $(@ $strength$(-$var-$var2)?)? $($variables).+, _return_value );)*
+                    $body
+                }
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => @default-panic, move | $($arg:tt $(: $typ:ty)?),* | $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            move | $($arg $(: $typ)?),* | {
+                $( $crate::to_type_async_clone!($($variables).+ $(as $rename)?); )*
+                $async move {
+                    $( $crate::to_type_after!($(as $rename)? @default-panic, $(@ $strength$(-$var-$var2)?)? $($variables).+);)*
+                    $body
+                }
+            }
+        }
+    );
+    ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => $(@default-return $return_value:expr,)? move | $($arg:tt $(: $typ:ty)?),* | $async:tt move $body:block ) => (
+        {
+            $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
+            move | $($arg $(: $typ)?),* | {
+                $( $crate::to_type_async_clone!($($variables).+ $(as $rename)?); )*
+                $async move {
+                    let _return_value = || $crate::to_return_value!($($return_value)?);
+                    $( $crate::to_type_after!($(as $rename)? $(@ $strength$(-$var-$var2)?)? $($variables).+, _return_value );)*
+                    $body
+                }
+            }
+        }
+    );
     ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => @default-panic, move || $body:block ) => (
         {
             $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?); )*
@@ -518,7 +640,9 @@ macro_rules! clone {
         compile_error!("Closure needs to be \"moved\" so please add `move` before closure");
     );
     ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => async $($x:tt)+ ) => (
-        compile_error!("async blocks are not supported by the clone! macro");
+        // In case we have:
+        // clone!(@weak foo => async {});
+        compile_error!("Async block needs to be \"moved\" so please add `move` after `async`");
     );
     ($($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $variables:expr),+ => move || $($_:tt)* ) => (
         $( $crate::to_type_before!($(@ $strength$(-$var-$var2)?)? $variables); )*
@@ -4,18 +4,51 @@
 
 // TODO: support marshaller.
 
+use std::cell::RefCell;
 use std::mem;
 use std::ptr;
 use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
 
 use libc::{c_uint, c_void};
 
 use gobject_sys;
 use translate::{from_glib_none, mut_override, ToGlibPtr, ToGlibPtrMut, Uninitialized};
 use types::Type;
+use value::{FromValue, FromValueOptional, GetError};
 use ToValue;
 use Value;
 
+// Resets the owner of a `new_mut()` closure once the callback returned, also if it panicked
+struct OwnerGuard<'a>(&'a AtomicUsize);
+
+impl<'a> Drop for OwnerGuard<'a> {
+    fn drop(&mut self) {
+        self.0.store(::std::usize::MAX, Ordering::SeqCst);
+    }
+}
+
+fn serialize_mut<F: FnMut(&[Value]) -> Option<Value> + Send + 'static>(
+    callback: F,
+) -> impl Fn(&[Value]) -> Option<Value> + Send + Sync + 'static {
+    let callback = Mutex::new(callback);
+    let owner = AtomicUsize::new(::std::usize::MAX);
+
+    move |values| {
+        let thread_id = crate::get_thread_id();
+        if owner.load(Ordering::SeqCst) == thread_id {
+            panic!("Closure called reentrantly");
+        }
+        // A panic in an earlier invocation doesn't leave the state of `callback` inconsistent
+        // from our side, so the closure stays usable
+        let mut callback = callback.lock().unwrap_or_else(PoisonError::into_inner);
+        owner.store(thread_id, Ordering::SeqCst);
+        let _guard = OwnerGuard(&owner);
+        (&mut *callback)(values)
+    }
+}
+
 glib_wrapper! {
     #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
     pub struct Closure(Shared<gobject_sys::GClosure>);
@@ -31,16 +64,50 @@ glib_wrapper! {
 }
 
 impl Closure {
+    /// Creates a new closure around `callback`.
+    ///
+    /// The closure can be invoked from any thread, also concurrently and reentrantly from
+    /// within `callback` itself.
     pub fn new<F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static>(callback: F) -> Self {
         unsafe { Closure::new_unsafe(callback) }
     }
 
+    /// Creates a new closure around `callback`.
+    ///
+    /// Different to `new()`, `callback` does not have to be `Send` or `Sync` but the closure
+    /// panics if it is invoked from a different thread than the one it was created on.
     pub fn new_local<F: Fn(&[Value]) -> Option<Value> + 'static>(callback: F) -> Self {
         let callback = crate::ThreadGuard::new(callback);
 
         unsafe { Closure::new_unsafe(move |values| (callback.get_ref())(values)) }
     }
 
+    /// Creates a new closure around a `callback` that can mutate its state.
+    ///
+    /// The closure can be invoked from any thread but invocations are serialized. As `callback`
+    /// needs exclusive access to its state, it must not invoke the closure reentrantly, e.g. by
+    /// emitting the signal it is connected to. The closure panics in that case instead of
+    /// deadlocking.
+    pub fn new_mut<F: FnMut(&[Value]) -> Option<Value> + Send + 'static>(callback: F) -> Self {
+        Closure::new(serialize_mut(callback))
+    }
+
+    /// Creates a new closure around a `callback` that can mutate its state.
+    ///
+    /// Different to `new_mut()`, `callback` does not have to be `Send` but the closure panics
+    /// if it is invoked from a different thread than the one it was created on. The closure
+    /// also panics if it is invoked reentrantly from within `callback`.
+    pub fn new_local_mut<F: FnMut(&[Value]) -> Option<Value> + 'static>(callback: F) -> Self {
+        let callback = RefCell::new(callback);
+
+        Closure::new_local(move |values| {
+            let mut callback = callback
+                .try_borrow_mut()
+                .expect("Closure called reentrantly");
+            (&mut *callback)(values)
+        })
+    }
+
     #[allow(clippy::missing_safety_doc)]
     pub unsafe fn new_unsafe<F: Fn(&[Value]) -> Option<Value>>(callback: F) -> Self {
         unsafe extern "C" fn marshal<F>(
@@ -129,8 +196,132 @@ impl Closure {
 unsafe impl Send for Closure {}
 unsafe impl Sync for Closure {}
 
+#[doc(hidden)]
+pub trait FromClosureValue<'a>: Sized {
+    fn from_closure_value(value: &'a Value) -> Result<Self, GetError>;
+}
+
+impl<'a, T: FromValue<'a>> FromClosureValue<'a> for T {
+    fn from_closure_value(value: &'a Value) -> Result<Self, GetError> {
+        value.get_some::<T>()
+    }
+}
+
+impl<'a, T: FromValueOptional<'a>> FromClosureValue<'a> for Option<T> {
+    fn from_closure_value(value: &'a Value) -> Result<Self, GetError> {
+        value.get::<T>()
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! closure_impl {
+    (@unpack $values:ident, [$($arg:ident : $ty:ty),*]) => {
+        let n_args = <[&str]>::len(&[$(stringify!($arg)),*]);
+        assert_eq!(
+            $values.len(),
+            n_args,
+            "Closure called with {} arguments instead of {}",
+            $values.len(),
+            n_args
+        );
+        #[allow(unused_mut, unused_variables)]
+        let mut values_iter = $values.iter();
+        $(
+            let $arg: $ty = $crate::closure::FromClosureValue::from_closure_value(
+                values_iter.next().unwrap(),
+            )
+            .unwrap_or_else(|err| {
+                panic!("Wrong type for closure argument `{}`: {}", stringify!($arg), err)
+            });
+        )*
+    };
+    (@call $values:ident, [$($arg:ident : $ty:ty),*], -> $ret:ty, $body:block) => {{
+        $crate::closure_impl!(@unpack $values, [$($arg : $ty),*]);
+        #[allow(clippy::redundant_closure_call)]
+        let ret: $ret = (|| $body)();
+        Some($crate::ToValue::to_value(&ret))
+    }};
+    (@call $values:ident, [$($arg:ident : $ty:ty),*], , $body:block) => {{
+        $crate::closure_impl!(@unpack $values, [$($arg : $ty),*]);
+        #[allow(clippy::redundant_closure_call)]
+        let () = (|| $body)();
+        None
+    }};
+    ($ctor:ident, $($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => move || $(-> $ret:ty)? $body:block) => (
+        $crate::closure_impl!($ctor, $($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?),+ => move | | $(-> $ret)? $body)
+    );
+    ($ctor:ident, $($(@ $strength:ident$(-$var:ident-$var2:ident)?)? $($variables:ident).+ $(as $rename:ident)?),+ => move | $($arg:ident : $ty:ty),* | $(-> $ret:ty)? $body:block) => (
+        $crate::Closure::$ctor($crate::clone!($($(@ $strength$(-$var-$var2)?)? $($variables).+ $(as $rename)?),+ => @default-return None, move |values: &[$crate::Value]| {
+            $crate::closure_impl!(@call values, [$($arg : $ty),*], $(-> $ret)?, $body)
+        }))
+    );
+    ($ctor:ident, $(move)? || $(-> $ret:ty)? $body:block) => (
+        $crate::closure_impl!($ctor, move | | $(-> $ret)? $body)
+    );
+    ($ctor:ident, $(move)? | $($arg:ident : $ty:ty),* | $(-> $ret:ty)? $body:block) => (
+        $crate::Closure::$ctor(move |values: &[$crate::Value]| {
+            $crate::closure_impl!(@call values, [$($arg : $ty),*], $(-> $ret)?, $body)
+        })
+    );
+}
+
+/// Macro for creating a [`Closure`] from a Rust closure with typed arguments.
+///
+/// The arguments are unpacked from the `Value`s the `Closure` is invoked with, and the return
+/// value is converted into a `Value`. Arguments of type `Option<T>` accept `None` values, e.g.
+/// for nullable objects. The `Closure` panics if it is invoked with the wrong number of arguments
+/// or with arguments of the wrong type.
+///
+/// The callback has to be `Send` and `Sync`, use [`closure_local!`] for callbacks that can only
+/// be called from the current thread.
+///
+/// Like with [`clone!`], variables can be passed as strong or weak references into the closure.
+/// If upgrading a weak reference fails, the `Closure` returns no value.
+///
+/// ```
+/// use glib::closure;
+/// use std::sync::Arc;
+///
+/// let closure = closure!(|a: u32, b: &str| -> String { format!("{} {}", a, b) });
+/// let res = closure.invoke(&[&1u32, &"apples"]).unwrap();
+/// assert_eq!(res.get::<String>(), Ok(Some(String::from("1 apples"))));
+///
+/// let v = Arc::new(2u32);
+/// let closure = closure!(@weak v => move |a: u32| -> u32 { a * *v });
+/// let res = closure.invoke(&[&3u32]).unwrap();
+/// assert_eq!(res.get_some::<u32>(), Ok(6));
+/// ```
+///
+/// [`Closure`]: closure/struct.Closure.html
+/// [`closure_local!`]: macro.closure_local.html
+/// [`clone!`]: macro.clone.html
+#[macro_export]
+macro_rules! closure {
+    ($($tt:tt)*) => (
+        $crate::closure_impl!(new, $($tt)*)
+    );
+}
+
+/// Macro for creating a [`Closure`] from a Rust closure with typed arguments.
+///
+/// Different to [`closure!`], the callback does not have to be `Send` or `Sync` but the `Closure`
+/// must only be invoked from the current thread.
+///
+/// [`Closure`]: closure/struct.Closure.html
+/// [`closure!`]: macro.closure.html
+#[macro_export]
+macro_rules! closure_local {
+    ($($tt:tt)*) => (
+        $crate::closure_impl!(new_local, $($tt)*)
+    );
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
@@ -174,4 +365,64 @@ mod tests {
         let int_res = result.map(|result| result.get_some::<i32>());
         assert_eq!(int_res, Some(Ok(24)));
     }
+
+    #[test]
+    fn test_closure_mut() {
+        let mut count = 0;
+        let closure = Closure::new_mut(move |_values| {
+            count += 1;
+            Some(count.to_value())
+        });
+        let result = closure.invoke(&[]);
+        assert_eq!(result.map(|result| result.get_some::<i32>()), Some(Ok(1)));
+        let result = closure.invoke(&[]);
+        assert_eq!(result.map(|result| result.get_some::<i32>()), Some(Ok(2)));
+
+        // A panicking callback neither poisons the closure nor leaves it marked as running
+        let callback = super::serialize_mut(|values| {
+            if values.is_empty() {
+                panic!("no values");
+            }
+            None
+        });
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| callback(&[]))).is_err());
+        assert!(callback(&[1.to_value()]).is_none());
+
+        let mut values = Vec::new();
+        let closure = Closure::new_local_mut(move |args| {
+            values.push(args[0].get_some::<i32>().unwrap());
+            Some((values.len() as u32).to_value())
+        });
+        closure.invoke(&[&1]);
+        let result = closure.invoke(&[&2]);
+        assert_eq!(result.map(|result| result.get_some::<u32>()), Some(Ok(2)));
+    }
+
+    #[test]
+    fn test_closure_macro() {
+        let closure = closure!(|a: i32, b: String| -> i32 {
+            assert_eq!(b, "test");
+            a * 2
+        });
+        let result = closure.invoke(&[&21, &"test".to_string()]);
+        assert_eq!(result.map(|result| result.get_some::<i32>()), Some(Ok(42)));
+
+        let called = Arc::new(AtomicUsize::new(0));
+        let closure = closure!(@strong called => move |a: Option<String>| {
+            assert_eq!(a, None);
+            called.fetch_add(1, Ordering::Relaxed);
+        });
+        assert!(closure.invoke(&[&None::<String>]).is_none());
+        assert_eq!(called.load(Ordering::Relaxed), 1);
+
+        let v = Rc::new(Cell::new(0));
+        let closure = closure_local!(@weak v => move || -> i32 {
+            v.set(v.get() + 1);
+            v.get()
+        });
+        let result = closure.invoke(&[]);
+        assert_eq!(result.map(|result| result.get_some::<i32>()), Some(Ok(1)));
+        drop(v);
+        assert!(closure.invoke(&[]).is_none());
+    }
 }
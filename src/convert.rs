@@ -0,0 +1,150 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::c_char;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::slice;
+use translate::*;
+use Error;
+use GString;
+
+unsafe fn bytes_from_glib_full(ptr: *mut c_char, len: usize) -> Vec<u8> {
+    let bytes = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+    glib_sys::g_free(ptr as *mut _);
+    bytes
+}
+
+/// Converts `str` from the character set `from_codeset` to `to_codeset`.
+pub fn convert(str: &[u8], to_codeset: &str, from_codeset: &str) -> Result<Vec<u8>, Error> {
+    unsafe {
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_convert(
+            str.as_ptr() as *const _,
+            str.len().into_glib(),
+            to_codeset.to_glib_none().0,
+            from_codeset.to_glib_none().0,
+            ptr::null_mut(),
+            &mut bytes_written,
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(bytes_from_glib_full(ret, bytes_written))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+/// Converts `opsysstring` from the encoding of the current locale to UTF-8.
+pub fn locale_to_utf8(opsysstring: &[u8]) -> Result<GString, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_locale_to_utf8(
+            opsysstring.as_ptr() as *const _,
+            opsysstring.len().into_glib(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+/// Converts `utf8string` from UTF-8 to the encoding of the current locale.
+pub fn locale_from_utf8(utf8string: &str) -> Result<Vec<u8>, Error> {
+    unsafe {
+        let mut bytes_written = 0;
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_locale_from_utf8(
+            utf8string.as_ptr() as *const _,
+            utf8string.len().into_glib(),
+            ptr::null_mut(),
+            &mut bytes_written,
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(bytes_from_glib_full(ret, bytes_written))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+/// Converts `filename` from the GLib filename encoding to UTF-8.
+pub fn filename_to_utf8<P: AsRef<Path>>(filename: P) -> Result<GString, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_filename_to_utf8(
+            filename.as_ref().to_glib_none().0,
+            -1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+/// Converts `utf8string` from UTF-8 to the GLib filename encoding.
+pub fn filename_from_utf8(utf8string: &str) -> Result<PathBuf, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_filename_from_utf8(
+            utf8string.to_glib_none().0,
+            -1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ConvertError;
+
+    #[test]
+    fn convert_latin1() {
+        let latin1 = convert("äöü".as_bytes(), "ISO-8859-1", "UTF-8").unwrap();
+        assert_eq!(latin1, b"\xe4\xf6\xfc");
+        let utf8 = convert(&latin1, "UTF-8", "ISO-8859-1").unwrap();
+        assert_eq!(utf8, "äöü".as_bytes());
+    }
+
+    #[test]
+    fn convert_error() {
+        let err = convert(b"\xff", "ISO-8859-1", "UTF-8").unwrap_err();
+        assert_eq!(
+            err.kind::<ConvertError>(),
+            Some(ConvertError::IllegalSequence)
+        );
+
+        let err = convert(b"abc", "UTF-8", "NOT-A-CHARSET").unwrap_err();
+        assert_eq!(err.kind::<ConvertError>(), Some(ConvertError::NoConversion));
+    }
+
+    #[test]
+    fn filename() {
+        let path = filename_from_utf8("foo.txt").unwrap();
+        assert_eq!(path, Path::new("foo.txt"));
+        assert_eq!(filename_to_utf8(&path).unwrap(), "foo.txt");
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use std::time::Duration;
+use translate::*;
+
+use DateTime;
+use TimeSpan;
+
+impl DateTime {
+    /// Creates a copy of `self` and adds `duration` to it.
+    ///
+    /// Returns `None` if the result is out of the range of `DateTime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` is not representable as a `TimeSpan`.
+    pub fn add_duration(&self, duration: Duration) -> Option<DateTime> {
+        self.add(duration.into_glib_micros())
+    }
+
+    /// Creates a copy of `self` and subtracts `duration` from it.
+    ///
+    /// Returns `None` if the result is out of the range of `DateTime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` is not representable as a `TimeSpan`.
+    pub fn sub_duration(&self, duration: Duration) -> Option<DateTime> {
+        self.add(-duration.into_glib_micros::<TimeSpan>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_duration() {
+        let dt = DateTime::new_utc(2020, 1, 1, 0, 0, 0.0).unwrap();
+        let later = dt.add_duration(Duration::from_millis(1500)).unwrap();
+        assert_eq!(later.difference(&dt), 1_500_000);
+        assert_eq!(later.sub_duration(Duration::from_millis(1500)).unwrap(), dt);
+    }
+}
@@ -9,8 +9,11 @@ use std::borrow::Cow;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
+use std::io;
 use std::str;
+use std::sync::Arc;
 use translate::*;
+use FileError;
 use Quark;
 
 glib_wrapper! {
@@ -40,6 +43,48 @@ impl Error {
         }
     }
 
+    /// Creates an error with supplied error enum variant and a message that includes
+    /// `source` and all errors of its [`source`] chain.
+    ///
+    /// A `GError` can't store Rust values, so the chain is preserved as part of the message,
+    /// in the form `message: source: source of source`. This keeps the full context available
+    /// after the error was passed through C code.
+    ///
+    /// [`source`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.source
+    pub fn with_source<T: ErrorDomain>(
+        error: T,
+        message: &str,
+        source: &(dyn error::Error + 'static),
+    ) -> Error {
+        let mut message = String::from(message);
+        let mut source = Some(source);
+        while let Some(err) = source {
+            if !message.is_empty() {
+                message.push_str(": ");
+            }
+            message.push_str(&err.to_string());
+            source = err.source();
+        }
+        Error::new(error, &message)
+    }
+
+    /// Wraps an arbitrary Rust error into the error domain of `T`.
+    ///
+    /// The message consists of `err` and its [`source`] chain as described for
+    /// [`with_source`](#method.with_source).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let err: Box<dyn std::error::Error> = ...;
+    /// let err = glib::Error::from_error(FileError::Failed, &*err);
+    /// ```
+    ///
+    /// [`source`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.source
+    pub fn from_error<T: ErrorDomain>(error: T, err: &(dyn error::Error + 'static)) -> Error {
+        Error::with_source(error, "", err)
+    }
+
     /// Checks if the error domain matches `T`.
     pub fn is<T: ErrorDomain>(&self) -> bool {
         self.0.domain == T::domain().to_glib()
@@ -77,7 +122,21 @@ impl Error {
         }
     }
 
-    fn message(&self) -> &str {
+    /// Checks if the error matches the specified error enum variant.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if error.matches(FileError::Exist) {
+    ///     ...
+    /// }
+    /// ```
+    pub fn matches<T: ErrorDomain>(&self, err: T) -> bool {
+        self.is::<T>() && self.0.code == err.code()
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
         unsafe {
             let bytes = CStr::from_ptr(self.0.message).to_bytes();
             str::from_utf8(bytes)
@@ -104,6 +163,30 @@ impl fmt::Debug for Error {
 
 impl error::Error for Error {}
 
+impl From<io::Error> for Error {
+    /// Converts into the `FileError` domain.
+    ///
+    /// The code is derived from the OS error number if there is one, otherwise from the
+    /// error kind.
+    fn from(err: io::Error) -> Self {
+        let code = match err.raw_os_error() {
+            Some(errno) => FileError::from_errno(errno),
+            None => match err.kind() {
+                io::ErrorKind::NotFound => FileError::Noent,
+                io::ErrorKind::PermissionDenied => FileError::Acces,
+                io::ErrorKind::AlreadyExists => FileError::Exist,
+                io::ErrorKind::BrokenPipe => FileError::Pipe,
+                io::ErrorKind::WouldBlock => FileError::Again,
+                io::ErrorKind::InvalidInput => FileError::Inval,
+                io::ErrorKind::Interrupted => FileError::Intr,
+                io::ErrorKind::UnexpectedEof => FileError::Io,
+                _ => FileError::Failed,
+            },
+        };
+        Error::from_error(code, &err)
+    }
+}
+
 /// `GLib` error domain.
 ///
 /// This trait is implemented by error enums that represent error domains (types).
@@ -167,6 +250,8 @@ pub struct BoolError {
     pub function: &'static str,
     #[doc(hidden)]
     pub line: u32,
+    #[doc(hidden)]
+    pub source: Option<Arc<dyn error::Error + Send + Sync + 'static>>,
 }
 
 impl BoolError {
@@ -181,9 +266,18 @@ impl BoolError {
             filename,
             function,
             line,
+            source: None,
         }
     }
 
+    /// Sets the underlying error that caused this error.
+    ///
+    /// It is returned from [`source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source).
+    pub fn with_source<E: error::Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
     pub fn from_glib<Msg: Into<Cow<'static, str>>>(
         b: glib_sys::gboolean,
         message: Msg,
@@ -204,11 +298,19 @@ impl fmt::Display for BoolError {
     }
 }
 
-impl error::Error for BoolError {}
+impl error::Error for BoolError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn error::Error + 'static))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use FileError;
+    use KeyFileError;
 
     #[test]
     fn test_bool_error() {
@@ -235,4 +337,53 @@ mod tests {
         let true_dynamic_res = glib_result_from_gboolean!(glib_sys::GTRUE, "{} message", "Dynamic");
         assert!(true_dynamic_res.is_ok());
     }
+
+    #[test]
+    fn test_matches() {
+        let err = Error::new(FileError::Exist, "Exists");
+        assert!(err.is::<FileError>());
+        assert!(!err.is::<KeyFileError>());
+        assert!(err.matches(FileError::Exist));
+        assert!(!err.matches(FileError::Noent));
+        assert!(!err.matches(KeyFileError::UnknownEncoding));
+        assert_eq!(err.kind::<FileError>(), Some(FileError::Exist));
+        assert_eq!(err.kind::<KeyFileError>(), None);
+        assert_eq!(err.message(), "Exists");
+    }
+
+    #[test]
+    fn test_source() {
+        let io_err = io::Error::new(io::ErrorKind::Other, "disk on fire");
+        let bool_err = glib_bool_error!("Failed to save").with_source(io_err);
+        assert_eq!(
+            error::Error::source(&bool_err).unwrap().to_string(),
+            "disk on fire"
+        );
+        assert!(error::Error::source(&bool_err.clone()).is_some());
+
+        let err = Error::with_source(FileError::Failed, "Failed to load", &bool_err);
+        assert!(err.matches(FileError::Failed));
+        assert_eq!(
+            err.message(),
+            "Failed to load: Failed to save: disk on fire"
+        );
+
+        let boxed: Box<dyn error::Error> = Box::new(bool_err);
+        let err = Error::from_error(KeyFileError::Parse, &*boxed);
+        assert!(err.matches(KeyFileError::Parse));
+        assert_eq!(err.message(), "Failed to save: disk on fire");
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert!(err.matches(FileError::Noent));
+        assert_eq!(err.message(), "no such file");
+
+        let err = Error::from(io::Error::from_raw_os_error(::libc::EEXIST));
+        assert!(err.matches(FileError::Exist));
+
+        let err = Error::from(io::Error::new(io::ErrorKind::Other, "oops"));
+        assert!(err.matches(FileError::Failed));
+    }
 }
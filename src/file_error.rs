@@ -36,6 +36,15 @@ pub enum FileError {
     Failed,
 }
 
+impl FileError {
+    /// Returns the `FileError` corresponding to the `errno` value `err_no`.
+    ///
+    /// Unknown values are mapped to `FileError::Failed`.
+    pub fn from_errno(err_no: i32) -> FileError {
+        unsafe { ErrorDomain::from(glib_sys::g_file_error_from_errno(err_no)).unwrap() }
+    }
+}
+
 impl ErrorDomain for FileError {
     fn domain() -> Quark {
         unsafe { from_glib(glib_sys::g_file_error_quark()) }
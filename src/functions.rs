@@ -31,8 +31,8 @@ use SpawnFlags;
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_async_with_fds<P: AsRef<std::path::Path>, T: AsRawFd, U: AsRawFd, V: AsRawFd>(
     working_directory: P,
-    argv: &[&str],
-    envp: &[&str],
+    argv: &[&std::path::Path],
+    envp: &[&std::path::Path],
     flags: SpawnFlags,
     child_setup: Option<Box_<dyn FnOnce() + 'static>>,
     stdin_fd: T,
@@ -88,8 +88,8 @@ pub fn spawn_async_with_fds<P: AsRef<std::path::Path>, T: AsRawFd, U: AsRawFd, V
 //     V: AsRawHandle,
 // >(
 //     working_directory: P,
-//     argv: &[&str],
-//     envp: &[&str],
+//     argv: &[&std::path::Path],
+//     envp: &[&std::path::Path],
 //     flags: SpawnFlags,
 //     child_setup: Option<Box_<dyn FnOnce() + 'static>>,
 //     stdin_fd: T,
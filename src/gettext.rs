@@ -0,0 +1,234 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Translation of messages with gettext.
+//!
+//! The [`gettext!`](../macro.gettext.html), [`ngettext!`](../macro.ngettext.html) and
+//! [`pgettext!`](../macro.pgettext.html) macros look up translations in the default text
+//! domain, which is set with [`textdomain`](../fn.textdomain.html), and substitute `{}` and
+//! `{0}`-style placeholders in the translated message with their arguments. As the format
+//! string is only known at runtime, no other format specifiers are supported.
+//!
+//! Translations are looked up with `g_dgettext()` and related functions, so they follow the
+//! same rules as C code using GLib, e.g. messages are not translated if only the locale of
+//! `LC_MESSAGES` but not of `LANGUAGE` has a translation.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #[macro_use]
+//! extern crate glib;
+//!
+//! # fn main() {
+//! glib::bindtextdomain("myapp", "/usr/share/locale").unwrap();
+//! glib::bind_textdomain_codeset("myapp", "UTF-8").unwrap();
+//! glib::textdomain("myapp").unwrap();
+//!
+//! let n = 3;
+//! println!("{}", gettext!("Hello {}!", "world"));
+//! println!("{}", ngettext!("One file", "{} files", n, n));
+//! println!("{}", pgettext!("menu", "Open"));
+//! # }
+//! ```
+
+use error::BoolError;
+use libc::c_char;
+use std::fmt::{self, Write};
+use std::path::Path;
+use translate::*;
+
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), feature = "dox"))]
+extern "C" {
+    #[link_name = "bindtextdomain"]
+    fn c_bindtextdomain(domainname: *const c_char, dirname: *const c_char) -> *mut c_char;
+    #[link_name = "bind_textdomain_codeset"]
+    fn c_bind_textdomain_codeset(domainname: *const c_char, codeset: *const c_char) -> *mut c_char;
+    #[link_name = "textdomain"]
+    fn c_textdomain(domainname: *const c_char) -> *mut c_char;
+}
+
+/// Sets the directory containing the message catalogs of `domain`.
+///
+/// This is only available with glibc, where gettext is part of the C library. On other
+/// platforms the application has to be linked against `libintl` itself.
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), feature = "dox"))]
+pub fn bindtextdomain<P: AsRef<Path>>(domain: &str, dirname: P) -> Result<(), BoolError> {
+    unsafe {
+        if c_bindtextdomain(domain.to_glib_none().0, dirname.as_ref().to_glib_none().0).is_null() {
+            Err(glib_bool_error!("Failed to bind text domain"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Sets the encoding of the translations returned for `domain`.
+///
+/// This should always be `"UTF-8"`, as the translation functions of this crate assume that
+/// the returned strings are valid UTF-8.
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), feature = "dox"))]
+pub fn bind_textdomain_codeset(domain: &str, codeset: &str) -> Result<(), BoolError> {
+    unsafe {
+        if c_bind_textdomain_codeset(domain.to_glib_none().0, codeset.to_glib_none().0).is_null() {
+            Err(glib_bool_error!("Failed to set text domain codeset"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Sets the default text domain that is used by the translation macros.
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), feature = "dox"))]
+pub fn textdomain(domain: &str) -> Result<(), BoolError> {
+    unsafe {
+        if c_textdomain(domain.to_glib_none().0).is_null() {
+            Err(glib_bool_error!("Failed to set text domain"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Replaces the placeholders in a translated message with `args`.
+///
+/// `{}` is replaced with the next argument and `{N}` with the `N`th one, while `{{` and `}}`
+/// are unescaped. Placeholders without a corresponding argument are kept unchanged.
+#[doc(hidden)]
+pub fn format_translation(format: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut result = String::with_capacity(format.len());
+    let mut next_arg = 0;
+    let mut rest = format;
+
+    while let Some(pos) = rest.find(|c| c == '{' || c == '}') {
+        result.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            result.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+
+        if tail.starts_with('{') {
+            if let Some(end) = tail.find('}') {
+                let spec = &tail[1..end];
+                let index = if spec.is_empty() {
+                    next_arg += 1;
+                    Some(next_arg - 1)
+                } else {
+                    spec.parse::<usize>().ok()
+                };
+                if let Some(arg) = index.and_then(|index| args.get(index)) {
+                    let _ = write!(result, "{}", arg);
+                    rest = &tail[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        result.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Translates a message and substitutes its placeholders.
+///
+/// See the [`gettext`](gettext/index.html) module for details.
+///
+/// ```
+/// #[macro_use]
+/// extern crate glib;
+///
+/// # fn main() {
+/// assert_eq!(gettext!("Hello {}!", "world"), "Hello world!");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! gettext {
+    ($msgid:expr $(, $arg:expr)* $(,)?) => {
+        $crate::gettext::format_translation(
+            &$crate::dgettext(None, $msgid),
+            &[$(&$arg as &dyn ::std::fmt::Display),*],
+        )
+    };
+}
+
+/// Translates a message with a singular and plural form depending on `n`, and substitutes its
+/// placeholders.
+///
+/// ```
+/// #[macro_use]
+/// extern crate glib;
+///
+/// # fn main() {
+/// assert_eq!(ngettext!("One file", "{} files", 1, 1), "One file");
+/// assert_eq!(ngettext!("One file", "{} files", 3, 3), "3 files");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ngettext {
+    ($msgid:expr, $msgid_plural:expr, $n:expr $(, $arg:expr)* $(,)?) => {
+        $crate::gettext::format_translation(
+            &$crate::dngettext(None, $msgid, $msgid_plural, $n as _),
+            &[$(&$arg as &dyn ::std::fmt::Display),*],
+        )
+    };
+}
+
+/// Translates a message in the given context, and substitutes its placeholders.
+///
+/// The context disambiguates identical messages with different meanings, like "Open" as a
+/// verb or adjective.
+///
+/// ```
+/// #[macro_use]
+/// extern crate glib;
+///
+/// # fn main() {
+/// assert_eq!(pgettext!("menu", "Open"), "Open");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pgettext {
+    ($context:expr, $msgid:expr $(, $arg:expr)* $(,)?) => {
+        $crate::gettext::format_translation(
+            &$crate::dpgettext2(None, $context, $msgid),
+            &[$(&$arg as &dyn ::std::fmt::Display),*],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            format_translation("no placeholders", &[]),
+            "no placeholders"
+        );
+        assert_eq!(format_translation("{} and {}", &[&1, &"two"]), "1 and two");
+        assert_eq!(
+            format_translation("{1} before {0}", &[&"a", &"b"]),
+            "b before a"
+        );
+        assert_eq!(format_translation("{{}} {}", &[&1]), "{} 1");
+        assert_eq!(format_translation("{} {} {x} }", &[&1]), "1 {} {x} }");
+        assert_eq!(format_translation("ü{}ö", &[&'ä']), "üäö");
+    }
+
+    #[test]
+    fn macros() {
+        // Without a message catalog the original messages are returned
+        assert_eq!(gettext!("Plain"), "Plain");
+        assert_eq!(gettext!("{} + {} = {}", 1, 2, 1 + 2,), "1 + 2 = 3");
+        assert_eq!(ngettext!("{} item", "{} items", 1u32, 1), "1 item");
+        assert_eq!(ngettext!("{} item", "{} items", 2usize, 2), "2 items");
+        assert_eq!(pgettext!("verb", "Open {}", "file"), "Open file");
+    }
+}
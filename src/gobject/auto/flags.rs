@@ -122,3 +122,51 @@ impl FromGlib<gobject_sys::GSignalFlags> for SignalFlags {
         SignalFlags::from_bits_truncate(value)
     }
 }
+
+bitflags! {
+    pub struct TypeFlags: u32 {
+        const ABSTRACT = 16;
+        const VALUE_ABSTRACT = 32;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for TypeFlags {
+    type GlibType = gobject_sys::GTypeFlags;
+
+    fn to_glib(&self) -> gobject_sys::GTypeFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<gobject_sys::GTypeFlags> for TypeFlags {
+    fn from_glib(value: gobject_sys::GTypeFlags) -> TypeFlags {
+        TypeFlags::from_bits_truncate(value)
+    }
+}
+
+bitflags! {
+    pub struct TypeFundamentalFlags: u32 {
+        const CLASSED = 1;
+        const INSTANTIATABLE = 2;
+        const DERIVABLE = 4;
+        const DEEP_DERIVABLE = 8;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for TypeFundamentalFlags {
+    type GlibType = gobject_sys::GTypeFundamentalFlags;
+
+    fn to_glib(&self) -> gobject_sys::GTypeFundamentalFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<gobject_sys::GTypeFundamentalFlags> for TypeFundamentalFlags {
+    fn from_glib(value: gobject_sys::GTypeFundamentalFlags) -> TypeFundamentalFlags {
+        TypeFundamentalFlags::from_bits_truncate(value)
+    }
+}
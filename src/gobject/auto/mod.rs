@@ -9,6 +9,8 @@ mod flags;
 pub use self::flags::BindingFlags;
 pub use self::flags::ParamFlags;
 pub use self::flags::SignalFlags;
+pub use self::flags::TypeFlags;
+pub use self::flags::TypeFundamentalFlags;
 
 #[doc(hidden)]
 pub mod traits {}
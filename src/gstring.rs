@@ -3,11 +3,12 @@
 // Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
 
 use libc;
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::cmp::Ordering;
 use std::ffi::{CStr, CString, OsStr};
 use std::fmt;
-use std::hash;
+use std::hash::{self, Hash};
+use std::mem;
 use std::ops::Deref;
 use std::os::raw::c_char;
 use std::ptr;
@@ -82,6 +83,19 @@ impl GString {
         };
         cstr.to_str().unwrap()
     }
+
+    /// Converts the string into its bytes, without the trailing nul byte.
+    ///
+    /// This doesn't copy the data if the string was created from Rust.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        if let GString(Inner::Native(ref mut cstring)) = self {
+            return cstring
+                .take()
+                .expect("Native shouldn't be empty")
+                .into_bytes();
+        }
+        self.as_bytes().to_vec()
+    }
 }
 
 impl Drop for GString {
@@ -102,16 +116,8 @@ impl fmt::Display for GString {
 
 impl hash::Hash for GString {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        let bytes = match self {
-            GString(Inner::Foreign(ptr, length)) => unsafe {
-                slice::from_raw_parts(*ptr as *const u8, length + 1)
-            },
-            GString(Inner::Native(cstring)) => cstring
-                .as_ref()
-                .expect("Native shouldn't be empty")
-                .as_bytes(),
-        };
-        state.write(bytes);
+        // Must be consistent with the `str` implementation for `Borrow<str>`
+        self.as_str().hash(state)
     }
 }
 
@@ -245,6 +251,55 @@ impl From<GString> for Box<str> {
     }
 }
 
+impl From<GString> for Vec<u8> {
+    #[inline]
+    fn from(s: GString) -> Self {
+        s.into_bytes()
+    }
+}
+
+impl From<GString> for ::Bytes {
+    /// Converts the string into `Bytes` without copying the data.
+    fn from(mut s: GString) -> Self {
+        match s.0 {
+            Inner::Native(ref mut cstring) => ::Bytes::from_owned(
+                cstring
+                    .take()
+                    .expect("Native shouldn't be empty")
+                    .into_bytes(),
+            ),
+            Inner::Foreign(ptr, length) => unsafe {
+                mem::forget(s);
+                from_glib_full(glib_sys::g_bytes_new_take(ptr as *mut _, length))
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a GString> for Cow<'a, str> {
+    #[inline]
+    fn from(s: &'a GString) -> Self {
+        Cow::Borrowed(s.as_str())
+    }
+}
+
+impl From<GString> for Cow<'static, str> {
+    #[inline]
+    fn from(s: GString) -> Self {
+        Cow::Owned(s.into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for GString {
+    #[inline]
+    fn from(s: Cow<'a, str>) -> Self {
+        match s {
+            Cow::Borrowed(s) => s.into(),
+            Cow::Owned(s) => s.into(),
+        }
+    }
+}
+
 impl From<String> for GString {
     #[inline]
     fn from(s: String) -> Self {
@@ -288,6 +343,20 @@ impl<'a> From<&'a CStr> for GString {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for GString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as serde::Deserialize>::deserialize(deserializer).map(GString::from)
+    }
+}
+
 #[doc(hidden)]
 impl FromGlibPtrFull<*const c_char> for GString {
     #[inline]
@@ -403,6 +472,15 @@ impl GlibPtrDefault for GString {
     type GlibType = *const c_char;
 }
 
+impl HashTableValue for GString {
+    const DESTROY_FUNC: glib_sys::GDestroyNotify = Some(glib_sys::g_free);
+}
+
+impl HashTableKey for GString {
+    const HASH_FUNC: glib_sys::GHashFunc = Some(glib_sys::g_str_hash);
+    const EQUAL_FUNC: glib_sys::GEqualFunc = Some(glib_sys::g_str_equal);
+}
+
 impl StaticType for GString {
     fn static_type() -> Type {
         String::static_type()
@@ -447,7 +525,9 @@ impl_from_glib_container_as_vec_string!(GString, *mut c_char);
 mod tests {
     use glib_sys;
     use gstring::GString;
+    use std::borrow::Cow;
     use std::ffi::CString;
+    use translate::from_glib_full;
 
     #[test]
     fn test_gstring() {
@@ -518,5 +598,46 @@ mod tests {
         h.insert(gstring, 42);
         let gstring: GString = "foo".into();
         assert!(h.contains_key(&gstring));
+        assert_eq!(h.get("foo"), Some(&42));
+
+        let foreign: GString =
+            unsafe { from_glib_full(glib_sys::g_strdup(b"foo\0".as_ptr() as *const _)) };
+        assert!(h.contains_key(&foreign));
+    }
+
+    #[test]
+    fn test_into_bytes() {
+        let gstring = GString::from("foo");
+        assert_eq!(gstring.into_bytes(), b"foo");
+
+        let foreign: GString =
+            unsafe { from_glib_full(glib_sys::g_strdup(b"bar\0".as_ptr() as *const _)) };
+        assert_eq!(Vec::from(foreign), b"bar");
+
+        let foreign: GString =
+            unsafe { from_glib_full(glib_sys::g_strdup(b"baz\0".as_ptr() as *const _)) };
+        assert_eq!(::Bytes::from(foreign), b"baz".as_ref());
+        assert_eq!(::Bytes::from(GString::from("qux")), b"qux".as_ref());
+    }
+
+    #[test]
+    fn test_cow() {
+        let gstring = GString::from(Cow::Borrowed("foo"));
+        let cow: Cow<str> = (&gstring).into();
+        assert_eq!(cow, "foo");
+        let cow: Cow<str> = gstring.into();
+        assert_eq!(cow, Cow::<str>::Owned("foo".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        extern crate serde_json;
+
+        let gstring = GString::from("foo \"bar\"");
+        let json = serde_json::to_string(&gstring).unwrap();
+        assert_eq!(json, r#""foo \"bar\"""#);
+        let gstring2: GString = serde_json::from_str(&json).unwrap();
+        assert_eq!(gstring2, gstring);
     }
 }
@@ -0,0 +1,418 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Bindings for `GIOChannel`, mostly useful when interacting with C APIs that use them.
+//!
+//! New code should use `std::io` directly, or `gio` streams for asynchronous I/O.
+
+use glib_sys::{self, gboolean, gpointer};
+use libc::c_char;
+use std::cell::RefCell;
+#[cfg(unix)]
+use std::convert::TryFrom;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
+use std::ptr;
+use std::slice;
+use std::str;
+use translate::*;
+use Continue;
+use ConvertError;
+use Error;
+use FileError;
+use GString;
+use IOCondition;
+use MainContext;
+#[cfg(unix)]
+use SeekType;
+use SourceId;
+
+#[cfg(all(not(unix), feature = "dox"))]
+use libc::c_int as RawFd;
+
+#[cfg(any(windows, feature = "dox"))]
+extern "C" {
+    fn g_io_channel_win32_new_fd(fd: libc::c_int) -> *mut glib_sys::GIOChannel;
+    fn g_io_channel_win32_new_socket(socket: libc::c_int) -> *mut glib_sys::GIOChannel;
+    fn g_io_channel_win32_new_messages(hwnd: usize) -> *mut glib_sys::GIOChannel;
+}
+
+glib_wrapper! {
+    /// A reference counted channel for reading from and writing to files, pipes and sockets.
+    ///
+    /// By default channels use UTF-8 encoding, which fails for binary data. Use
+    /// [`set_encoding(None)`](#method.set_encoding) before reading or writing arbitrary bytes.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct IOChannel(Shared<glib_sys::GIOChannel>);
+
+    match fn {
+        ref => |ptr| glib_sys::g_io_channel_ref(ptr),
+        unref => |ptr| glib_sys::g_io_channel_unref(ptr),
+        get_type => || glib_sys::g_io_channel_get_type(),
+    }
+}
+
+impl IOChannel {
+    /// Opens `filename` with an `fopen()`-style `mode` like `"r"`, `"w"` or `"a+"`.
+    pub fn new_file<P: AsRef<Path>>(filename: P, mode: &str) -> Result<IOChannel, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_io_channel_new_file(
+                filename.as_ref().to_glib_none().0,
+                mode.to_glib_none().0,
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    /// Creates a channel for the file descriptor `fd`.
+    ///
+    /// The file descriptor is not closed when the channel is freed unless
+    /// [`set_close_on_unref`](#method.set_close_on_unref) is used.
+    #[cfg(any(unix, feature = "dox"))]
+    pub fn unix_new(fd: RawFd) -> IOChannel {
+        unsafe { from_glib_full(glib_sys::g_io_channel_unix_new(fd)) }
+    }
+
+    /// Creates a channel for the C runtime file descriptor `fd`.
+    #[cfg(any(windows, feature = "dox"))]
+    pub fn win32_new_fd(fd: i32) -> IOChannel {
+        unsafe { from_glib_full(g_io_channel_win32_new_fd(fd)) }
+    }
+
+    /// Creates a channel for the Winsock socket `socket`.
+    #[cfg(any(windows, feature = "dox"))]
+    pub fn win32_new_socket(socket: i32) -> IOChannel {
+        unsafe { from_glib_full(g_io_channel_win32_new_socket(socket)) }
+    }
+
+    /// Creates a channel for the window messages of the window handle `hwnd`.
+    #[cfg(any(windows, feature = "dox"))]
+    pub fn win32_new_messages(hwnd: usize) -> IOChannel {
+        unsafe { from_glib_full(g_io_channel_win32_new_messages(hwnd)) }
+    }
+
+    /// Returns the encoding used for reading and writing, or `None` for binary data.
+    pub fn get_encoding(&self) -> Option<GString> {
+        unsafe { from_glib_none(glib_sys::g_io_channel_get_encoding(self.to_glib_none().0)) }
+    }
+
+    /// Sets the encoding used for reading and writing.
+    ///
+    /// `None` makes the channel read and write raw bytes, and is the only encoding that allows
+    /// the channel to be unbuffered.
+    pub fn set_encoding(&self, encoding: Option<&str>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_io_channel_set_encoding(
+                self.to_glib_none().0,
+                encoding.to_glib_none().0,
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    pub fn get_buffered(&self) -> bool {
+        unsafe { from_glib(glib_sys::g_io_channel_get_buffered(self.to_glib_none().0)) }
+    }
+
+    /// Enables or disables buffering, which can only be disabled if the encoding is `None`.
+    pub fn set_buffered(&self, buffered: bool) {
+        unsafe {
+            glib_sys::g_io_channel_set_buffered(self.to_glib_none().0, buffered.to_glib());
+        }
+    }
+
+    pub fn get_close_on_unref(&self) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_io_channel_get_close_on_unref(
+                self.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Sets whether the underlying file descriptor is closed when the last reference to the
+    /// channel is dropped.
+    pub fn set_close_on_unref(&self, do_close: bool) {
+        unsafe {
+            glib_sys::g_io_channel_set_close_on_unref(self.to_glib_none().0, do_close.to_glib());
+        }
+    }
+
+    /// Returns which of `IOCondition::IN` and `IOCondition::OUT` can be satisfied from the
+    /// channel's buffers without touching the underlying file descriptor.
+    pub fn get_buffer_condition(&self) -> IOCondition {
+        unsafe {
+            from_glib(glib_sys::g_io_channel_get_buffer_condition(
+                self.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Reads the next line including the line terminator, or `None` at the end of the file.
+    ///
+    /// If the encoding is `None`, this fails for lines that are not valid UTF-8.
+    pub fn read_line(&self) -> Result<Option<GString>, Error> {
+        unsafe {
+            let mut line = ptr::null_mut();
+            let mut length = 0;
+            let mut error = ptr::null_mut();
+            let status = glib_sys::g_io_channel_read_line(
+                self.to_glib_none().0,
+                &mut line,
+                &mut length,
+                ptr::null_mut(),
+                &mut error,
+            );
+            match status {
+                glib_sys::G_IO_STATUS_NORMAL => {
+                    if str::from_utf8(slice::from_raw_parts(line as *const u8, length)).is_err() {
+                        glib_sys::g_free(line as gpointer);
+                        return Err(Error::new(
+                            ConvertError::IllegalSequence,
+                            "Invalid byte sequence in line",
+                        ));
+                    }
+                    Ok(Some(from_glib_full(line)))
+                }
+                glib_sys::G_IO_STATUS_EOF => Ok(None),
+                glib_sys::G_IO_STATUS_AGAIN => Err(Error::new(
+                    FileError::Again,
+                    "Resource temporarily unavailable",
+                )),
+                _ => Err(from_glib_full(error)),
+            }
+        }
+    }
+
+    /// Closes the channel, after writing out all buffered data if `flush` is `true`.
+    pub fn shutdown(&self, flush: bool) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ =
+                glib_sys::g_io_channel_shutdown(self.to_glib_none().0, flush.to_glib(), &mut error);
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    fn check_status(status: glib_sys::GIOStatus, error: *mut glib_sys::GError) -> io::Result<()> {
+        match status {
+            glib_sys::G_IO_STATUS_NORMAL | glib_sys::G_IO_STATUS_EOF => Ok(()),
+            glib_sys::G_IO_STATUS_AGAIN => Err(io::ErrorKind::WouldBlock.into()),
+            _ => {
+                let error: Error = unsafe { from_glib_full(error) };
+                Err(io::Error::new(io::ErrorKind::Other, error))
+            }
+        }
+    }
+}
+
+impl io::Read for IOChannel {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut bytes_read = 0;
+        let status = unsafe {
+            let mut error = ptr::null_mut();
+            let status = glib_sys::g_io_channel_read_chars(
+                self.to_glib_none().0,
+                buf.as_mut_ptr() as *mut c_char,
+                buf.len(),
+                &mut bytes_read,
+                &mut error,
+            );
+            IOChannel::check_status(status, error)
+        };
+        match status {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && bytes_read > 0 => {
+                Ok(bytes_read)
+            }
+            status => status.map(|_| bytes_read),
+        }
+    }
+}
+
+impl io::Write for IOChannel {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut bytes_written = 0;
+        let status = unsafe {
+            let mut error = ptr::null_mut();
+            let status = glib_sys::g_io_channel_write_chars(
+                self.to_glib_none().0,
+                buf.as_ptr() as *const c_char,
+                buf.len() as isize,
+                &mut bytes_written,
+                &mut error,
+            );
+            IOChannel::check_status(status, error)
+        };
+        match status {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock && bytes_written > 0 => {
+                Ok(bytes_written)
+            }
+            status => status.map(|_| bytes_written),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let status = glib_sys::g_io_channel_flush(self.to_glib_none().0, &mut error);
+            IOChannel::check_status(status, error)
+        }
+    }
+}
+
+/// Seeking is only supported on UNIX, as GLib doesn't report the new position and it has to be
+/// queried from the file descriptor.
+#[cfg(unix)]
+impl io::Seek for IOChannel {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let (offset, type_) = match pos {
+            io::SeekFrom::Start(offset) => (
+                i64::try_from(offset).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?,
+                SeekType::Set,
+            ),
+            io::SeekFrom::Current(offset) => (offset, SeekType::Cur),
+            io::SeekFrom::End(offset) => (offset, SeekType::End),
+        };
+        unsafe {
+            let mut error = ptr::null_mut();
+            let status = glib_sys::g_io_channel_seek_position(
+                self.to_glib_none().0,
+                offset,
+                type_.to_glib(),
+                &mut error,
+            );
+            IOChannel::check_status(status, error)?;
+            // Seeking discards the read buffer and flushes the write buffer, so the position of
+            // the file descriptor is the position of the channel
+            match libc::lseek(self.as_raw_fd(), 0, libc::SEEK_CUR) {
+                -1 => Err(io::Error::last_os_error()),
+                position => Ok(position as u64),
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for IOChannel {
+    fn as_raw_fd(&self) -> RawFd {
+        unsafe { glib_sys::g_io_channel_unix_get_fd(self.to_glib_none().0) }
+    }
+}
+
+unsafe extern "C" fn trampoline_watch<F: FnMut(&IOChannel, IOCondition) -> Continue + 'static>(
+    channel: *mut glib_sys::GIOChannel,
+    condition: glib_sys::GIOCondition,
+    func: gpointer,
+) -> gboolean {
+    let func: &RefCell<F> = &*(func as *const RefCell<F>);
+    let channel: Borrowed<IOChannel> = from_glib_borrow(channel);
+    (&mut *func.borrow_mut())(&channel, from_glib(condition)).to_glib()
+}
+
+unsafe extern "C" fn destroy_closure_watch<
+    F: FnMut(&IOChannel, IOCondition) -> Continue + 'static,
+>(
+    ptr: gpointer,
+) {
+    Box::<RefCell<F>>::from_raw(ptr as *mut _);
+}
+
+/// Adds a closure to be called by the default main loop whenever `channel` reaches the given
+/// IO condition.
+///
+/// `func` will be called repeatedly while the channel matches the given IO condition until it
+/// returns `Continue(false)`.
+///
+/// As channels can't be shared between threads, there is no variant of this function that
+/// allows `func` to be called from another thread than the one that owns the main context.
+///
+/// This function panics if called from a different thread than the one that owns the main
+/// context.
+pub fn io_add_watch_local<F>(channel: &IOChannel, condition: IOCondition, func: F) -> SourceId
+where
+    F: FnMut(&IOChannel, IOCondition) -> Continue + 'static,
+{
+    unsafe {
+        assert!(MainContext::default().is_owner());
+        let func: Box<RefCell<F>> = Box::new(RefCell::new(func));
+        from_glib(glib_sys::g_io_add_watch_full(
+            channel.to_glib_none().0,
+            glib_sys::G_PRIORITY_DEFAULT,
+            condition.to_glib(),
+            Some(trampoline_watch::<F>),
+            Box::into_raw(func) as gpointer,
+            Some(destroy_closure_watch::<F>),
+        ))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    #[test]
+    fn pipe() {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let reader = IOChannel::unix_new(fds[0]);
+        let mut writer = IOChannel::unix_new(fds[1]);
+        reader.set_close_on_unref(true);
+        assert_eq!(reader.as_raw_fd(), fds[0]);
+        reader.set_encoding(None).unwrap();
+        writer.set_encoding(None).unwrap();
+
+        writer.write_all(b"first line\nsecond\n\xff\x00").unwrap();
+        writer.shutdown(true).unwrap();
+
+        assert_eq!(reader.read_line().unwrap().unwrap(), "first line\n");
+        assert_eq!(reader.read_line().unwrap().unwrap(), "second\n");
+        assert_eq!(
+            reader.read_line().unwrap_err().kind::<ConvertError>(),
+            Some(ConvertError::IllegalSequence)
+        );
+        assert_eq!(reader.read_line().unwrap(), None);
+    }
+
+    #[test]
+    fn file() {
+        let path = ::get_tmp_dir()
+            .unwrap()
+            .join(format!("glib-rs-io-channel-test-{}", ::std::process::id()));
+
+        let mut channel = IOChannel::new_file(&path, "w+").unwrap();
+        channel.set_encoding(None).unwrap();
+        channel.write_all(b"0123456789").unwrap();
+        assert_eq!(channel.seek(SeekFrom::Start(2)).unwrap(), 2);
+        let mut buf = [0; 3];
+        channel.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"234");
+        assert_eq!(channel.seek(SeekFrom::Current(1)).unwrap(), 6);
+        assert_eq!(channel.seek(SeekFrom::End(-1)).unwrap(), 9);
+        channel.read_exact(&mut buf[..1]).unwrap();
+        assert_eq!(&buf[..1], b"9");
+        drop(channel);
+
+        let err = IOChannel::new_file(path.join("nonexistent"), "r").unwrap_err();
+        assert!(err.kind::<FileError>().is_some());
+        ::std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -251,4 +251,109 @@ impl KeyFile {
             }
         }
     }
+
+    pub fn set_boolean_list(&self, group_name: &str, key: &str, list: &[bool]) {
+        let list: Vec<glib_sys::gboolean> = list.iter().map(ToGlib::to_glib).collect();
+        unsafe {
+            glib_sys::g_key_file_set_boolean_list(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                mut_override(list.as_ptr()),
+                list.len(),
+            );
+        }
+    }
+
+    pub fn set_integer_list(&self, group_name: &str, key: &str, list: &[i32]) {
+        unsafe {
+            glib_sys::g_key_file_set_integer_list(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                mut_override(list.as_ptr()),
+                list.len(),
+            );
+        }
+    }
+
+    pub fn set_double_list(&self, group_name: &str, key: &str, list: &[f64]) {
+        unsafe {
+            glib_sys::g_key_file_set_double_list(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                mut_override(list.as_ptr()),
+                list.len(),
+            );
+        }
+    }
+
+    pub fn set_string_list(&self, group_name: &str, key: &str, list: &[&str]) {
+        unsafe {
+            glib_sys::g_key_file_set_string_list(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                list.to_glib_none().0,
+                list.len(),
+            );
+        }
+    }
+
+    pub fn set_locale_string_list(&self, group_name: &str, key: &str, locale: &str, list: &[&str]) {
+        unsafe {
+            glib_sys::g_key_file_set_locale_string_list(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                locale.to_glib_none().0,
+                list.to_glib_none().0,
+                list.len(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists() {
+        let key_file = KeyFile::new();
+        key_file.set_boolean_list("Group", "bools", &[true, false]);
+        key_file.set_integer_list("Group", "ints", &[1, -2, 3]);
+        key_file.set_double_list("Group", "doubles", &[0.5, 1.5]);
+        key_file.set_string_list("Group", "strings", &["a", "b;c"]);
+        key_file.set_locale_string_list("Group", "greeting", "de", &["Hallo", "Welt"]);
+
+        let key_file2 = KeyFile::new();
+        key_file2
+            .load_from_data(&key_file.to_data(), KeyFileFlags::NONE)
+            .unwrap();
+        assert_eq!(
+            key_file2.get_boolean_list("Group", "bools").unwrap(),
+            [true, false]
+        );
+        assert_eq!(
+            key_file2.get_integer_list("Group", "ints").unwrap(),
+            [1, -2, 3]
+        );
+        assert_eq!(
+            key_file2.get_double_list("Group", "doubles").unwrap(),
+            [0.5, 1.5]
+        );
+        assert_eq!(
+            key_file2.get_string_list("Group", "strings").unwrap(),
+            ["a", "b;c"]
+        );
+        assert_eq!(
+            key_file2
+                .get_locale_string_list("Group", "greeting", Some("de"))
+                .unwrap(),
+            ["Hallo", "Welt"]
+        );
+        assert_eq!(key_file2.get_keys("Group").unwrap().0.len(), 5);
+    }
 }
@@ -0,0 +1,148 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Tracking of live objects to find leaks.
+//!
+//! With the `leak_tracker` feature enabled, every construction and drop of an object wrapper is
+//! recorded together with the type of the object. Objects stay in the registry until they are
+//! finalized, even if no wrapper for them exists anymore, so that references held from C are
+//! noticed as well. [`leak_report`](fn.leak_report.html) returns all objects that are currently
+//! alive, e.g. for checking during test teardown that all objects of a closed window were freed.
+//!
+//! If the `GLIB_LEAK_TRACKER_BACKTRACE` environment variable is set, the backtrace of the first
+//! wrapper construction is recorded for every object. This is slow and should only be used while
+//! debugging.
+
+use backtrace::Backtrace;
+use glib_sys::gpointer;
+use gobject_sys;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::sync::Mutex;
+use translate::*;
+use Type;
+
+struct Entry {
+    type_: Type,
+    wrappers: usize,
+    backtrace: Option<String>,
+}
+
+static REGISTRY: Lazy<Mutex<HashMap<usize, Entry>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static CAPTURE_BACKTRACES: Lazy<bool> =
+    Lazy::new(|| env::var_os("GLIB_LEAK_TRACKER_BACKTRACE").is_some());
+
+// Called when the object is finalized. Unlike weak references, which are notified already
+// during dispose, qdata is only freed during finalization so that objects that were disposed,
+// e.g. destroyed widgets, but are still referenced are reported as well.
+unsafe extern "C" fn finalize_notify(ptr: gpointer) {
+    REGISTRY.lock().unwrap().remove(&(ptr as usize));
+}
+
+// Called whenever a new wrapper owning a strong reference to `ptr` is created
+#[doc(hidden)]
+pub unsafe fn track(ptr: *mut gobject_sys::GObject) {
+    let mut registry = REGISTRY.lock().unwrap();
+    if let Some(entry) = registry.get_mut(&(ptr as usize)) {
+        entry.wrappers += 1;
+        return;
+    }
+
+    let backtrace = if *CAPTURE_BACKTRACES {
+        Some(format!("{:?}", Backtrace::new()))
+    } else {
+        None
+    };
+
+    registry.insert(
+        ptr as usize,
+        Entry {
+            type_: from_glib((*(*ptr).g_type_instance.g_class).g_type),
+            wrappers: 1,
+            backtrace,
+        },
+    );
+    gobject_sys::g_object_set_qdata_full(
+        ptr,
+        ::static_quark!("glib-rs-leak-tracker").to_glib(),
+        ptr as gpointer,
+        Some(finalize_notify),
+    );
+}
+
+// Called whenever a wrapper owning a strong reference to `ptr` is dropped
+#[doc(hidden)]
+pub unsafe fn untrack(ptr: *mut gobject_sys::GObject) {
+    if let Some(entry) = REGISTRY.lock().unwrap().get_mut(&(ptr as usize)) {
+        entry.wrappers = entry.wrappers.saturating_sub(1);
+    }
+}
+
+/// An object that was alive at the time of a [`leak_report`](fn.leak_report.html).
+#[derive(Debug, Clone)]
+pub struct TrackedObject {
+    ptr: usize,
+    type_: Type,
+    wrappers: usize,
+    backtrace: Option<String>,
+}
+
+impl TrackedObject {
+    /// Returns the pointer to the object.
+    ///
+    /// The object might already be finalized when this is called, so the pointer must only be
+    /// used for identification.
+    pub fn as_ptr(&self) -> *mut gobject_sys::GObject {
+        self.ptr as *mut _
+    }
+
+    /// Returns the type of the object.
+    pub fn get_type(&self) -> Type {
+        self.type_
+    }
+
+    /// Returns the number of Rust wrappers that owned a reference to the object.
+    ///
+    /// If this is 0 then the object is kept alive by references from outside Rust code.
+    pub fn get_wrapper_count(&self) -> usize {
+        self.wrappers
+    }
+
+    /// Returns the backtrace of the first wrapper construction for the object, if recorded.
+    pub fn get_backtrace(&self) -> Option<&str> {
+        self.backtrace.as_ref().map(String::as_str)
+    }
+}
+
+impl fmt::Display for TrackedObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} ({} wrappers)",
+            self.type_, self.ptr as *const gobject_sys::GObject, self.wrappers
+        )?;
+        if let Some(ref backtrace) = self.backtrace {
+            write!(f, "\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns all objects that were seen by a wrapper and are not finalized yet.
+pub fn leak_report() -> Vec<TrackedObject> {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(ptr, entry)| TrackedObject {
+            ptr: *ptr,
+            type_: entry.type_,
+            wrappers: entry.wrappers,
+            backtrace: entry.backtrace.clone(),
+        })
+        .collect()
+}
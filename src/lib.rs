@@ -68,7 +68,7 @@
 //! checks. Such wrappers are defined via the
 //! [`glib_wrapper!`](macro.glib_wrapper!.html) macro, which uses abstractions
 //! defined in the [`wrapper`](wrapper/index.html), [`boxed`](boxed/index.html),
-//! [`shared`](shared/index.html) and [`object`](object/index.html) modules.
+//! [`boxed_inline`](boxed_inline/index.html), [`shared`](shared/index.html) and [`object`](object/index.html) modules.
 //!
 //! The [`translate`](translate/index.html) module defines and partly implements
 //! conversions between high level Rust types (including the aforementioned
@@ -90,34 +90,45 @@ pub extern crate glib_sys;
 pub extern crate gobject_sys;
 
 extern crate glib_macros;
-pub use glib_macros::{gflags, GBoxed, GEnum};
+pub use glib_macros::{gflags, Downgrade, ErrorDomain, GBoxed, GEnum, OptionEntries};
 
 extern crate futures_channel;
 extern crate futures_core;
 extern crate futures_executor;
+extern crate futures_sink;
 extern crate futures_task;
 extern crate futures_util;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "leak_tracker")]
+extern crate backtrace;
+
 pub use byte_array::ByteArray;
 pub use bytes::Bytes;
 pub use closure::Closure;
+pub use convert::{
+    convert, filename_from_utf8, filename_to_utf8, locale_from_utf8, locale_to_utf8,
+};
 pub use error::{BoolError, Error};
 pub use file_error::FileError;
 pub use object::{
-    Cast, InitiallyUnowned, InitiallyUnownedClass, IsA, IsClassFor, Object, ObjectClass, ObjectExt,
-    ObjectType, SendWeakRef, WeakRef,
+    Cast, InitiallyUnowned, InitiallyUnownedClass, InterfaceRef, IsA, IsClassFor, NotifyFuture,
+    Object, ObjectClass, ObjectExt, ObjectType, SendWeakRef, SignalStream, WeakPtr, WeakRef,
 };
 pub use signal::{
     signal_handler_block, signal_handler_disconnect, signal_handler_unblock,
-    signal_stop_emission_by_name, SignalHandlerId,
+    signal_stop_emission_by_name, SignalHandlerId, SignalId,
 };
 use std::ffi::CStr;
-pub use string::String;
+#[allow(deprecated)]
+pub use string_builder::{String, StringBuilder};
 
 pub use enums::{EnumClass, EnumValue, FlagsBuilder, FlagsClass, FlagsValue, UserDirectory};
 pub use types::{StaticType, Type};
 pub use value::{SendValue, ToSendValue, ToValue, TypedValue, Value};
-pub use variant::{FromVariant, StaticVariantType, ToVariant, Variant};
+pub use variant::{FromVariant, StaticVariantType, ToVariant, TypedVariant, Variant};
 pub use variant_dict::VariantDict;
 pub use variant_iter::VariantIter;
 pub use variant_type::{VariantTy, VariantType};
@@ -129,6 +140,8 @@ pub mod wrapper;
 #[macro_use]
 pub mod boxed;
 #[macro_use]
+pub mod boxed_inline;
+#[macro_use]
 pub mod shared;
 #[macro_use]
 pub mod error;
@@ -150,27 +163,58 @@ mod gobject;
 mod byte_array;
 mod bytes;
 pub mod char;
-mod string;
+mod string_builder;
 pub use char::*;
 mod checksum;
 pub mod closure;
+mod convert;
 mod enums;
 mod file_error;
 mod functions;
 pub use functions::*;
+#[cfg(any(unix, feature = "dox"))]
+mod spawn;
+#[cfg(any(unix, feature = "dox"))]
+pub use spawn::{spawn_command_line_sync, Child, SpawnBuilder, SpawnOutput};
+mod io_channel;
+pub use io_channel::{io_add_watch_local, IOChannel};
 mod key_file;
+mod markup;
+pub use markup::{MarkupParseContext, MarkupParseContextRef, MarkupParser};
+mod option_context;
+pub use option_context::{OptionContext, OptionEntries, OptionEntry, OptionType, OptionValue};
 pub mod prelude;
 pub mod signal;
 pub mod source;
 pub use source::*;
 #[macro_use]
 pub mod translate;
+mod array;
+mod base64;
 mod gstring;
+mod list;
+mod strv;
+mod tree;
+pub use array::Array;
+pub use base64::{Base64Decoder, Base64Encoder};
 pub use gstring::GString;
+pub use list::{List, ListIntoIter, ListIter, SList, SListIntoIter, SListIter};
+pub use strv::{StrV, StrVIter};
+pub use tree::Tree;
 pub mod types;
 mod utils;
 pub use utils::*;
+pub mod gettext;
+#[cfg(any(all(target_os = "linux", target_env = "gnu"), feature = "dox"))]
+pub use gettext::{bind_textdomain_codeset, bindtextdomain, textdomain};
+mod time;
+pub use time::{MonotonicTime, RealTime};
+mod unicode;
+pub use unicode::*;
 mod main_context;
+pub use main_context::{MainContextAcquireGuard, ThreadDefaultGuard};
+mod main_loop;
+pub use main_loop::MainLoopQuitGuard;
 mod main_context_channel;
 pub mod value;
 pub mod variant;
@@ -180,16 +224,24 @@ mod variant_type;
 pub use main_context_channel::{Receiver, Sender, SyncSender};
 mod date;
 pub use date::Date;
+mod date_time;
 mod value_array;
 pub use value_array::ValueArray;
 mod param_spec;
 pub use param_spec::*;
 mod quark;
 pub use quark::Quark;
+#[doc(hidden)]
+pub use quark::StaticQuark;
 #[macro_use]
 mod log;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 pub use log::log_set_handler;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub use log::{
+    log_set_writer_func, log_structured_array, log_writer_default, log_writer_format_fields,
+    log_writer_journald, LogField, LogWriterOutput,
+};
 
 // #[cfg(any(feature = "v2_50", feature = "dox"))]
 // pub use log::log_variant;
@@ -208,18 +260,25 @@ mod bridged_logging;
 #[cfg(any(feature = "log", feature = "dox"))]
 pub use bridged_logging::{rust_log_handler, GlibLogger, GlibLoggerDomain, GlibLoggerFormat};
 
+#[cfg(feature = "leak_tracker")]
+pub mod leak_tracker;
+#[cfg(feature = "leak_tracker")]
+pub use leak_tracker::leak_report;
+
 pub mod send_unique;
 pub use send_unique::{SendUnique, SendUniqueCell};
+pub mod test_utils;
 
 #[macro_use]
 pub mod subclass;
 
 mod main_context_futures;
+pub use main_context_futures::{JoinError, JoinHandle};
 mod source_futures;
 pub use source_futures::*;
 
 mod thread_pool;
-pub use thread_pool::ThreadPool;
+pub use thread_pool::{spawn_blocking, ThreadPool};
 
 /// This is the log domain used by the [`clone!`][crate::clone] macro. If you want to use a custom
 /// logger (it prints to stdout by default), you can set your own logger using the corresponding
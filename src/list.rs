@@ -0,0 +1,491 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Owned `GList` and `GSList` collections.
+//!
+//! The lists own their elements, which are stored in their C representation as returned by
+//! `to_glib_full()`. Accessing an element therefore returns a new reference (or copy) of it.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::List;
+//!
+//! let mut list = List::new();
+//! list.push_back(String::from("b"));
+//! list.push_front(String::from("a"));
+//! assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b"]);
+//! ```
+
+use glib_sys;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr;
+use translate::*;
+
+unsafe fn item_to_full<T>(item: &T) -> glib_sys::gpointer
+where
+    T: GlibPtrDefault + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+{
+    let ptr: <T as GlibPtrDefault>::GlibType = item.to_glib_full();
+    Ptr::to(ptr)
+}
+
+unsafe fn item_from_none<T>(data: glib_sys::gpointer) -> T
+where
+    T: GlibPtrDefault + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>,
+{
+    from_glib_none::<<T as GlibPtrDefault>::GlibType, T>(Ptr::from(data))
+}
+
+unsafe fn item_from_full<T>(data: glib_sys::gpointer) -> T
+where
+    T: GlibPtrDefault + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
+{
+    from_glib_full::<<T as GlibPtrDefault>::GlibType, T>(Ptr::from(data))
+}
+
+macro_rules! glib_list {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $iter:ident, $into_iter:ident, $ffi_name:ty,
+        prepend => $prepend:path,
+        append => $append:path,
+        concat => $concat:path,
+        reverse => $reverse:path,
+        length => $length:path,
+        last => $last:path,
+        delete_link => $delete_link:path,
+        free => $free:path,
+    ) => {
+        $(#[$attr])*
+        pub struct $name<T>
+        where
+            T: GlibPtrDefault + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
+        {
+            ptr: *mut $ffi_name,
+            phantom: PhantomData<T>,
+        }
+
+        impl<T> $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            /// Creates a new empty list.
+            pub fn new() -> Self {
+                $name {
+                    ptr: ptr::null_mut(),
+                    phantom: PhantomData,
+                }
+            }
+
+            /// Returns `true` if the list contains no elements.
+            pub fn is_empty(&self) -> bool {
+                self.ptr.is_null()
+            }
+
+            /// Returns the number of elements of the list.
+            ///
+            /// This walks the whole list.
+            pub fn len(&self) -> usize {
+                unsafe { $length(self.ptr) as usize }
+            }
+
+            /// Adds `item` to the front of the list.
+            pub fn push_front(&mut self, item: T) {
+                unsafe {
+                    self.ptr = $prepend(self.ptr, item_to_full(&item));
+                }
+            }
+
+            /// Adds `item` to the back of the list.
+            ///
+            /// This walks the whole list.
+            pub fn push_back(&mut self, item: T) {
+                unsafe {
+                    self.ptr = $append(self.ptr, item_to_full(&item));
+                }
+            }
+
+            /// Removes the first element of the list and returns it.
+            pub fn pop_front(&mut self) -> Option<T> {
+                unsafe { self.remove_link(self.ptr) }
+            }
+
+            /// Removes the last element of the list and returns it.
+            ///
+            /// This walks the whole list.
+            pub fn pop_back(&mut self) -> Option<T> {
+                unsafe { self.remove_link($last(self.ptr)) }
+            }
+
+            unsafe fn remove_link(&mut self, link: *mut $ffi_name) -> Option<T> {
+                if link.is_null() {
+                    return None;
+                }
+
+                let item = item_from_full((*link).data);
+                self.ptr = $delete_link(self.ptr, link);
+                Some(item)
+            }
+
+            /// Moves all elements of `other` to the back of the list, leaving `other` empty.
+            pub fn append(&mut self, other: &mut Self) {
+                unsafe {
+                    self.ptr = $concat(self.ptr, other.ptr);
+                }
+                other.ptr = ptr::null_mut();
+            }
+
+            /// Reverses the order of the elements of the list.
+            pub fn reverse(&mut self) {
+                unsafe {
+                    self.ptr = $reverse(self.ptr);
+                }
+            }
+
+            /// Returns an iterator over new references to the elements of the list.
+            pub fn iter(&self) -> $iter<T> {
+                $iter {
+                    ptr: self.ptr,
+                    phantom: PhantomData,
+                }
+            }
+
+            /// Returns the underlying list.
+            pub fn as_ptr(&self) -> *mut $ffi_name {
+                self.ptr
+            }
+        }
+
+        impl<T> Drop for $name<T>
+        where
+            T: GlibPtrDefault + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
+        {
+            fn drop(&mut self) {
+                unsafe {
+                    let mut link = self.ptr;
+                    while !link.is_null() {
+                        let _: T = item_from_full((*link).data);
+                        link = (*link).next;
+                    }
+                    $free(self.ptr);
+                }
+            }
+        }
+
+        impl<T> Default for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> Clone for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn clone(&self) -> Self {
+                self.iter().collect()
+            }
+        }
+
+        impl<T> fmt::Debug for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>
+                + fmt::Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<T> FromIterator<T> for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                let mut list = Self::new();
+                for item in iter {
+                    list.push_front(item);
+                }
+                list.reverse();
+                list
+            }
+        }
+
+        impl<T> Extend<T> for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                let mut other: Self = iter.into_iter().collect();
+                self.append(&mut other);
+            }
+        }
+
+        impl<T> From<Vec<T>> for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn from(v: Vec<T>) -> Self {
+                v.into_iter().collect()
+            }
+        }
+
+        impl<T> From<$name<T>> for Vec<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            fn from(list: $name<T>) -> Self {
+                list.into_iter().collect()
+            }
+        }
+
+        /// Iterator over new references to the elements of a list.
+        pub struct $iter<'a, T: 'a> {
+            ptr: *mut $ffi_name,
+            phantom: PhantomData<&'a T>,
+        }
+
+        impl<'a, T> Iterator for $iter<'a, T>
+        where
+            T: GlibPtrDefault + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>,
+        {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                if self.ptr.is_null() {
+                    return None;
+                }
+
+                unsafe {
+                    let item = item_from_none((*self.ptr).data);
+                    self.ptr = (*self.ptr).next;
+                    Some(item)
+                }
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'b> ToGlibPtr<'b, <T as GlibPtrDefault>::GlibType>,
+        {
+            type Item = T;
+            type IntoIter = $iter<'a, T>;
+
+            fn into_iter(self) -> $iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        /// Iterator that moves the elements out of a list.
+        pub struct $into_iter<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            list: $name<T>,
+        }
+
+        impl<T> Iterator for $into_iter<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                self.list.pop_front()
+            }
+        }
+
+        impl<T> IntoIterator for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            type Item = T;
+            type IntoIter = $into_iter<T>;
+
+            fn into_iter(self) -> $into_iter<T> {
+                $into_iter { list: self }
+            }
+        }
+
+        #[doc(hidden)]
+        impl<'a, T> ToGlibPtr<'a, *mut $ffi_name> for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'b> ToGlibPtr<'b, <T as GlibPtrDefault>::GlibType>,
+        {
+            type Storage = &'a Self;
+
+            #[inline]
+            fn to_glib_none(&'a self) -> Stash<'a, *mut $ffi_name, Self> {
+                Stash(self.ptr, self)
+            }
+
+            #[inline]
+            fn to_glib_full(&self) -> *mut $ffi_name {
+                let mut list = self.clone();
+                let ptr = list.ptr;
+                list.ptr = ptr::null_mut();
+                ptr
+            }
+        }
+
+        #[doc(hidden)]
+        impl<T> FromGlibPtrFull<*mut $ffi_name> for $name<T>
+        where
+            T: GlibPtrDefault + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
+        {
+            #[inline]
+            unsafe fn from_glib_full(ptr: *mut $ffi_name) -> Self {
+                $name {
+                    ptr,
+                    phantom: PhantomData,
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        impl<T> FromGlibPtrNone<*mut $ffi_name> for $name<T>
+        where
+            T: GlibPtrDefault
+                + FromGlibPtrNone<<T as GlibPtrDefault>::GlibType>
+                + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>
+                + for<'a> ToGlibPtr<'a, <T as GlibPtrDefault>::GlibType>,
+        {
+            #[inline]
+            unsafe fn from_glib_none(ptr: *mut $ffi_name) -> Self {
+                $iter::<T> {
+                    ptr,
+                    phantom: PhantomData,
+                }
+                .collect()
+            }
+        }
+    };
+}
+
+glib_list!(
+    /// An owned doubly-linked `GList`.
+    List, ListIter, ListIntoIter, glib_sys::GList,
+    prepend => glib_sys::g_list_prepend,
+    append => glib_sys::g_list_append,
+    concat => glib_sys::g_list_concat,
+    reverse => glib_sys::g_list_reverse,
+    length => glib_sys::g_list_length,
+    last => glib_sys::g_list_last,
+    delete_link => glib_sys::g_list_delete_link,
+    free => glib_sys::g_list_free,
+);
+
+glib_list!(
+    /// An owned singly-linked `GSList`.
+    SList, SListIter, SListIntoIter, glib_sys::GSList,
+    prepend => glib_sys::g_slist_prepend,
+    append => glib_sys::g_slist_append,
+    concat => glib_sys::g_slist_concat,
+    reverse => glib_sys::g_slist_reverse,
+    length => glib_sys::g_slist_length,
+    last => glib_sys::g_slist_last,
+    delete_link => glib_sys::g_slist_delete_link,
+    free => glib_sys::g_slist_free,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object::ObjectExt;
+    use Object;
+    use StaticType;
+
+    #[test]
+    fn test_list() {
+        let mut list: List<String> = vec!["b".to_string(), "c".to_string()].into();
+        list.push_front("a".to_string());
+        list.push_back("d".to_string());
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+
+        assert_eq!(list.pop_front().as_ref().map(String::as_str), Some("a"));
+        assert_eq!(list.pop_back().as_ref().map(String::as_str), Some("d"));
+
+        let mut other: List<String> = vec!["e".to_string()].into();
+        list.append(&mut other);
+        assert!(other.is_empty());
+        list.reverse();
+        assert_eq!(Vec::from(list), ["e", "c", "b"]);
+    }
+
+    #[test]
+    fn test_list_iterators() {
+        let mut list: SList<String> = vec!["a", "b"].into_iter().map(String::from).collect();
+        list.extend(vec!["c".to_string(), "d".to_string()]);
+        let mut items = Vec::new();
+        for item in &list {
+            items.push(item);
+        }
+        assert_eq!(items, ["a", "b", "c", "d"]);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_slist_objects() {
+        let obj = Object::new(Object::static_type(), &[]).unwrap();
+        let mut list = SList::new();
+        list.push_back(obj.clone());
+        list.push_back(obj.clone());
+        assert_eq!(obj.ref_count(), 3);
+
+        let copy: SList<Object> = unsafe { from_glib_full(list.to_glib_full()) };
+        assert_eq!(obj.ref_count(), 5);
+        drop(copy);
+
+        assert_eq!(list.pop_back(), Some(obj.clone()));
+        assert_eq!(obj.ref_count(), 2);
+        drop(list);
+        assert_eq!(obj.ref_count(), 1);
+    }
+}
@@ -3,13 +3,26 @@
 // Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
 
 use glib_sys;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use libc::c_char;
 use once_cell::sync::Lazy;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 use std::boxed::Box as Box_;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::ffi::CStr;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::fmt;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::marker::PhantomData;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::slice;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::str;
 use std::sync::{Arc, Mutex};
 use translate::*;
 use GString;
 
+/// The ID of a log handler installed with [`log_set_handler`](fn.log_set_handler.html).
 #[derive(Debug)]
 pub struct LogHandlerId(u32);
 
@@ -39,6 +52,21 @@ pub enum LogLevel {
     Debug,
 }
 
+impl LogLevel {
+    /// Returns the syslog priority corresponding to the log level, as used for the `PRIORITY`
+    /// field of structured log messages.
+    pub fn priority(&self) -> &'static str {
+        match *self {
+            LogLevel::Error => "3",
+            LogLevel::Critical => "4",
+            LogLevel::Warning => "4",
+            LogLevel::Message => "5",
+            LogLevel::Info => "6",
+            LogLevel::Debug => "7",
+        }
+    }
+}
+
 #[doc(hidden)]
 impl ToGlib for LogLevel {
     type GlibType = u32;
@@ -113,6 +141,32 @@ fn to_log_flags(fatal: bool, recursion: bool) -> u32 {
         }
 }
 
+/// Sets a handler for the messages of `log_domain` with a level in `log_levels`.
+///
+/// `None` sets the handler for messages logged without a log domain. Handlers are called
+/// instead of the default handler, so a handler that does nothing silences the domain.
+///
+/// If `fatal` is `true`, the handler is also called for fatal messages, and if `recursion`
+/// is `true` for messages that are logged while a log handler is running.
+///
+/// The handler can be removed with [`log_remove_handler`](fn.log_remove_handler.html).
+///
+/// Example:
+///
+/// ```no_run
+/// use glib::{log_remove_handler, log_set_handler, LogLevels};
+///
+/// // Silence the debug output of a noisy library
+/// let id = log_set_handler(
+///     Some("Noisy"),
+///     LogLevels::LEVEL_DEBUG | LogLevels::LEVEL_INFO,
+///     false,
+///     false,
+///     |_domain, _level, _message| {},
+/// );
+/// // ...
+/// log_remove_handler(Some("Noisy"), id);
+/// ```
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 pub fn log_set_handler<P: Fn(&str, LogLevel, &str) + Send + Sync + 'static>(
     log_domain: Option<&str>,
@@ -152,16 +206,27 @@ pub fn log_set_handler<P: Fn(&str, LogLevel, &str) + Send + Sync + 'static>(
     }
 }
 
+/// Removes a handler that was set with [`log_set_handler`](fn.log_set_handler.html) for
+/// `log_domain`.
 pub fn log_remove_handler(log_domain: Option<&str>, handler_id: LogHandlerId) {
     unsafe {
         glib_sys::g_log_remove_handler(log_domain.to_glib_none().0, handler_id.to_glib());
     }
 }
 
+/// Sets the levels of messages that abort the program, in addition to `LogLevels::LEVEL_ERROR`
+/// which is always fatal.
+///
+/// This is useful in tests, e.g. to make warnings and criticals fatal. Returns the previous
+/// fatal levels.
 pub fn log_set_always_fatal(fatal_levels: LogLevels) -> LogLevels {
     unsafe { from_glib(glib_sys::g_log_set_always_fatal(fatal_levels.to_glib())) }
 }
 
+/// Sets the levels of messages of `log_domain` that abort the program, in addition to
+/// `LogLevels::LEVEL_ERROR` which is always fatal.
+///
+/// Returns the previous fatal levels of the domain.
 pub fn log_set_fatal_mask(log_domain: &str, fatal_levels: LogLevels) -> LogLevels {
     unsafe {
         from_glib(glib_sys::g_log_set_fatal_mask(
@@ -171,6 +236,238 @@ pub fn log_set_fatal_mask(log_domain: &str, fatal_levels: LogLevels) -> LogLevel
     }
 }
 
+/// A key-value pair of a structured log message.
+///
+/// The value is usually a string but can contain arbitrary binary data.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[repr(transparent)]
+pub struct LogField<'a>(glib_sys::GLogField, PhantomData<&'a ()>);
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+impl<'a> LogField<'a> {
+    /// Creates a field for `key` with the value `value`.
+    ///
+    /// Keys should be uppercase and consist of letters, numbers and underscores only, as
+    /// required by journald.
+    pub fn new(key: &'a CStr, value: &'a [u8]) -> Self {
+        LogField(
+            glib_sys::GLogField {
+                key: key.as_ptr(),
+                value: value.as_ptr() as glib_sys::gconstpointer,
+                length: value.len().into_glib(),
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the key of the field.
+    pub fn key(&self) -> &str {
+        unsafe { CStr::from_ptr(self.0.key).to_str().unwrap_or("") }
+    }
+
+    /// Returns the value of the field.
+    pub fn value_bytes(&self) -> &[u8] {
+        unsafe {
+            if self.0.value.is_null() {
+                &[]
+            } else if self.0.length < 0 {
+                // Negative lengths denote nul-terminated strings
+                CStr::from_ptr(self.0.value as *const c_char).to_bytes()
+            } else {
+                slice::from_raw_parts(self.0.value as *const u8, self.0.length as usize)
+            }
+        }
+    }
+
+    /// Returns the value of the field if it is valid UTF-8.
+    pub fn value_str(&self) -> Option<&str> {
+        str::from_utf8(self.value_bytes()).ok()
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+impl<'a> fmt::Debug for LogField<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogField")
+            .field("key", &self.key())
+            .field("value", &String::from_utf8_lossy(self.value_bytes()))
+            .finish()
+    }
+}
+
+/// Logs a structured message consisting of `fields`.
+///
+/// The [`log_structured!`](macro.log_structured.html) macro is usually more convenient.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_structured_array(log_level: LogLevel, fields: &[LogField]) {
+    unsafe {
+        glib_sys::g_log_structured_array(
+            log_level.to_glib(),
+            fields.as_ptr() as *const glib_sys::GLogField,
+            fields.len(),
+        );
+    }
+}
+
+/// Return value of log writer functions.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogWriterOutput {
+    /// The log message was handled by the writer.
+    Handled,
+    /// The log message was not handled and may be passed on to a fallback writer.
+    Unhandled,
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+impl ToGlib for LogWriterOutput {
+    type GlibType = glib_sys::GLogWriterOutput;
+
+    fn to_glib(&self) -> glib_sys::GLogWriterOutput {
+        match *self {
+            LogWriterOutput::Handled => glib_sys::G_LOG_WRITER_HANDLED,
+            LogWriterOutput::Unhandled => glib_sys::G_LOG_WRITER_UNHANDLED,
+        }
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+impl FromGlib<glib_sys::GLogWriterOutput> for LogWriterOutput {
+    fn from_glib(value: glib_sys::GLogWriterOutput) -> LogWriterOutput {
+        if value == glib_sys::G_LOG_WRITER_HANDLED {
+            LogWriterOutput::Handled
+        } else {
+            LogWriterOutput::Unhandled
+        }
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+unsafe fn log_fields_from_glib<'a>(
+    fields: *const glib_sys::GLogField,
+    n_fields: usize,
+) -> &'a [LogField<'a>] {
+    if fields.is_null() || n_fields == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(fields as *const LogField, n_fields)
+    }
+}
+
+/// Sets the function that all log messages are passed to, including the ones logged with
+/// [`g_log!`](macro.g_log.html) and the internal messages of GLib and GTK.
+///
+/// The writer receives the log level, the log domain and message (taken from the `GLIB_DOMAIN`
+/// and `MESSAGE` fields) and all fields of the message. It can pass messages on to the default
+/// writers, e.g. [`log_writer_default`](fn.log_writer_default.html).
+///
+/// The writer function can only be set once, and must be set before any message is logged.
+/// Otherwise GLib aborts.
+///
+/// Example:
+///
+/// ```no_run
+/// use glib::{log_set_writer_func, log_writer_default, LogWriterOutput};
+///
+/// log_set_writer_func(|level, domain, message, fields| {
+///     if domain == Some("noisy") {
+///         return LogWriterOutput::Handled;
+///     }
+///     eprintln!("[{:?}] {}", level, message);
+///     log_writer_default(level, fields)
+/// });
+/// ```
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_set_writer_func<
+    P: Fn(LogLevel, Option<&str>, &str, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+>(
+    writer_func: P,
+) {
+    unsafe extern "C" fn writer_trampoline<
+        P: Fn(LogLevel, Option<&str>, &str, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+    >(
+        log_level: glib_sys::GLogLevelFlags,
+        fields: *const glib_sys::GLogField,
+        n_fields: libc::size_t,
+        user_data: glib_sys::gpointer,
+    ) -> glib_sys::GLogWriterOutput {
+        let fields = log_fields_from_glib(fields, n_fields);
+        let mut domain = None;
+        let mut message = "";
+        for field in fields {
+            match field.key() {
+                "GLIB_DOMAIN" => domain = field.value_str(),
+                "MESSAGE" => message = field.value_str().unwrap_or(""),
+                _ => (),
+            }
+        }
+        let callback: &P = &*(user_data as *mut _);
+        (*callback)(from_glib(log_level), domain, message, fields).to_glib()
+    }
+    unsafe extern "C" fn destroy_func<
+        P: Fn(LogLevel, Option<&str>, &str, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+    >(
+        data: glib_sys::gpointer,
+    ) {
+        let _callback: Box_<P> = Box_::from_raw(data as *mut _);
+    }
+    let writer_func: Box_<P> = Box_::new(writer_func);
+    unsafe {
+        glib_sys::g_log_set_writer_func(
+            Some(writer_trampoline::<P>),
+            Box_::into_raw(writer_func) as *mut _,
+            Some(destroy_func::<P>),
+        );
+    }
+}
+
+/// Formats `fields` and writes them to stdout or stderr, like GLib does by default.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_writer_default(log_level: LogLevel, fields: &[LogField]) -> LogWriterOutput {
+    unsafe {
+        from_glib(glib_sys::g_log_writer_default(
+            log_level.to_glib(),
+            fields.as_ptr() as *const glib_sys::GLogField,
+            fields.len(),
+            ::std::ptr::null_mut(),
+        ))
+    }
+}
+
+/// Sends `fields` to the systemd journal.
+///
+/// Returns `LogWriterOutput::Unhandled` if the journal is not available.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_writer_journald(log_level: LogLevel, fields: &[LogField]) -> LogWriterOutput {
+    unsafe {
+        from_glib(glib_sys::g_log_writer_journald(
+            log_level.to_glib(),
+            fields.as_ptr() as *const glib_sys::GLogField,
+            fields.len(),
+            ::std::ptr::null_mut(),
+        ))
+    }
+}
+
+/// Formats `fields` as a human readable log line, optionally with ANSI color escapes.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub fn log_writer_format_fields(
+    log_level: LogLevel,
+    fields: &[LogField],
+    use_color: bool,
+) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_log_writer_format_fields(
+            log_level.to_glib(),
+            fields.as_ptr() as *const glib_sys::GLogField,
+            fields.len(),
+            use_color.to_glib(),
+        ))
+    }
+}
+
 // #[cfg(any(feature = "v2_50", feature = "dox"))]
 // pub fn log_variant(log_domain: Option<&str>, log_level: LogLevel, fields: &Variant) {
 //     unsafe {
@@ -186,13 +483,20 @@ type PrintCallback = dyn Fn(&str) + Send + Sync + 'static;
 
 static PRINT_HANDLER: Lazy<Mutex<Option<Arc<PrintCallback>>>> = Lazy::new(|| Mutex::new(None));
 
+/// Sets the function that is called with the output of `g_print()`, e.g. from the
+/// [`g_print!`](macro.g_print.html) macro or from C libraries.
+///
+/// This allows capturing the output instead of having it written to stdout.
+///
 /// To set back the default print handler, use the [`unset_print_handler`] function.
 pub fn set_print_handler<P: Fn(&str) + Send + Sync + 'static>(func: P) {
     unsafe extern "C" fn func_func(string: *const libc::c_char) {
-        if let Some(callback) = match *PRINT_HANDLER.lock().expect("Failed to lock PRINT_HANDLER") {
-            Some(ref handler) => Some(Arc::clone(handler)),
-            None => None,
-        } {
+        // Don't hold the lock while calling the handler so that it can replace itself
+        let callback = PRINT_HANDLER
+            .lock()
+            .expect("Failed to lock PRINT_HANDLER")
+            .clone();
+        if let Some(callback) = callback {
             let string: Borrowed<GString> = from_glib_borrow(string);
             (*callback)(string.as_str())
         }
@@ -203,7 +507,9 @@ pub fn set_print_handler<P: Fn(&str) + Send + Sync + 'static>(func: P) {
     unsafe { glib_sys::g_set_print_handler(Some(func_func as _)) };
 }
 
-/// To set the default print handler, use the [`set_print_handler`] function.
+/// Restores the default print handler, which writes to stdout.
+///
+/// To set a custom print handler, use the [`set_print_handler`] function.
 pub fn unset_print_handler() {
     *PRINT_HANDLER
         .lock()
@@ -213,16 +519,20 @@ pub fn unset_print_handler() {
 
 static PRINTERR_HANDLER: Lazy<Mutex<Option<Arc<PrintCallback>>>> = Lazy::new(|| Mutex::new(None));
 
-/// To set back the default print handler, use the [`unset_printerr_handler`] function.
+/// Sets the function that is called with the output of `g_printerr()`, e.g. from the
+/// [`g_printerr!`](macro.g_printerr.html) macro or from C libraries.
+///
+/// This allows capturing the output instead of having it written to stderr.
+///
+/// To set back the default printerr handler, use the [`unset_printerr_handler`] function.
 pub fn set_printerr_handler<P: Fn(&str) + Send + Sync + 'static>(func: P) {
     unsafe extern "C" fn func_func(string: *const libc::c_char) {
-        if let Some(callback) = match *PRINTERR_HANDLER
+        // Don't hold the lock while calling the handler so that it can replace itself
+        let callback = PRINTERR_HANDLER
             .lock()
             .expect("Failed to lock PRINTERR_HANDLER")
-        {
-            Some(ref handler) => Some(Arc::clone(handler)),
-            None => None,
-        } {
+            .clone();
+        if let Some(callback) = callback {
             let string: Borrowed<GString> = from_glib_borrow(string);
             (*callback)(string.as_str())
         }
@@ -233,7 +543,9 @@ pub fn set_printerr_handler<P: Fn(&str) + Send + Sync + 'static>(func: P) {
     unsafe { glib_sys::g_set_printerr_handler(Some(func_func as _)) };
 }
 
-/// To set the default print handler, use the [`set_printerr_handler`] function.
+/// Restores the default printerr handler, which writes to stderr.
+///
+/// To set a custom printerr handler, use the [`set_printerr_handler`] function.
 pub fn unset_printerr_handler() {
     *PRINTERR_HANDLER
         .lock()
@@ -253,13 +565,12 @@ pub fn log_set_default_handler<P: Fn(&str, LogLevel, &str) + Send + Sync + 'stat
         message: *const libc::c_char,
         _user_data: glib_sys::gpointer,
     ) {
-        if let Some(callback) = match *DEFAULT_HANDLER
+        // Don't hold the lock while calling the handler so that it can replace itself
+        let callback = DEFAULT_HANDLER
             .lock()
             .expect("Failed to lock DEFAULT_HANDLER")
-        {
-            Some(ref handler) => Some(Arc::clone(handler)),
-            None => None,
-        } {
+            .clone();
+        if let Some(callback) = callback {
             let log_domain: Borrowed<GString> = from_glib_borrow(log_domain);
             let message: Borrowed<GString> = from_glib_borrow(message);
             (*callback)(log_domain.as_str(), from_glib(log_levels), message.as_str());
@@ -662,36 +973,191 @@ macro_rules! g_printerr {
     }};
 }
 
-// /// Macro used to log using GLib logging system. It uses [g_log_structured][gls].
-// ///
-// /// [gls]: https://developer.gnome.org/glib/stable/glib-Message-Logging.html#g-log-structured)
-// ///
-// /// Example:
-// ///
-// /// ```no_run
-// /// use glib::{LogLevel, g_log_structured};
-// ///
-// /// g_log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!"});
-// /// g_log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!", "random" => "yes"});
-// /// ```
-// #[cfg(any(feature = "v2_50", feature = "dox"))]
-// #[macro_export]
-// macro_rules! g_log_structured {
-//     ($log_domain:expr, $log_level:expr, {$($key:expr => $value:expr),+}) => {{
-//         use $crate::translate::{Stash, ToGlib, ToGlibPtr};
-//         use $crate::LogLevel;
-//         use std::ffi::CString;
-
-//         fn check_log_args(_log_domain: &str, _log_level: LogLevel) {}
-//         fn check_key(key: &str) -> Stash<*const i8, str> { key.to_glib_none() }
-
-//         check_log_args(&$log_domain, $log_level);
-//         unsafe {
-//             glib_sys::g_log_structured(
-//                 $log_domain.to_glib_none().0,
-//                 $log_level.to_glib(),
-//                 $(check_key($key).0, check_key(format!("{}", $value).as_str()).0 ),+
-//             )
-//         }
-//     }};
-// }
+/// Macro used to log structured messages using GLib logging system. It uses
+/// [g_log_structured_array][glsa].
+///
+/// [glsa]: https://developer.gnome.org/glib/stable/glib-Message-Logging.html#g-log-structured-array
+///
+/// The keys must be string literals. The values are formatted with their `Display`
+/// implementation. A `GLIB_DOMAIN` field with the log domain and a `PRIORITY` field derived from
+/// the log level are added automatically, and a `MESSAGE` field should always be provided. See
+/// the [systemd journal fields] for the keys in common use.
+///
+/// [systemd journal fields]: https://www.freedesktop.org/software/systemd/man/systemd.journal-fields.html
+///
+/// Example:
+///
+/// ```no_run
+/// use glib::{LogLevel, log_structured};
+///
+/// log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!"});
+/// log_structured!(
+///     "test",
+///     LogLevel::Warning,
+///     {
+///         "MESSAGE" => "tadam!",
+///         "CODE_FILE" => file!(),
+///         "CODE_LINE" => line!(),
+///         "MY_APPLICATION_COUNT" => 42,
+///     }
+/// );
+/// ```
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[macro_export]
+macro_rules! log_structured {
+    ($log_domain:expr, $log_level:expr, {$($key:literal => $value:expr),+ $(,)?}) => {{
+        // the next line is used to enforce the type for the macro checker...
+        let log_domain: &str = $log_domain;
+        let log_level: $crate::LogLevel = $log_level;
+        $crate::log_structured_array(
+            log_level,
+            &[
+                $crate::LogField::new(
+                    ::std::ffi::CStr::from_bytes_with_nul(b"GLIB_DOMAIN\0").unwrap(),
+                    log_domain.as_bytes(),
+                ),
+                $crate::LogField::new(
+                    ::std::ffi::CStr::from_bytes_with_nul(b"PRIORITY\0").unwrap(),
+                    log_level.priority().as_bytes(),
+                ),
+                $(
+                    $crate::LogField::new(
+                        ::std::ffi::CStr::from_bytes_with_nul(concat!($key, "\0").as_bytes())
+                            .unwrap(),
+                        format!("{}", $value).as_bytes(),
+                    ),
+                )+
+            ],
+        );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "v2_46", feature = "dox"))]
+    #[test]
+    fn test_log_handler() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let messages_clone = messages.clone();
+        let id = log_set_handler(
+            Some("glib-rs-test"),
+            LogLevels::LEVEL_WARNING | LogLevels::LEVEL_MESSAGE,
+            false,
+            false,
+            move |domain, level, message| {
+                messages_clone
+                    .lock()
+                    .unwrap()
+                    .push((domain.to_owned(), level, message.to_owned()));
+            },
+        );
+        g_warning!("glib-rs-test", "warning {}", 1);
+        g_message!("glib-rs-test", "message");
+        // Not captured, goes to the default handler
+        g_debug!("glib-rs-test", "debug");
+        log_remove_handler(Some("glib-rs-test"), id);
+        g_message!("glib-rs-test", "after removal");
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            [
+                (
+                    String::from("glib-rs-test"),
+                    LogLevel::Warning,
+                    String::from("warning 1")
+                ),
+                (
+                    String::from("glib-rs-test"),
+                    LogLevel::Message,
+                    String::from("message")
+                ),
+            ]
+        );
+    }
+
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    #[test]
+    fn test_log_writer_format_fields() {
+        let domain = CStr::from_bytes_with_nul(b"GLIB_DOMAIN\0").unwrap();
+        let message = CStr::from_bytes_with_nul(b"MESSAGE\0").unwrap();
+        let fields = [
+            LogField::new(domain, b"glib-rs-test"),
+            LogField::new(message, b"formatted message"),
+        ];
+
+        let line = log_writer_format_fields(LogLevel::Warning, &fields, false);
+        assert!(line.starts_with("glib-rs-test-WARNING **: "));
+        assert!(line.ends_with(": formatted message"));
+
+        let line = log_writer_format_fields(LogLevel::Message, &fields, false);
+        assert!(line.starts_with("glib-rs-test-Message: "));
+        assert!(line.ends_with(": formatted message"));
+    }
+
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    #[test]
+    fn test_log_structured() {
+        let fields = Arc::new(Mutex::new(Vec::new()));
+        let fields_clone = fields.clone();
+        // The writer function can only be set once per process, so this is the only test
+        // setting it
+        log_set_writer_func(move |level, domain, _message, log_fields| {
+            if domain != Some("glib-rs-structured-test") {
+                return log_writer_default(level, log_fields);
+            }
+            fields_clone.lock().unwrap().extend(
+                log_fields
+                    .iter()
+                    .map(|field| (field.key().to_owned(), field.value_bytes().to_owned())),
+            );
+            LogWriterOutput::Handled
+        });
+
+        log_structured!(
+            "glib-rs-structured-test",
+            LogLevel::Message,
+            {
+                "MESSAGE" => "structured message",
+                "GLIB_RS_TEST_COUNT" => 42,
+            }
+        );
+
+        let fields = fields.lock().unwrap();
+        let get = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.as_slice())
+        };
+        assert_eq!(get("GLIB_DOMAIN"), Some(&b"glib-rs-structured-test"[..]));
+        assert_eq!(get("PRIORITY"), Some(&b"5"[..]));
+        assert_eq!(get("MESSAGE"), Some(&b"structured message"[..]));
+        assert_eq!(get("GLIB_RS_TEST_COUNT"), Some(&b"42"[..]));
+    }
+
+    #[test]
+    fn test_print_handler() {
+        let output = Arc::new(Mutex::new(String::new()));
+        let output_clone = output.clone();
+        set_print_handler(move |s| output_clone.lock().unwrap().push_str(s));
+        g_print!("{} + {} = {}%", 1, 1, 2);
+        unset_print_handler();
+        assert_eq!(*output.lock().unwrap(), "1 + 1 = 2%");
+    }
+
+    #[test]
+    fn test_printerr_handler() {
+        let output = Arc::new(Mutex::new(String::new()));
+        let output_clone = output.clone();
+        set_printerr_handler(move |s| {
+            output_clone.lock().unwrap().push_str(s);
+            // Replacing the handler from within itself must not deadlock
+            unset_printerr_handler();
+        });
+        g_printerr!("error");
+        g_printerr!("not captured");
+        assert_eq!(*output.lock().unwrap(), "error");
+    }
+}
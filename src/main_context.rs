@@ -4,8 +4,11 @@
 
 use glib_sys::{self, gboolean, gpointer};
 use source::Priority;
+use std::marker::PhantomData;
 use std::mem;
+use std::time::{Duration, Instant};
 use translate::*;
+use Continue;
 use MainContext;
 use Source;
 use SourceId;
@@ -24,6 +27,9 @@ impl MainContext {
         }
     }
 
+    /// Finds the source with the given `source_id` that is attached to this main context.
+    ///
+    /// Returns `None` if no such source exists, e.g. because it was already removed.
     pub fn find_source_by_id(&self, source_id: &SourceId) -> Option<Source> {
         unsafe {
             from_glib_none(glib_sys::g_main_context_find_source_by_id(
@@ -34,6 +40,11 @@ impl MainContext {
     }
 
     /// Invokes `func` on the main context.
+    ///
+    /// If the current thread is the owner of the main context, or the main context is the
+    /// global default main context and can be acquired by the current thread, `func` is called
+    /// immediately before this function returns. Otherwise `func` is scheduled to be called
+    /// from an idle source once the main context is iterated.
     pub fn invoke<F>(&self, func: F)
     where
         F: FnOnce() + Send + 'static,
@@ -42,6 +53,8 @@ impl MainContext {
     }
 
     /// Invokes `func` on the main context with the given priority.
+    ///
+    /// See `invoke()` for details about when `func` is called immediately.
     pub fn invoke_with_priority<F>(&self, priority: Priority, func: F)
     where
         F: FnOnce() + Send + 'static,
@@ -55,6 +68,7 @@ impl MainContext {
     ///
     /// Different to `invoke()`, this does not require `func` to be
     /// `Send` but can only be called from the thread that owns the main context.
+    /// As such `func` is always called immediately before this function returns.
     ///
     /// This function panics if called from a different thread than the one that
     /// owns the main context.
@@ -107,45 +121,129 @@ impl MainContext {
         )
     }
 
+    /// Tries to become the owner of the main context.
+    ///
+    /// On success this returns a guard that releases the ownership again once it's dropped.
+    /// This fails if the main context is currently owned by another thread.
+    pub fn acquire(&self) -> Result<MainContextAcquireGuard, ::BoolError> {
+        unsafe {
+            let ret: bool = from_glib(glib_sys::g_main_context_acquire(self.to_glib_none().0));
+            if ret {
+                Ok(MainContextAcquireGuard(self, PhantomData))
+            } else {
+                Err(glib_bool_error!("Failed to acquire ownership of main context, already acquired by another thread"))
+            }
+        }
+    }
+
+    /// Calls closure with the main context acquired by the current thread.
+    ///
+    /// This fails without calling the closure if the main context is currently owned by
+    /// another thread.
+    pub fn with_acquired<R, F: FnOnce() -> R>(&self, func: F) -> Result<R, ::BoolError> {
+        let _acquire = self.acquire()?;
+        Ok(func())
+    }
+
+    /// Makes the main context the thread default one of the current thread.
+    ///
+    /// The previous thread default main context is restored once the returned guard is
+    /// dropped, including when unwinding from a panic. Guards have to be dropped in the
+    /// reverse order they were created in.
+    pub fn push_thread_default(&self) -> ThreadDefaultGuard {
+        unsafe {
+            glib_sys::g_main_context_push_thread_default(self.to_glib_none().0);
+        }
+        ThreadDefaultGuard(self, PhantomData)
+    }
+
+    /// Iterates the main context until `func` returns `true` or `timeout` has elapsed.
+    ///
+    /// `func` is checked before every iteration. Returns `true` if `func` returned `true` and
+    /// `false` if the timeout was reached first, which makes this suitable for tests that wait
+    /// for an asynchronous operation to finish without hanging forever if it never does.
+    pub fn run_until<F: FnMut() -> bool>(&self, mut func: F, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        // Wakes up the blocking iterations below until the deadline is reached
+        let source = ::timeout_source_new(timeout, None, ::PRIORITY_DEFAULT, || Continue(true));
+        source.attach(Some(self));
+
+        let res = loop {
+            if func() {
+                break true;
+            }
+            if Instant::now() >= deadline {
+                break false;
+            }
+            self.iteration(true);
+        };
+
+        source.destroy();
+
+        res
+    }
+
     /// Calls closure with context configured as the thread default one.
     ///
     /// Thread default context is changed in panic-safe manner by calling
     /// [`push_thread_default`][push_thread_default] before calling closure
-    /// and [`pop_thread_default`][pop_thread_default] afterwards regardless
-    /// of whether closure panicked or not.
+    /// and restoring the previous one afterwards regardless of whether
+    /// closure panicked or not.
     ///
     /// [push_thread_default]: struct.MainContext.html#method.push_thread_default
-    /// [pop_thread_default]: struct.MainContext.html#method.pop_thread_default
     pub fn with_thread_default<R, F: Sized>(&self, func: F) -> R
     where
         F: FnOnce() -> R,
     {
-        let _thread_default = ThreadDefaultContext::new(self);
+        let _thread_default = self.push_thread_default();
         func()
     }
 }
 
-struct ThreadDefaultContext<'a>(&'a MainContext);
+/// Guard returned by [`MainContext::acquire`] that releases the main context again once
+/// it's dropped.
+///
+/// [`MainContext::acquire`]: struct.MainContext.html#method.acquire
+#[must_use = "if unused the main context will be released immediately"]
+#[derive(Debug)]
+pub struct MainContextAcquireGuard<'a>(&'a MainContext, PhantomData<*const ()>);
 
-impl<'a> ThreadDefaultContext<'a> {
-    fn new(ctx: &MainContext) -> ThreadDefaultContext {
-        ctx.push_thread_default();
-        ThreadDefaultContext(ctx)
+impl<'a> Drop for MainContextAcquireGuard<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            glib_sys::g_main_context_release(self.0.to_glib_none().0);
+        }
     }
 }
 
-impl<'a> Drop for ThreadDefaultContext<'a> {
+/// Guard returned by [`MainContext::push_thread_default`] that restores the previous
+/// thread default main context once it's dropped.
+///
+/// [`MainContext::push_thread_default`]: struct.MainContext.html#method.push_thread_default
+#[must_use = "if unused the thread default main context will be restored immediately"]
+#[derive(Debug)]
+pub struct ThreadDefaultGuard<'a>(&'a MainContext, PhantomData<*const ()>);
+
+impl<'a> Drop for ThreadDefaultGuard<'a> {
     fn drop(&mut self) {
-        self.0.pop_thread_default();
+        unsafe {
+            glib_sys::g_main_context_pop_thread_default(self.0.to_glib_none().0);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
     use std::panic;
     use std::ptr;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn test_invoke() {
@@ -160,6 +258,109 @@ mod tests {
         l.run();
     }
 
+    #[test]
+    fn test_iteration() {
+        let c = MainContext::new();
+        let _acquire = c.acquire().unwrap();
+        assert!(c.is_owner());
+        assert!(!c.pending());
+        assert!(!c.iteration(false));
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count_clone = count.clone();
+        let source = ::idle_source_new(None, ::PRIORITY_DEFAULT_IDLE, move || {
+            count_clone.fetch_add(1, Ordering::SeqCst);
+            ::Continue(false)
+        });
+        let source_id = source.attach(Some(&c));
+        assert!(c.find_source_by_id(&source_id).is_some());
+
+        // Drive the main context manually like a custom frame loop would
+        assert!(c.pending());
+        while c.pending() {
+            c.iteration(false);
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(c.find_source_by_id(&source_id).is_none());
+
+        // Waking up from another thread makes a blocking iteration return
+        let c_clone = c.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            c_clone.wakeup();
+        });
+        c.iteration(true);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_invoke_local() {
+        let c = MainContext::new();
+        let _acquire = c.acquire().unwrap();
+
+        let called = Rc::new(Cell::new(false));
+        let called_clone = called.clone();
+        c.invoke_local_with_priority(::PRIORITY_HIGH, move || called_clone.set(true));
+
+        // Owned by the current thread, so the closure was called right away
+        assert!(called.get());
+        assert!(!c.pending());
+    }
+
+    #[test]
+    fn test_source_handle() {
+        let c = MainContext::new();
+
+        // Sources can be configured before they are attached
+        let source = ::idle_source_new(None, ::PRIORITY_DEFAULT_IDLE, || ::Continue(true));
+        source.set_name("unattached-source");
+        source.set_priority(::PRIORITY_HIGH_IDLE);
+        assert_eq!(source.get_name().as_deref(), Some("unattached-source"));
+        assert_eq!(source.get_priority(), ::PRIORITY_HIGH_IDLE.to_glib());
+        let source_id = source.attach(Some(&c));
+
+        let handle = ::SourceHandle::from_id(Some(&c), &source_id).unwrap();
+        assert!(handle.is_attached());
+
+        let handle_clone = handle.clone();
+        thread::spawn(move || {
+            handle_clone.set_priority(::PRIORITY_LOW);
+            handle_clone.set_name("test-source");
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(handle.source().get_priority(), ::PRIORITY_LOW.to_glib());
+        assert_eq!(handle.source().get_name().as_deref(), Some("test-source"));
+
+        handle.clone().remove();
+        assert!(!handle.is_attached());
+        // Removing a second time is fine
+        handle.remove();
+    }
+
+    #[test]
+    fn test_acquire() {
+        let c = MainContext::new();
+
+        {
+            let _acquire = c.acquire().unwrap();
+            assert!(c.is_owner());
+
+            let c_clone = c.clone();
+            thread::spawn(move || {
+                assert!(c_clone.acquire().is_err());
+                assert!(c_clone.with_acquired(|| ()).is_err());
+            })
+            .join()
+            .unwrap();
+        }
+        assert!(!c.is_owner());
+
+        assert!(c.with_acquired(|| c.is_owner()).unwrap());
+        assert!(!c.is_owner());
+    }
+
     fn is_same_context(a: &MainContext, b: &MainContext) -> bool {
         ptr::eq(a.to_glib_none().0, b.to_glib_none().0)
     }
@@ -185,6 +386,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_push_thread_default() {
+        let a = MainContext::new();
+        let b = MainContext::new();
+
+        {
+            let _a = a.push_thread_default();
+            let t = MainContext::get_thread_default().unwrap();
+            assert!(is_same_context(&a, &t));
+
+            {
+                let _b = b.push_thread_default();
+                let t = MainContext::get_thread_default().unwrap();
+                assert!(is_same_context(&b, &t));
+            }
+
+            let t = MainContext::get_thread_default().unwrap();
+            assert!(is_same_context(&a, &t));
+        }
+
+        assert!(MainContext::get_thread_default().is_none());
+    }
+
     #[test]
     fn test_with_thread_default_is_panic_safe() {
         let a = MainContext::new();
@@ -207,4 +431,32 @@ mod tests {
             assert!(is_same_context(&a, &t));
         });
     }
+
+    #[test]
+    fn test_run_until() {
+        let c = MainContext::new();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count_clone = count.clone();
+        ::idle_source_new(None, ::PRIORITY_DEFAULT_IDLE, move || {
+            count_clone.fetch_add(1, Ordering::SeqCst);
+            Continue(true)
+        })
+        .attach(Some(&c));
+
+        assert!(c.run_until(
+            || count.load(Ordering::SeqCst) >= 3,
+            Duration::from_secs(10)
+        ));
+    }
+
+    #[test]
+    fn test_run_until_timeout() {
+        let c = MainContext::new();
+
+        let start = Instant::now();
+        assert!(!c.run_until(|| false, Duration::from_millis(50)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(!c.pending());
+    }
 }
@@ -2,10 +2,15 @@
 // See the COPYRIGHT file at the top-level directory of this distribution.
 // Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
 
+#[cfg(feature = "channel_stream")]
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll, Waker};
+use futures_sink::Sink;
 use glib_sys;
 use std::collections::VecDeque;
 use std::fmt;
 use std::mem;
+use std::pin::Pin;
 use std::ptr;
 use std::sync::mpsc;
 use std::sync::{Arc, Condvar, Mutex};
@@ -30,6 +35,8 @@ struct ChannelInner<T> {
     queue: VecDeque<T>,
     source: ChannelSourceState,
     num_senders: usize,
+    waker: Option<Waker>,
+    sender_wakers: Vec<Waker>,
 }
 
 impl<T> ChannelInner<T> {
@@ -56,6 +63,29 @@ impl<T> ChannelInner<T> {
             }
         }
     }
+
+    fn wake_receiver(&mut self) {
+        // Wake up the GSource if the receiver is attached, or the task
+        // polling the receiver if it is used as a `Stream`
+        self.set_ready_time(0);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn register_sender_waker(&mut self, waker: &Waker) {
+        if !self.sender_wakers.iter().any(|w| w.will_wake(waker)) {
+            self.sender_wakers.push(waker.clone());
+        }
+    }
+
+    fn wake_senders(&mut self) {
+        // Wake up all tasks that are waiting for free space in the queue, or for
+        // the queue to be emptied in case of a bound of 0
+        for waker in self.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
 }
 
 struct ChannelBound {
@@ -78,6 +108,8 @@ impl<T> Channel<T> {
                 queue: VecDeque::new(),
                 source: ChannelSourceState::NotAttached,
                 num_senders: 0,
+                waker: None,
+                sender_wakers: Vec::new(),
             }),
             bound.map(|bound| ChannelBound {
                 bound,
@@ -112,8 +144,8 @@ impl<T> Channel<T> {
         // Store the item on our queue
         inner.queue.push_back(t);
 
-        // and then wake up the GSource
-        inner.set_ready_time(0);
+        // and then wake up the receiver
+        inner.wake_receiver();
 
         // If we have a bound of 0 we need to wait until the receiver actually
         // handled the data
@@ -156,8 +188,8 @@ impl<T> Channel<T> {
         // Store the item on our queue
         inner.queue.push_back(t);
 
-        // and then wake up the GSource
-        inner.set_ready_time(0);
+        // and then wake up the receiver
+        inner.wake_receiver();
 
         // If we have a bound of 0 we need to wait until the receiver actually
         // handled the data
@@ -179,6 +211,59 @@ impl<T> Channel<T> {
         Ok(())
     }
 
+    fn poll_ready(&self, ctx: &mut Context) -> Poll<()> {
+        let mut inner = (self.0).0.lock().unwrap();
+
+        // Unbounded channels are always ready, and a disconnected receiver is reported when
+        // actually sending the item
+        let bound = match (self.0).1 {
+            Some(ChannelBound { bound, .. }) => bound,
+            None => return Poll::Ready(()),
+        };
+
+        // Same check as in send(), including the special case of a 0 bound
+        if inner.queue.len() >= bound && !inner.queue.is_empty() && !inner.receiver_disconnected() {
+            inner.register_sender_waker(ctx.waker());
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    fn start_send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
+        let mut inner = (self.0).0.lock().unwrap();
+
+        // Error out directly if the receiver is disconnected
+        if inner.receiver_disconnected() {
+            return Err(mpsc::SendError(t));
+        }
+
+        // Store the item on our queue without waiting for free space, which
+        // was already checked by poll_ready(), and then wake up the receiver
+        inner.queue.push_back(t);
+        inner.wake_receiver();
+
+        Ok(())
+    }
+
+    fn poll_flush(&self, ctx: &mut Context) -> Poll<()> {
+        let mut inner = (self.0).0.lock().unwrap();
+
+        // Only with a bound of 0 we have to wait until the receiver actually
+        // handled the data, like in send()
+        match (self.0).1 {
+            Some(ChannelBound { bound: 0, .. }) => (),
+            _ => return Poll::Ready(()),
+        }
+
+        if !inner.queue.is_empty() && !inner.receiver_disconnected() {
+            inner.register_sender_waker(ctx.waker());
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
     fn try_recv(&self) -> Result<T, mpsc::TryRecvError> {
         let mut inner = (self.0).0.lock().unwrap();
 
@@ -187,6 +272,7 @@ impl<T> Channel<T> {
             // Wake up a sender that is currently waiting, if any
             if let Some(ChannelBound { ref cond, .. }) = (self.0).1 {
                 cond.notify_one();
+                inner.wake_senders();
             }
             return Ok(item);
         }
@@ -266,6 +352,7 @@ unsafe extern "C" fn finalize<T, F: FnMut(T) -> Continue + 'static>(
         inner.source = ChannelSourceState::Destroyed;
         if let Some(ChannelBound { ref cond, .. }) = (channel.0).1 {
             cond.notify_all();
+            inner.wake_senders();
         }
     }
 
@@ -305,11 +392,37 @@ impl<T> Sender<T> {
     }
 
     /// Sends a value to the channel.
+    ///
+    /// This fails if the receiver was dropped or its source was removed from the main context,
+    /// in which case the unsent value is returned as part of the error.
     pub fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
         self.0.send(t)
     }
 }
 
+/// A `Sender` can also be used as a `Sink`, e.g. for forwarding a `Stream` to a main context.
+///
+/// As the channel is unbounded the sink is always ready.
+impl<T> Sink<T> for Sender<T> {
+    type Error = mpsc::SendError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, _ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.0.start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
         // Decrease the number of senders and wake up the channel if this
@@ -317,7 +430,7 @@ impl<T> Drop for Sender<T> {
         let mut inner = ((self.0).0).0.lock().unwrap();
         inner.num_senders -= 1;
         if inner.num_senders == 0 {
-            inner.set_ready_time(0);
+            inner.wake_receiver();
         }
     }
 }
@@ -351,16 +464,48 @@ impl<T> SyncSender<T> {
     }
 
     /// Sends a value to the channel and blocks if the channel is full.
+    ///
+    /// This fails if the receiver was dropped or its source was removed from the main context,
+    /// in which case the unsent value is returned as part of the error.
     pub fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
         self.0.send(t)
     }
 
     /// Sends a value to the channel.
+    ///
+    /// This fails without blocking if the channel is full, or if the receiver was dropped or its
+    /// source was removed from the main context. In both cases the unsent value is returned as
+    /// part of the error.
     pub fn try_send(&self, t: T) -> Result<(), mpsc::TrySendError<T>> {
         self.0.try_send(t)
     }
 }
 
+/// A `SyncSender` can also be used as a `Sink`, e.g. for forwarding a `Stream` to a main
+/// context.
+///
+/// Instead of blocking, the sink is not ready while the channel is full. With a bound of 0
+/// flushing the sink waits until the receiver has handled the item.
+impl<T> Sink<T> for SyncSender<T> {
+    type Error = mpsc::SendError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(ctx).map(Ok)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.0.start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_flush(ctx).map(Ok)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_flush(ctx).map(Ok)
+    }
+}
+
 impl<T> Drop for SyncSender<T> {
     fn drop(&mut self) {
         // Decrease the number of senders and wake up the channel if this
@@ -368,7 +513,7 @@ impl<T> Drop for SyncSender<T> {
         let mut inner = ((self.0).0).0.lock().unwrap();
         inner.num_senders -= 1;
         if inner.num_senders == 0 {
-            inner.set_ready_time(0);
+            inner.wake_receiver();
         }
     }
 }
@@ -401,6 +546,7 @@ impl<T> Drop for Receiver<T> {
             inner.source = ChannelSourceState::Destroyed;
             if let Some(ChannelBound { ref cond, .. }) = (channel.0).1 {
                 cond.notify_all();
+                inner.wake_senders();
             }
         }
     }
@@ -479,6 +625,33 @@ impl<T> Receiver<T> {
     }
 }
 
+/// A `Receiver` can also be consumed as a `Stream` from any future, e.g. one
+/// spawned on a main context, instead of attaching it with a closure.
+///
+/// The stream ends once all senders are dropped.
+///
+/// This requires the `channel_stream` feature.
+#[cfg(feature = "channel_stream")]
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<T>> {
+        let channel = self.0.as_ref().expect("Receiver without channel");
+
+        // Register the waker first so that no item sent in the meantime is missed
+        {
+            let mut inner = (channel.0).0.lock().unwrap();
+            inner.waker = Some(ctx.waker().clone());
+        }
+
+        match channel.try_recv() {
+            Ok(item) => Poll::Ready(Some(item)),
+            Err(mpsc::TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+}
+
 impl MainContext {
     /// Creates a channel for a main context.
     ///
@@ -539,7 +712,7 @@ mod tests {
         let c = MainContext::new();
         let l = MainLoop::new(Some(&c), false);
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::channel(Priority::default());
 
@@ -570,7 +743,7 @@ mod tests {
         let c = MainContext::new();
         let l = MainLoop::new(Some(&c), false);
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
 
@@ -601,11 +774,24 @@ mod tests {
         assert_eq!(sender.send(1), Err(mpsc::SendError(1)));
     }
 
+    #[test]
+    fn test_drop_receiver_sync() {
+        let (sender, receiver) = MainContext::sync_channel::<String>(Priority::default(), 1);
+
+        drop(receiver);
+        let err = sender.send(String::from("unsent")).unwrap_err();
+        assert_eq!(err.0, "unsent");
+        assert_eq!(
+            sender.try_send(String::from("unsent")),
+            Err(mpsc::TrySendError::Disconnected(String::from("unsent")))
+        );
+    }
+
     #[test]
     fn test_remove_receiver() {
         let c = MainContext::new();
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
 
@@ -621,7 +807,7 @@ mod tests {
     fn test_remove_receiver_and_drop_source() {
         let c = MainContext::new();
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
 
@@ -649,12 +835,143 @@ mod tests {
         assert_eq!(sender.send(1), Err(mpsc::SendError(1)));
     }
 
+    #[test]
+    #[cfg(feature = "channel_stream")]
+    fn test_stream() {
+        use futures_util::stream::StreamExt;
+
+        let c = MainContext::new();
+
+        let (sender, receiver) = MainContext::channel(Priority::default());
+
+        let thread = thread::spawn(move || {
+            sender.send(1).unwrap();
+            sender.send(2).unwrap();
+            sender.send(3).unwrap();
+        });
+
+        let sum = c.block_on(receiver.fold(0, |sum, item| futures_util::future::ready(sum + item)));
+        thread.join().unwrap();
+
+        assert_eq!(sum, 6);
+    }
+
+    struct FlagWaker(AtomicBool);
+
+    impl futures_task::ArcWake for FlagWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    // Receives an item without going through the `Stream` implementation
+    fn try_recv<T>(receiver: &Receiver<T>) -> Option<T> {
+        receiver.0.as_ref().unwrap().try_recv().ok()
+    }
+
+    #[test]
+    fn test_sink() {
+        let (mut sender, receiver) = MainContext::channel(Priority::default());
+
+        let flag = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let waker = futures_task::waker(flag.clone());
+        let mut ctx = Context::from_waker(&waker);
+
+        for i in 1..4 {
+            assert_eq!(
+                Pin::new(&mut sender).poll_ready(&mut ctx),
+                Poll::Ready(Ok(()))
+            );
+            Pin::new(&mut sender).start_send(i).unwrap();
+        }
+        assert_eq!(
+            Pin::new(&mut sender).poll_close(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+        drop(sender);
+
+        let mut sum = 0;
+        while let Some(item) = try_recv(&receiver) {
+            sum += item;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn test_sync_channel_sink() {
+        let (mut sender, receiver) = MainContext::sync_channel(Priority::default(), 2);
+
+        let flag = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let waker = futures_task::waker(flag.clone());
+        let mut ctx = Context::from_waker(&waker);
+
+        // The first two must succeed
+        for i in 1..3 {
+            assert_eq!(
+                Pin::new(&mut sender).poll_ready(&mut ctx),
+                Poll::Ready(Ok(()))
+            );
+            Pin::new(&mut sender).start_send(i).unwrap();
+        }
+
+        // Now the channel is full until an item is consumed
+        assert_eq!(Pin::new(&mut sender).poll_ready(&mut ctx), Poll::Pending);
+        assert!(!flag.0.load(Ordering::SeqCst));
+        assert_eq!(try_recv(&receiver), Some(1));
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(
+            Pin::new(&mut sender).poll_ready(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+        Pin::new(&mut sender).start_send(3).unwrap();
+
+        // Dropping the receiver wakes up the sender again and sending fails
+        flag.0.store(false, Ordering::SeqCst);
+        assert_eq!(Pin::new(&mut sender).poll_ready(&mut ctx), Poll::Pending);
+        drop(receiver);
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(
+            Pin::new(&mut sender).poll_ready(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+        assert_eq!(Pin::new(&mut sender).start_send(4), Err(mpsc::SendError(4)));
+    }
+
+    #[test]
+    fn test_sync_channel_sink_rendezvous() {
+        let (mut sender, receiver) = MainContext::sync_channel(Priority::default(), 0);
+
+        let flag = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let waker = futures_task::waker(flag.clone());
+        let mut ctx = Context::from_waker(&waker);
+
+        assert_eq!(
+            Pin::new(&mut sender).poll_ready(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+        Pin::new(&mut sender).start_send(1).unwrap();
+
+        // Flushing only succeeds once the receiver took the item
+        assert_eq!(Pin::new(&mut sender).poll_flush(&mut ctx), Poll::Pending);
+        assert_eq!(Pin::new(&mut sender).poll_ready(&mut ctx), Poll::Pending);
+        assert_eq!(try_recv(&receiver), Some(1));
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(
+            Pin::new(&mut sender).poll_flush(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+        assert_eq!(
+            Pin::new(&mut sender).poll_ready(&mut ctx),
+            Poll::Ready(Ok(()))
+        );
+    }
+
     #[test]
     fn test_sync_channel() {
         let c = MainContext::new();
         let l = MainLoop::new(Some(&c), false);
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::sync_channel(Priority::default(), 2);
 
@@ -703,7 +1020,7 @@ mod tests {
         let c = MainContext::new();
         let l = MainLoop::new(Some(&c), false);
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::sync_channel(Priority::default(), 3);
 
@@ -754,7 +1071,7 @@ mod tests {
     fn test_sync_channel_drop_receiver_wakeup() {
         let c = MainContext::new();
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::sync_channel(Priority::default(), 2);
 
@@ -784,7 +1101,7 @@ mod tests {
         let c = MainContext::new();
         let l = MainLoop::new(Some(&c), false);
 
-        c.acquire();
+        let _acquire = c.acquire().unwrap();
 
         let (sender, receiver) = MainContext::sync_channel(Priority::default(), 0);
 
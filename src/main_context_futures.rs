@@ -2,12 +2,17 @@
 // See the COPYRIGHT file at the top-level directory of this distribution.
 // Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
 
+use futures_channel::oneshot;
 use futures_core::future::Future;
 use futures_core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use futures_task::{FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError};
-use futures_util::future::FutureExt;
+use futures_util::future::{AbortHandle, Abortable, FutureExt};
 use glib_sys;
+use std::any::Any;
+use std::error;
+use std::fmt;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::pin;
 use std::ptr;
 use translate::{from_glib_borrow, from_glib_full, mut_override, Borrowed, ToGlib};
@@ -211,13 +216,137 @@ impl TaskSource {
     }
 }
 
+/// Error returned by a [`JoinHandle`] if the spawned `Future` did not run to completion.
+///
+/// [`JoinHandle`]: struct.JoinHandle.html
+pub enum JoinError {
+    /// The `Future` was aborted via [`JoinHandle::abort`] or its `MainContext` went away
+    /// before it resolved.
+    ///
+    /// [`JoinHandle::abort`]: struct.JoinHandle.html#method.abort
+    Aborted,
+    /// The `Future` panicked. This contains the panic payload.
+    Panicked(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    /// Returns `true` if the `Future` was aborted.
+    pub fn is_aborted(&self) -> bool {
+        match *self {
+            JoinError::Aborted => true,
+            JoinError::Panicked(_) => false,
+        }
+    }
+
+    /// Returns `true` if the `Future` panicked.
+    pub fn is_panic(&self) -> bool {
+        !self.is_aborted()
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JoinError::Aborted => f.write_str("Aborted"),
+            JoinError::Panicked(_) => f.debug_tuple("Panicked").field(&"..").finish(),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JoinError::Aborted => f.write_str("Future was aborted"),
+            JoinError::Panicked(_) => f.write_str("Future panicked"),
+        }
+    }
+}
+
+impl error::Error for JoinError {}
+
+/// A handle to a `Future` spawned on a `MainContext`.
+///
+/// The handle itself is a `Future` that resolves to the output of the spawned `Future`, or to a
+/// [`JoinError`] if it was aborted or panicked.
+///
+/// Dropping the handle detaches the spawned `Future`, it keeps on running on the main context
+/// until it resolved. A panic of a detached `Future` is propagated from the main context as
+/// before.
+///
+/// [`JoinError`]: enum.JoinError.html
+pub struct JoinHandle<T> {
+    receiver: oneshot::Receiver<Result<T, Box<dyn Any + Send + 'static>>>,
+    abort_handle: AbortHandle,
+}
+
+impl<T> JoinHandle<T> {
+    fn new<F: Future<Output = T>>(f: F) -> (impl Future<Output = ()>, Self) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let (sender, receiver) = oneshot::channel();
+
+        let f = Abortable::new(AssertUnwindSafe(f).catch_unwind(), abort_registration).map(
+            move |res| {
+                // If aborted the sender is dropped here and the handle resolves accordingly
+                if let Ok(res) = res {
+                    // Nobody is waiting for a panic if the handle was dropped already
+                    if let Err(Err(panic)) = sender.send(res) {
+                        panic::resume_unwind(panic);
+                    }
+                }
+            },
+        );
+
+        (
+            f,
+            JoinHandle {
+                receiver,
+                abort_handle,
+            },
+        )
+    }
+
+    /// Aborts the spawned `Future`.
+    ///
+    /// The `Future` is dropped the next time the main context would poll it, and this handle
+    /// resolves to `JoinError::Aborted` unless the `Future` has already resolved.
+    pub fn abort(&self) {
+        self.abort_handle.abort();
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinHandle").finish()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<T, JoinError>> {
+        match self.receiver.poll_unpin(ctx) {
+            Poll::Ready(Ok(Ok(res))) => Poll::Ready(Ok(res)),
+            Poll::Ready(Ok(Err(panic))) => Poll::Ready(Err(JoinError::Panicked(panic))),
+            Poll::Ready(Err(oneshot::Canceled)) => Poll::Ready(Err(JoinError::Aborted)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 impl MainContext {
     /// Spawn a new infallible `Future` on the main context.
     ///
     /// This can be called from any thread and will execute the future from the thread
     /// where main context is running, e.g. via a `MainLoop`.
-    pub fn spawn<F: Future<Output = ()> + Send + 'static>(&self, f: F) {
-        self.spawn_with_priority(::PRIORITY_DEFAULT, f);
+    ///
+    /// The returned [`JoinHandle`] resolves to the output of the `Future`.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn<R: Send + 'static, F: Future<Output = R> + Send + 'static>(
+        &self,
+        f: F,
+    ) -> JoinHandle<R> {
+        self.spawn_with_priority(::PRIORITY_DEFAULT, f)
     }
 
     /// Spawn a new infallible `Future` on the main context.
@@ -227,22 +356,36 @@ impl MainContext {
     /// This can be called only from the thread where the main context is running, e.g.
     /// from any other `Future` that is executed on this main context, or after calling
     /// `push_thread_default` or `acquire` on the main context.
-    pub fn spawn_local<F: Future<Output = ()> + 'static>(&self, f: F) {
-        self.spawn_local_with_priority(::PRIORITY_DEFAULT, f);
+    ///
+    /// The returned [`JoinHandle`] resolves to the output of the `Future`.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn_local<R: 'static, F: Future<Output = R> + 'static>(&self, f: F) -> JoinHandle<R> {
+        self.spawn_local_with_priority(::PRIORITY_DEFAULT, f)
     }
 
     /// Spawn a new infallible `Future` on the main context, with a non-default priority.
     ///
     /// This can be called from any thread and will execute the future from the thread
     /// where main context is running, e.g. via a `MainLoop`.
-    pub fn spawn_with_priority<F: Future<Output = ()> + Send + 'static>(
+    ///
+    /// The `Future` is polled from a source with the given `priority`, e.g. long-running
+    /// background work can use `PRIORITY_LOW` so that it does not delay futures or other
+    /// sources with the default priority.
+    ///
+    /// The returned [`JoinHandle`] resolves to the output of the `Future`.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn_with_priority<R: Send + 'static, F: Future<Output = R> + Send + 'static>(
         &self,
         priority: Priority,
         f: F,
-    ) {
+    ) -> JoinHandle<R> {
+        let (f, handle) = JoinHandle::new(f);
         let f = FutureObj::new(Box::new(f));
         let source = TaskSource::new(priority, FutureWrapper::Send(f));
         source.attach(Some(&*self));
+        handle
     }
 
     /// Spawn a new infallible `Future` on the main context, with a non-default priority.
@@ -252,18 +395,58 @@ impl MainContext {
     /// This can be called only from the thread where the main context is running, e.g.
     /// from any other `Future` that is executed on this main context, or after calling
     /// `push_thread_default` or `acquire` on the main context.
-    pub fn spawn_local_with_priority<F: Future<Output = ()> + 'static>(
+    ///
+    /// The `Future` is polled from a source with the given `priority`.
+    ///
+    /// The returned [`JoinHandle`] resolves to the output of the `Future`.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn_local_with_priority<R: 'static, F: Future<Output = R> + 'static>(
         &self,
         priority: Priority,
         f: F,
-    ) {
+    ) -> JoinHandle<R> {
         assert!(
             self.is_owner(),
             "Spawning local futures only allowed on the thread owning the MainContext"
         );
+        let (f, handle) = JoinHandle::new(f);
         let f = LocalFutureObj::new(Box::new(f));
         let source = TaskSource::new(priority, FutureWrapper::NonSend(ThreadGuard::new(f)));
         source.attach(Some(&*self));
+        handle
+    }
+
+    /// Spawn a new infallible `Future` on the main context, which is created by calling `func`
+    /// from the thread where the main context is running.
+    ///
+    /// Different to `spawn_local()` this can be called from any thread, and only `func` and the
+    /// output of the `Future` have to be `Send`. The `Future` itself can hold non-`Send` values,
+    /// e.g. GLib objects that must only be used from the main context.
+    ///
+    /// The returned [`JoinHandle`] can be awaited from any executor, e.g. from a task running on
+    /// a tokio runtime in another thread.
+    ///
+    /// The main context does not drive the reactor of other runtimes. Futures that need one, like
+    /// tokio network I/O, have to be spawned on a runtime that runs in its own threads, and its
+    /// join handle can then be awaited from the main context.
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    pub fn spawn_from_within<R, Fut, F>(&self, func: F) -> JoinHandle<R>
+    where
+        R: Send + 'static,
+        Fut: Future<Output = R> + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+    {
+        let mut func = Some(func);
+        let mut future: Option<ThreadGuard<pin::Pin<Box<Fut>>>> = None;
+        self.spawn(futures_util::future::poll_fn(move |ctx| {
+            let future = future.get_or_insert_with(|| {
+                let func = func.take().unwrap();
+                ThreadGuard::new(Box::pin(func()))
+            });
+            future.get_mut().as_mut().poll(ctx)
+        }))
     }
 
     /// Runs a new, infallible `Future` on the main context and block until it finished, returning
@@ -329,6 +512,7 @@ mod tests {
     use futures_util::future::TryFutureExt;
     use std::sync::mpsc;
     use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn test_spawn() {
@@ -367,15 +551,88 @@ mod tests {
         let c = MainContext::new();
         let l = ::MainLoop::new(Some(&c), false);
 
-        c.push_thread_default();
+        let _thread_default = c.push_thread_default();
         let l_clone = l.clone();
         c.spawn_local(futures_util::future::lazy(move |_ctx| {
             l_clone.quit();
         }));
 
         l.run();
+    }
+
+    #[test]
+    fn test_spawn_priority() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let c = MainContext::new();
+        let _acquire = c.acquire().unwrap();
+
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let order_clone = order.clone();
+        c.spawn_local_with_priority(
+            ::PRIORITY_LOW,
+            futures_util::future::lazy(move |_ctx| order_clone.borrow_mut().push(::PRIORITY_LOW)),
+        );
+        let order_clone = order.clone();
+        c.spawn_local_with_priority(
+            ::PRIORITY_HIGH,
+            futures_util::future::lazy(move |_ctx| order_clone.borrow_mut().push(::PRIORITY_HIGH)),
+        );
+
+        assert!(c.run_until(|| order.borrow().len() == 2, Duration::from_secs(10)));
+
+        assert_eq!(*order.borrow(), vec![::PRIORITY_HIGH, ::PRIORITY_LOW]);
+    }
+
+    #[test]
+    fn test_spawn_join() {
+        let c = MainContext::new();
 
-        c.pop_thread_default();
+        let handle = c.spawn(futures_util::future::ready(123));
+        assert_eq!(c.block_on(handle).unwrap(), 123);
+    }
+
+    #[test]
+    fn test_spawn_abort() {
+        let c = MainContext::new();
+
+        let handle = c.spawn(futures_util::future::pending::<()>());
+        handle.abort();
+        assert!(c.block_on(handle).unwrap_err().is_aborted());
+    }
+
+    #[test]
+    fn test_spawn_panic() {
+        let c = MainContext::new();
+
+        let handle: JoinHandle<()> = c.spawn(futures_util::future::lazy(|_ctx| panic!("oh no")));
+        assert!(c.block_on(handle).unwrap_err().is_panic());
+    }
+
+    #[test]
+    fn test_spawn_from_within() {
+        use std::rc::Rc;
+
+        let c = MainContext::new();
+        let l = ::MainLoop::new(Some(&c), false);
+
+        let c_clone = c.clone();
+        let l_clone = l.clone();
+        let other = thread::spawn(move || {
+            // The future holds an Rc and is only ever created and polled on the main context
+            let handle = c_clone.spawn_from_within(|| {
+                let value = Rc::new(123);
+                futures_util::future::lazy(move |_ctx| *value)
+            });
+            let res = futures_executor::block_on(handle).unwrap();
+            l_clone.quit();
+            res
+        });
+
+        l.run();
+        assert_eq!(other.join().unwrap(), 123);
     }
 
     #[test]
@@ -0,0 +1,86 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+use Continue;
+use MainLoop;
+
+impl MainLoop {
+    /// Returns a guard that quits the main loop once it is dropped.
+    ///
+    /// The guard can be sent to other threads and stored in controller objects, so that the main
+    /// loop is quit reliably on all code paths, including early returns and panics. It is also
+    /// fine to drop the guard before the main loop is run, in which case the main loop quits
+    /// right after it was started.
+    pub fn quit_guard(&self) -> MainLoopQuitGuard {
+        MainLoopQuitGuard(self.clone())
+    }
+
+    /// Runs the main loop until the guard passed to `func` is dropped.
+    ///
+    /// `func` is called before the main loop is run and can either drop the guard
+    /// directly or move it to wherever the decision to quit is made.
+    pub fn run_scoped<F: FnOnce(MainLoopQuitGuard)>(&self, func: F) {
+        func(self.quit_guard());
+        self.run();
+    }
+}
+
+/// Guard that quits a `MainLoop` once it is dropped.
+///
+/// See `MainLoop::quit_guard()` for details.
+#[must_use = "if unused the MainLoop will immediately quit"]
+#[derive(Debug)]
+pub struct MainLoopQuitGuard(MainLoop);
+
+impl MainLoopQuitGuard {
+    /// Returns the main loop this guard belongs to.
+    pub fn main_loop(&self) -> &MainLoop {
+        &self.0
+    }
+}
+
+impl Drop for MainLoopQuitGuard {
+    fn drop(&mut self) {
+        // Quit from a source of the main loop's context instead of directly so that
+        // quitting also works if the main loop is not running yet
+        let main_loop = self.0.clone();
+        let source = ::idle_source_new(None, ::PRIORITY_HIGH, move || {
+            main_loop.quit();
+            Continue(false)
+        });
+        source.attach(Some(&self.0.get_context()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+    use MainContext;
+
+    #[test]
+    fn test_run_scoped() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        l.run_scoped(|guard| {
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                drop(guard);
+            });
+        });
+        assert!(!l.is_running());
+    }
+
+    #[test]
+    fn test_quit_guard_before_run() {
+        let c = MainContext::new();
+        let l = MainLoop::new(Some(&c), false);
+
+        drop(l.quit_guard());
+        l.run();
+        assert!(!l.is_running());
+    }
+}
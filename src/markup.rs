@@ -0,0 +1,467 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Streaming parser for the simple XML subset understood by GLib's `GMarkup`.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::{MarkupParseContext, MarkupParseContextRef, MarkupParseFlags, MarkupParser};
+//!
+//! #[derive(Default)]
+//! struct Collector {
+//!     elements: Vec<String>,
+//! }
+//!
+//! impl MarkupParser for Collector {
+//!     fn start_element(
+//!         &mut self,
+//!         _context: &MarkupParseContextRef,
+//!         element_name: &str,
+//!         _attributes: &[(&str, &str)],
+//!     ) -> Result<(), glib::Error> {
+//!         self.elements.push(element_name.to_owned());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut context = MarkupParseContext::new(Collector::default(), MarkupParseFlags::empty());
+//! context.parse("<interface><object>").unwrap();
+//! context.parse("</object></interface>").unwrap();
+//! context.end_parse().unwrap();
+//! assert_eq!(context.into_parser().elements, ["interface", "object"]);
+//! ```
+
+use glib_sys;
+use libc::{c_char, c_int};
+use std::ffi::CStr;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::str;
+use translate::*;
+use Error;
+use MarkupParseFlags;
+
+/// Callbacks invoked by a [`MarkupParseContext`](struct.MarkupParseContext.html) while parsing.
+///
+/// All methods have default implementations that ignore the event. Returning an error from any
+/// of the methods stops parsing, and the error is returned from
+/// [`MarkupParseContext::parse`](struct.MarkupParseContext.html#method.parse).
+pub trait MarkupParser {
+    /// Called for every opening tag, with the attributes in document order.
+    fn start_element(
+        &mut self,
+        _context: &MarkupParseContextRef,
+        _element_name: &str,
+        _attributes: &[(&str, &str)],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for every closing tag, including for empty elements like `<foo/>`.
+    fn end_element(
+        &mut self,
+        _context: &MarkupParseContextRef,
+        _element_name: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for character data, with entities already expanded.
+    ///
+    /// The text of one element might be split over multiple calls.
+    fn text(&mut self, _context: &MarkupParseContextRef, _text: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called for comments, processing instructions and doctype declarations, as well as
+    /// `CDATA` sections unless `MarkupParseFlags::TREAT_CDATA_AS_TEXT` is set.
+    fn passthrough(
+        &mut self,
+        _context: &MarkupParseContextRef,
+        _passthrough_text: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when parsing fails, either because of invalid input or because one of the other
+    /// callbacks returned an error.
+    fn error(&mut self, _context: &MarkupParseContextRef, _error: &Error) {}
+}
+
+/// Borrowed parse context passed to the [`MarkupParser`](trait.MarkupParser.html) callbacks.
+pub struct MarkupParseContextRef(ptr::NonNull<glib_sys::GMarkupParseContext>);
+
+impl MarkupParseContextRef {
+    /// Returns the current line and character number, both starting at 1.
+    pub fn position(&self) -> (i32, i32) {
+        unsafe { get_position(self.0.as_ptr()) }
+    }
+
+    /// Returns the name of the element currently being parsed.
+    pub fn element(&self) -> Option<&str> {
+        unsafe { get_element(self.0.as_ptr()) }
+    }
+
+    /// Returns the names of all open elements, starting with the innermost one.
+    pub fn element_stack(&self) -> Vec<&str> {
+        unsafe {
+            let mut stack = Vec::new();
+            let mut node = glib_sys::g_markup_parse_context_get_element_stack(self.0.as_ptr());
+            while !node.is_null() {
+                stack.push(to_str((*node).data as *const c_char));
+                node = (*node).next;
+            }
+            stack
+        }
+    }
+}
+
+impl fmt::Debug for MarkupParseContextRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MarkupParseContextRef")
+            .field("position", &self.position())
+            .field("element", &self.element())
+            .finish()
+    }
+}
+
+/// Incremental `GMarkup` parser that forwards all events to a
+/// [`MarkupParser`](trait.MarkupParser.html).
+pub struct MarkupParseContext<P: MarkupParser> {
+    ptr: ptr::NonNull<glib_sys::GMarkupParseContext>,
+    // GLib only keeps a pointer to the vtable, so it has to stay at a fixed address
+    _vtable: Box<glib_sys::GMarkupParser>,
+    parser: *mut P,
+}
+
+impl<P: MarkupParser> MarkupParseContext<P> {
+    /// Creates a new parse context that calls `parser` for every event.
+    pub fn new(parser: P, flags: MarkupParseFlags) -> Self {
+        let vtable = Box::new(glib_sys::GMarkupParser {
+            start_element: Some(start_element_trampoline::<P>),
+            end_element: Some(end_element_trampoline::<P>),
+            text: Some(text_trampoline::<P>),
+            passthrough: Some(passthrough_trampoline::<P>),
+            error: Some(error_trampoline::<P>),
+        });
+        let parser = Box::into_raw(Box::new(parser));
+        unsafe {
+            let ptr = glib_sys::g_markup_parse_context_new(
+                &*vtable,
+                flags.to_glib(),
+                parser as glib_sys::gpointer,
+                None,
+            );
+            MarkupParseContext {
+                ptr: ptr::NonNull::new_unchecked(ptr),
+                _vtable: vtable,
+                parser,
+            }
+        }
+    }
+
+    /// Feeds the next chunk of the document to the parser.
+    ///
+    /// Chunks don't have to end at element or character boundaries. After an error was
+    /// returned the context can't be used for parsing anymore.
+    pub fn parse<T: AsRef<[u8]>>(&mut self, text: T) -> Result<(), Error> {
+        let text = text.as_ref();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_markup_parse_context_parse(
+                self.ptr.as_ptr(),
+                text.as_ptr() as *const c_char,
+                text.len() as isize,
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    /// Signals that the whole document was passed to [`parse`](#method.parse).
+    ///
+    /// Fails if the document was truncated, e.g. because an element was not closed.
+    pub fn end_parse(&mut self) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_markup_parse_context_end_parse(self.ptr.as_ptr(), &mut error);
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    /// Returns the current line and character number, both starting at 1.
+    pub fn position(&self) -> (i32, i32) {
+        unsafe { get_position(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the name of the element currently being parsed.
+    pub fn element(&self) -> Option<&str> {
+        unsafe { get_element(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the parser.
+    pub fn parser(&self) -> &P {
+        unsafe { &*self.parser }
+    }
+
+    /// Returns the parser mutably.
+    pub fn parser_mut(&mut self) -> &mut P {
+        unsafe { &mut *self.parser }
+    }
+
+    /// Frees the parse context and returns the parser.
+    pub fn into_parser(self) -> P {
+        unsafe {
+            glib_sys::g_markup_parse_context_free(self.ptr.as_ptr());
+            let parser = Box::from_raw(self.parser);
+            drop(ptr::read(&self._vtable));
+            mem::forget(self);
+            *parser
+        }
+    }
+}
+
+impl<P: MarkupParser> Drop for MarkupParseContext<P> {
+    fn drop(&mut self) {
+        unsafe {
+            glib_sys::g_markup_parse_context_free(self.ptr.as_ptr());
+            drop(Box::from_raw(self.parser));
+        }
+    }
+}
+
+impl<P: MarkupParser + fmt::Debug> fmt::Debug for MarkupParseContext<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MarkupParseContext")
+            .field("parser", self.parser())
+            .field("position", &self.position())
+            .finish()
+    }
+}
+
+// GMarkup validates every string it passes to the callbacks as UTF-8, even when the input is
+// fed in chunks that split a character
+unsafe fn to_str<'a>(s: *const c_char) -> &'a str {
+    str::from_utf8_unchecked(CStr::from_ptr(s).to_bytes())
+}
+
+unsafe fn get_position(context: *mut glib_sys::GMarkupParseContext) -> (i32, i32) {
+    let mut line_number: c_int = 0;
+    let mut char_number: c_int = 0;
+    glib_sys::g_markup_parse_context_get_position(context, &mut line_number, &mut char_number);
+    (line_number, char_number)
+}
+
+unsafe fn get_element<'a>(context: *mut glib_sys::GMarkupParseContext) -> Option<&'a str> {
+    let element = glib_sys::g_markup_parse_context_get_element(context);
+    if element.is_null() {
+        None
+    } else {
+        Some(to_str(element))
+    }
+}
+
+unsafe fn propagate(result: Result<(), Error>, error: *mut *mut glib_sys::GError) {
+    if let Err(err) = result {
+        *error = err.to_glib_full() as *mut _;
+    }
+}
+
+unsafe extern "C" fn start_element_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    element_name: *const c_char,
+    attribute_names: *mut *const c_char,
+    attribute_values: *mut *const c_char,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupParseContextRef(ptr::NonNull::new_unchecked(context));
+    let mut attributes = Vec::new();
+    let mut i = 0;
+    while !(*attribute_names.add(i)).is_null() {
+        attributes.push((
+            to_str(*attribute_names.add(i)),
+            to_str(*attribute_values.add(i)),
+        ));
+        i += 1;
+    }
+    propagate(
+        parser.start_element(&context, to_str(element_name), &attributes),
+        error,
+    );
+}
+
+unsafe extern "C" fn end_element_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    element_name: *const c_char,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupParseContextRef(ptr::NonNull::new_unchecked(context));
+    propagate(parser.end_element(&context, to_str(element_name)), error);
+}
+
+unsafe extern "C" fn text_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    text: *const c_char,
+    text_len: usize,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupParseContextRef(ptr::NonNull::new_unchecked(context));
+    let text = str::from_utf8_unchecked(slice::from_raw_parts(text as *const u8, text_len));
+    propagate(parser.text(&context, text), error);
+}
+
+unsafe extern "C" fn passthrough_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    passthrough_text: *const c_char,
+    text_len: usize,
+    user_data: glib_sys::gpointer,
+    error: *mut *mut glib_sys::GError,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupParseContextRef(ptr::NonNull::new_unchecked(context));
+    let text = str::from_utf8_unchecked(slice::from_raw_parts(
+        passthrough_text as *const u8,
+        text_len,
+    ));
+    propagate(parser.passthrough(&context, text), error);
+}
+
+unsafe extern "C" fn error_trampoline<P: MarkupParser>(
+    context: *mut glib_sys::GMarkupParseContext,
+    error: *mut glib_sys::GError,
+    user_data: glib_sys::gpointer,
+) {
+    let parser = &mut *(user_data as *mut P);
+    let context = MarkupParseContextRef(ptr::NonNull::new_unchecked(context));
+    let error: Borrowed<Error> = from_glib_borrow(error);
+    parser.error(&context, &error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MarkupError;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        errors: usize,
+    }
+
+    impl MarkupParser for Recorder {
+        fn start_element(
+            &mut self,
+            context: &MarkupParseContextRef,
+            element_name: &str,
+            attributes: &[(&str, &str)],
+        ) -> Result<(), Error> {
+            if element_name == "forbidden" {
+                return Err(Error::new(MarkupError::UnknownElement, "forbidden element"));
+            }
+            assert_eq!(context.element(), Some(element_name));
+            let attributes: Vec<_> = attributes
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            self.events.push(format!(
+                "start {} [{}] {}",
+                element_name,
+                attributes.join(","),
+                context.element_stack().join("<")
+            ));
+            Ok(())
+        }
+
+        fn end_element(
+            &mut self,
+            _context: &MarkupParseContextRef,
+            element_name: &str,
+        ) -> Result<(), Error> {
+            self.events.push(format!("end {}", element_name));
+            Ok(())
+        }
+
+        fn text(&mut self, _context: &MarkupParseContextRef, text: &str) -> Result<(), Error> {
+            if !text.trim().is_empty() {
+                self.events.push(format!("text {}", text));
+            }
+            Ok(())
+        }
+
+        fn passthrough(
+            &mut self,
+            _context: &MarkupParseContextRef,
+            passthrough_text: &str,
+        ) -> Result<(), Error> {
+            self.events
+                .push(format!("passthrough {}", passthrough_text));
+            Ok(())
+        }
+
+        fn error(&mut self, _context: &MarkupParseContextRef, _error: &Error) {
+            self.errors += 1;
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let document = "<!-- c --><a x=\"1\" y='&lt;'><b/>t&amp;t</a>";
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        // Feed one byte at a time to check that chunk boundaries don't matter
+        for i in 0..document.len() {
+            context.parse(&document[i..=i]).unwrap();
+        }
+        context.end_parse().unwrap();
+        let recorder = context.into_parser();
+        assert_eq!(
+            recorder.events,
+            [
+                "passthrough <!-- c -->",
+                "start a [x=1,y=<] a",
+                "start b [] b<a",
+                "end b",
+                "text t&t",
+                "end a",
+            ]
+        );
+        assert_eq!(recorder.errors, 0);
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        let err = context.parse("<a>\n  <b></a>").unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::Parse));
+        assert_eq!(context.position().0, 2);
+        assert_eq!(context.parser().errors, 1);
+
+        let mut context =
+            MarkupParseContext::new(Recorder::default(), MarkupParseFlags::PREFIX_ERROR_POSITION);
+        let err = context.parse("<a>\n<forbidden/>").unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::UnknownElement));
+        assert!(err.to_string().starts_with("2:"));
+
+        let mut context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        context.parse("<a>").unwrap();
+        let err = context.end_parse().unwrap_err();
+        assert_eq!(err.kind::<MarkupError>(), Some(MarkupError::Parse));
+    }
+}
@@ -4,6 +4,12 @@
 
 //! `IMPL` Object wrapper implementation and `Object` binding.
 
+use futures_channel::{mpsc, oneshot};
+use futures_core::future::Future;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use futures_util::future::FutureExt;
+use futures_util::stream::StreamExt;
 use glib_sys;
 use gobject_sys;
 use quark::Quark;
@@ -22,6 +28,7 @@ use value::ToValue;
 use BoolError;
 use Closure;
 use SignalHandlerId;
+use SignalId;
 use Type;
 use Value;
 
@@ -195,6 +202,93 @@ impl<T: IsClassFor> Drop for ClassRef<T> {
 unsafe impl<T: IsClassFor> Send for ClassRef<T> {}
 unsafe impl<T: IsClassFor> Sync for ClassRef<T> {}
 
+/// A reference to the default vtable of an interface type.
+///
+/// This allows introspecting the properties declared on an interface, independent of the
+/// classes implementing it.
+#[derive(Debug)]
+pub struct InterfaceRef(ptr::NonNull<gobject_sys::GTypeInterface>);
+
+impl InterfaceRef {
+    /// Gets the default vtable of the interface `type_`.
+    ///
+    /// This will return `None` if `type_` is not an interface type.
+    pub fn from_type(type_: Type) -> Option<InterfaceRef> {
+        if type_ == Type::BaseInterface || !type_.is_a(&Type::BaseInterface) {
+            return None;
+        }
+
+        unsafe {
+            let ptr = gobject_sys::g_type_default_interface_ref(type_.to_glib());
+            if ptr.is_null() {
+                None
+            } else {
+                Some(InterfaceRef(ptr::NonNull::new_unchecked(ptr as *mut _)))
+            }
+        }
+    }
+
+    /// Get the type id of this interface.
+    pub fn get_type(&self) -> Type {
+        unsafe { from_glib(self.0.as_ref().g_type) }
+    }
+
+    pub fn has_property<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+        type_: Option<Type>,
+    ) -> bool {
+        let property_name = property_name.into();
+        let ptype = self.get_property_type(property_name);
+
+        match (ptype, type_) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(ptype), Some(type_)) => ptype == type_,
+        }
+    }
+
+    pub fn get_property_type<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<Type> {
+        self.find_property(property_name)
+            .map(|pspec| pspec.get_value_type())
+    }
+
+    pub fn find_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<::ParamSpec> {
+        let property_name = property_name.into();
+        unsafe {
+            from_glib_none(with_c_str(property_name, |property_name| {
+                gobject_sys::g_object_interface_find_property(
+                    self.0.as_ptr() as *mut _,
+                    property_name,
+                )
+            }))
+        }
+    }
+
+    pub fn list_properties(&self) -> Vec<::ParamSpec> {
+        unsafe {
+            let mut n_properties = 0;
+
+            let props = gobject_sys::g_object_interface_list_properties(
+                self.0.as_ptr() as *mut _,
+                &mut n_properties,
+            );
+            FromGlibContainer::from_glib_container_num(props, n_properties as usize)
+        }
+    }
+}
+
+impl Drop for InterfaceRef {
+    fn drop(&mut self) {
+        unsafe {
+            gobject_sys::g_type_default_interface_unref(self.0.as_ptr() as *mut _);
+        }
+    }
+}
+
+unsafe impl Send for InterfaceRef {}
+unsafe impl Sync for InterfaceRef {}
+
 /// Upcasting and downcasting support.
 ///
 /// Provides conversions up and down the class hierarchy tree.
@@ -396,6 +490,16 @@ pub trait Cast: ObjectType {
 
 impl<T: ObjectType> Cast for T {}
 
+impl<T: ObjectType + GlibPtrDefault> HashTableValue for T {
+    const DESTROY_FUNC: glib_sys::GDestroyNotify = Some(gobject_sys::g_object_unref);
+}
+
+// Objects are hashed and compared by pointer, just like their `Hash` and `Eq` implementations
+impl<T: ObjectType + GlibPtrDefault> HashTableKey for T {
+    const HASH_FUNC: glib_sys::GHashFunc = Some(glib_sys::g_direct_hash);
+    const EQUAL_FUNC: glib_sys::GEqualFunc = Some(glib_sys::g_direct_equal);
+}
+
 /// Marker trait for the statically known possibility of downcasting from `Self` to `T`.
 pub trait CanDowncast<T> {}
 
@@ -409,6 +513,9 @@ pub struct ObjectRef {
 impl Clone for ObjectRef {
     fn clone(&self) -> Self {
         unsafe {
+            #[cfg(feature = "leak_tracker")]
+            ::leak_tracker::track(self.inner.as_ptr());
+
             ObjectRef {
                 inner: ptr::NonNull::new_unchecked(gobject_sys::g_object_ref(self.inner.as_ptr())),
             }
@@ -419,6 +526,9 @@ impl Clone for ObjectRef {
 impl Drop for ObjectRef {
     fn drop(&mut self) {
         unsafe {
+            #[cfg(feature = "leak_tracker")]
+            ::leak_tracker::untrack(self.inner.as_ptr());
+
             gobject_sys::g_object_unref(self.inner.as_ptr());
         }
     }
@@ -565,8 +675,13 @@ impl FromGlibPtrNone<*mut GObject> for ObjectRef {
         assert_ne!((*ptr).ref_count, 0);
 
         // Attention: This takes ownership of floating references!
+        let ptr = gobject_sys::g_object_ref_sink(ptr);
+
+        #[cfg(feature = "leak_tracker")]
+        ::leak_tracker::track(ptr);
+
         ObjectRef {
-            inner: ptr::NonNull::new_unchecked(gobject_sys::g_object_ref_sink(ptr)),
+            inner: ptr::NonNull::new_unchecked(ptr),
         }
     }
 }
@@ -587,6 +702,9 @@ impl FromGlibPtrFull<*mut GObject> for ObjectRef {
         assert!(!ptr.is_null());
         assert_ne!((*ptr).ref_count, 0);
 
+        #[cfg(feature = "leak_tracker")]
+        ::leak_tracker::track(ptr);
+
         ObjectRef {
             inner: ptr::NonNull::new_unchecked(ptr),
         }
@@ -721,15 +839,24 @@ macro_rules! glib_weak_impl {
 /// ObjectType implementations for Object types. See `glib_wrapper!`.
 #[macro_export]
 macro_rules! glib_object_wrapper {
-    (@generic_impl [$($attr:meta)*] $name:ident, $ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ty, @get_type $get_type_expr:expr) => {
+    (@generic_impl [$($attr:meta)*] $name:ident, $ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ty, @get_type $get_type_expr:expr,
+        @options [$($opt:ident)*]) => {
         $(#[$attr])*
-        // Always derive Hash/Ord (and below impl Debug, PartialEq, Eq, PartialOrd) for object
+        // Always derive Hash/Ord (and below impl PartialEq, Eq, PartialOrd) for object
         // types. Due to inheritance and up/downcasting we must implement these by pointer or
         // otherwise they would potentially give differeny results for the same object depending on
         // the type we currently know for it
-        #[derive(Clone, Hash, Ord, Eq, Debug)]
+        #[derive(Clone, Hash, Ord, Eq)]
         pub struct $name($crate::object::ObjectRef);
 
+        $crate::glib_wrapper_raw_ptr_impl!($name, $ffi_name, |this| {
+            $crate::translate::ToGlibPtr::<*mut $ffi_name>::to_glib_full(&this)
+        });
+
+        $crate::glib_object_wrapper!(@check_options [$($opt)*]);
+        $crate::glib_object_wrapper!(@debug_impl $name, [$($opt)*]);
+        $crate::glib_object_wrapper!(@display_impl $name, [$($opt)*]);
+
         #[doc(hidden)]
         impl Into<$crate::object::ObjectRef> for $name {
             fn into(self) -> $crate::object::ObjectRef {
@@ -1090,6 +1217,56 @@ macro_rules! glib_object_wrapper {
         $crate::glib_weak_impl!($name);
     };
 
+    (@check_options []) => { };
+
+    (@check_options [display $($opt:ident)*]) => {
+        $crate::glib_object_wrapper!(@check_options [$($opt)*]);
+    };
+
+    (@check_options [no_debug $($opt:ident)*]) => {
+        $crate::glib_object_wrapper!(@check_options [$($opt)*]);
+    };
+
+    (@check_options [$other:ident $($opt:ident)*]) => {
+        compile_error!(concat!("Unknown wrapper option `@", stringify!($other), "`"));
+    };
+
+    // Debug is implemented unless the `@no_debug` option is given
+    (@debug_impl $name:ident, []) => {
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.0).finish()
+            }
+        }
+    };
+
+    (@debug_impl $name:ident, [no_debug $($opt:ident)*]) => { };
+
+    (@debug_impl $name:ident, [$_opt:ident $($opt:ident)*]) => {
+        $crate::glib_object_wrapper!(@debug_impl $name, [$($opt)*]);
+    };
+
+    // Display is only implemented if the `@display` option is given
+    (@display_impl $name:ident, []) => { };
+
+    (@display_impl $name:ident, [display $($opt:ident)*]) => {
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let value = $crate::value::ToValue::to_value(self);
+                let contents: $crate::GString = unsafe {
+                    $crate::translate::from_glib_full($crate::gobject_sys::g_strdup_value_contents(
+                        $crate::translate::ToGlibPtr::to_glib_none(&value).0,
+                    ))
+                };
+                f.write_str(&contents)
+            }
+        }
+    };
+
+    (@display_impl $name:ident, [$_opt:ident $($opt:ident)*]) => {
+        $crate::glib_object_wrapper!(@display_impl $name, [$($opt)*]);
+    };
+
     (@munch_impls $name:ident, ) => { };
 
     (@munch_impls $name:ident, $super_name:path) => {
@@ -1184,14 +1361,15 @@ macro_rules! glib_object_wrapper {
     // parent class list
     (@object [$($attr:meta)*] $name:ident, $ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident, @get_type $get_type_expr:expr) => {
         $crate::glib_object_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
-            @get_type $get_type_expr);
+            @get_type $get_type_expr, @options []);
         $crate::glib_object_wrapper!(@class_impl $name, $ffi_class_name, $rust_class_name);
     };
 
     (@object [$($attr:meta)*] $name:ident, $ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident,
-        @get_type $get_type_expr:expr, @extends [$($extends:tt)*], @implements [$($implements:tt)*]) => {
+        @get_type $get_type_expr:expr, @extends [$($extends:tt)*], @implements [$($implements:tt)*],
+        @options [$($opt:ident)*]) => {
         $crate::glib_object_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
-            @get_type $get_type_expr);
+            @get_type $get_type_expr, @options [$($opt)*]);
         $crate::glib_object_wrapper!(@munch_first_impl $name, $rust_class_name, $($extends)*);
         $crate::glib_object_wrapper!(@munch_impls $name, $($implements)*);
         $crate::glib_object_wrapper!(@class_impl $name, $ffi_class_name, $rust_class_name);
@@ -1207,9 +1385,10 @@ macro_rules! glib_object_wrapper {
         unsafe impl $crate::object::IsA<$crate::object::Object> for $name { }
     };
 
-    (@interface [$($attr:meta)*] $name:ident, $ffi_name:ty, @get_type $get_type_expr:expr, @requires [$($requires:tt)*]) => {
+    (@interface [$($attr:meta)*] $name:ident, $ffi_name:ty, @get_type $get_type_expr:expr, @requires [$($requires:tt)*],
+        @options [$($opt:ident)*]) => {
         $crate::glib_object_wrapper!(@generic_impl [$($attr)*] $name, $ffi_name, (), (),
-            @get_type $get_type_expr);
+            @get_type $get_type_expr, @options [$($opt)*]);
         $crate::glib_object_wrapper!(@munch_impls $name, $($requires)*);
 
         #[doc(hidden)]
@@ -1235,7 +1414,7 @@ impl Object {
 
         let klass = ObjectClass::from_type(type_)
             .ok_or_else(|| glib_bool_error!("Can't retrieve class for type '{}'", type_))?;
-        let pspecs = klass.list_properties();
+        let pspecs = klass.properties();
 
         let params = properties
             .iter()
@@ -1261,7 +1440,7 @@ impl Object {
 
         let klass = ObjectClass::from_type(type_)
             .ok_or_else(|| glib_bool_error!("Can't retrieve class for type '{}'", type_))?;
-        let pspecs = klass.list_properties();
+        let pspecs = klass.properties();
 
         let params = properties
             .iter()
@@ -1355,6 +1534,44 @@ pub trait ObjectExt: ObjectType {
     fn set_properties(&self, property_values: &[(&str, &dyn ToValue)]) -> Result<(), BoolError>;
     fn set_properties_generic(&self, property_values: &[(&str, Value)]) -> Result<(), BoolError>;
     fn get_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Result<Value, BoolError>;
+
+    /// Sets the property described by `pspec`.
+    ///
+    /// Unlike [`set_property`](#tymethod.set_property) this doesn't have to find the `ParamSpec`
+    /// for the property name first, which saves a `ParamSpec` lookup and reference on every call
+    /// when the `ParamSpec` is looked up once, e.g. with [`find_property`](#tymethod.find_property),
+    /// and cached like a `SignalId`. GObject itself still looks up the property by name
+    /// internally as it provides no API for setting properties by `ParamSpec`.
+    fn set_property_by_pspec<V: ToValue>(
+        &self,
+        pspec: &::ParamSpec,
+        value: &V,
+    ) -> Result<(), BoolError>;
+
+    /// Gets the value of the property described by `pspec`.
+    ///
+    /// See [`set_property_by_pspec`](#tymethod.set_property_by_pspec).
+    fn get_property_by_pspec(&self, pspec: &::ParamSpec) -> Result<Value, BoolError>;
+
+    /// Gets the value of the property `property_name` into `value`.
+    ///
+    /// `value` is reused if it already has the type of the property and re-initialized
+    /// otherwise, which avoids allocating a new `Value` per call when reading a property
+    /// repeatedly.
+    fn get_property_into<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+        value: &mut Value,
+    ) -> Result<(), BoolError>;
+
+    /// Gets the value of the property described by `pspec` into `value`.
+    ///
+    /// See [`get_property_into`](#tymethod.get_property_into).
+    fn get_property_by_pspec_into(
+        &self,
+        pspec: &::ParamSpec,
+        value: &mut Value,
+    ) -> Result<(), BoolError>;
     fn has_property<'a, N: Into<&'a str>>(&self, property_name: N, type_: Option<Type>) -> bool;
     fn get_property_type<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<Type>;
     fn find_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<::ParamSpec>;
@@ -1393,6 +1610,10 @@ pub trait ObjectExt: ObjectType {
     fn block_signal(&self, handler_id: &SignalHandlerId);
     fn unblock_signal(&self, handler_id: &SignalHandlerId);
     fn stop_signal_emission(&self, signal_name: &str);
+    /// Stops the current emission of the signal with the given `signal_id` and `details`.
+    ///
+    /// Different to `stop_signal_emission()` this does not need to parse the signal name.
+    fn stop_signal_emission_by_id(&self, signal_id: SignalId, details: Option<Quark>);
 
     fn connect<'a, N, F>(
         &self,
@@ -1422,6 +1643,62 @@ pub trait ObjectExt: ObjectType {
     where
         N: Into<&'a str>,
         F: Fn(&[Value]) -> Option<Value>;
+    /// Connects to the signal with the given `signal_id` and `details`.
+    ///
+    /// This is equivalent to `connect()` with a detailed signal name like `notify::name`, but
+    /// avoids formatting and parsing the signal name on every connection.
+    fn connect_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static;
+    /// Connects to the signal with the given `signal_id` and `details`.
+    ///
+    /// This is equivalent to `connect_local()` with a detailed signal name like `notify::name`,
+    /// but avoids formatting and parsing the signal name on every connection.
+    fn connect_local_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value> + 'static;
+    #[allow(clippy::missing_safety_doc)]
+    unsafe fn connect_unsafe_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value>;
+    /// Same as `connect(signal_name, true, callback)`, i.e. `callback` is called after the
+    /// default handler of the signal.
+    fn connect_after<'a, N, F>(
+        &self,
+        signal_name: N,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        N: Into<&'a str>,
+        F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static;
+    /// Same as `connect_local(signal_name, true, callback)`, i.e. `callback` is called after the
+    /// default handler of the signal.
+    fn connect_local_after<'a, N, F>(
+        &self,
+        signal_name: N,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        N: Into<&'a str>,
+        F: Fn(&[Value]) -> Option<Value> + 'static;
     fn emit<'a, N: Into<&'a str>>(
         &self,
         signal_name: N,
@@ -1434,6 +1711,31 @@ pub trait ObjectExt: ObjectType {
     ) -> Result<Option<Value>, BoolError>;
     fn disconnect(&self, handler_id: SignalHandlerId);
 
+    /// Connects to the signal `signal_name` and returns a `Stream` that yields the signal
+    /// arguments, including the instance, on every emission.
+    ///
+    /// The signal handler is disconnected once the `Stream` is dropped, and the `Stream` ends
+    /// once the object is finalized. Only signals without return value can be connected to
+    /// like this, and the `Stream` must be polled from the thread the signal is emitted on.
+    fn signal_stream<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+    ) -> Result<SignalStream, BoolError>;
+
+    /// Returns a `Future` that resolves to the new value of the property `property_name` the
+    /// next time it is notified.
+    ///
+    /// The notify handler is disconnected once the `Future` resolved or is dropped, and the
+    /// `Future` resolves to `None` if the object is finalized before.
+    ///
+    /// The notify handler panics if the property is notified on a different thread than the one
+    /// `wait_for_notify` was called on, so this must only be used for objects whose properties
+    /// are only changed on that thread.
+    fn wait_for_notify<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+    ) -> Result<NotifyFuture, BoolError>;
+
     fn connect_notify<F: Fn(&Self, &::ParamSpec) + Send + Sync + 'static>(
         &self,
         name: Option<&str>,
@@ -1450,6 +1752,15 @@ pub trait ObjectExt: ObjectType {
 
     fn downgrade(&self) -> WeakRef<Self>;
 
+    /// Creates a [`WeakPtr`](struct.WeakPtr.html) to the object, which is cheaper than a
+    /// `WeakRef` but not thread-safe.
+    ///
+    /// # Safety
+    ///
+    /// The object must only ever be unreffed on the current thread for as long as the `WeakPtr`
+    /// or any of its clones point to it.
+    unsafe fn downgrade_ptr(&self) -> WeakPtr<Self>;
+
     fn bind_property<'a, O: ObjectType, N: Into<&'a str>, M: Into<&'a str>>(
         &'a self,
         source_property: N,
@@ -1480,7 +1791,7 @@ impl<T: ObjectType> ObjectExt for T {
     fn set_properties(&self, property_values: &[(&str, &dyn ToValue)]) -> Result<(), BoolError> {
         use std::ffi::CString;
 
-        let pspecs = self.list_properties();
+        let pspecs = self.get_object_class().properties();
 
         let params = property_values
             .iter()
@@ -1518,7 +1829,7 @@ impl<T: ObjectType> ObjectExt for T {
     fn set_properties_generic(&self, property_values: &[(&str, Value)]) -> Result<(), BoolError> {
         use std::ffi::CString;
 
-        let pspecs = self.list_properties();
+        let pspecs = self.get_object_class().properties();
 
         let params = property_values
             .iter()
@@ -1571,12 +1882,22 @@ impl<T: ObjectType> ObjectExt for T {
             }
         };
 
+        self.set_property_by_pspec(&pspec, value)
+    }
+
+    fn set_property_by_pspec<V: ToValue>(
+        &self,
+        pspec: &::ParamSpec,
+        value: &V,
+    ) -> Result<(), BoolError> {
+        validate_property_owner(self.get_type(), pspec)?;
+
         let mut property_value = value.to_value();
-        validate_property_type(self.get_type(), false, &pspec, &mut property_value)?;
+        validate_property_type(self.get_type(), false, pspec, &mut property_value)?;
         unsafe {
             gobject_sys::g_object_set_property(
                 self.as_object_ref().to_glib_none().0,
-                property_name.to_glib_none().0,
+                gobject_sys::g_param_spec_get_name(pspec.to_glib_none().0),
                 property_value.to_glib_none().0,
             );
         }
@@ -1605,11 +1926,13 @@ impl<T: ObjectType> ObjectExt for T {
         let mut property_value = value.clone();
         validate_property_type(self.get_type(), false, &pspec, &mut property_value)?;
         unsafe {
-            gobject_sys::g_object_set_property(
-                self.as_object_ref().to_glib_none().0,
-                property_name.to_glib_none().0,
-                property_value.to_glib_none().0,
-            );
+            with_c_str(property_name, |property_name| {
+                gobject_sys::g_object_set_property(
+                    self.as_object_ref().to_glib_none().0,
+                    property_name,
+                    property_value.to_glib_none().0,
+                )
+            });
         }
 
         Ok(())
@@ -1629,19 +1952,63 @@ impl<T: ObjectType> ObjectExt for T {
             }
         };
 
+        self.get_property_by_pspec(&pspec)
+    }
+
+    fn get_property_by_pspec(&self, pspec: &::ParamSpec) -> Result<Value, BoolError> {
+        let mut value = Value::from_type(pspec.get_value_type());
+        self.get_property_by_pspec_into(pspec, &mut value)?;
+        Ok(value)
+    }
+
+    fn get_property_into<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+        value: &mut Value,
+    ) -> Result<(), BoolError> {
+        let property_name = property_name.into();
+
+        let pspec = match self.find_property(property_name) {
+            Some(pspec) => pspec,
+            None => {
+                return Err(glib_bool_error!(
+                    "property '{}' of type '{}' not found",
+                    property_name,
+                    self.get_type()
+                ));
+            }
+        };
+
+        self.get_property_by_pspec_into(&pspec, value)
+    }
+
+    fn get_property_by_pspec_into(
+        &self,
+        pspec: &::ParamSpec,
+        value: &mut Value,
+    ) -> Result<(), BoolError> {
+        validate_property_owner(self.get_type(), pspec)?;
+
         if !pspec.get_flags().contains(::ParamFlags::READABLE) {
             return Err(glib_bool_error!(
                 "property '{}' of type '{}' is not readable",
-                property_name,
+                pspec.get_name(),
                 self.get_type()
             ));
         }
 
         unsafe {
-            let mut value = Value::from_type(pspec.get_value_type());
+            let value_type = pspec.get_value_type();
+            if value.type_() != value_type {
+                if value.type_() != ::Type::Invalid {
+                    gobject_sys::g_value_unset(value.to_glib_none_mut().0);
+                }
+                gobject_sys::g_value_init(value.to_glib_none_mut().0, value_type.to_glib());
+            }
+
             gobject_sys::g_object_get_property(
                 self.as_object_ref().to_glib_none().0,
-                property_name.to_glib_none().0,
+                gobject_sys::g_param_spec_get_name(pspec.to_glib_none().0),
                 value.to_glib_none_mut().0,
             );
 
@@ -1649,11 +2016,11 @@ impl<T: ObjectType> ObjectExt for T {
             if value.type_() == ::Type::Invalid {
                 Err(glib_bool_error!(
                     "Failed to get property value for property '{}' of type '{}'",
-                    property_name,
+                    pspec.get_name(),
                     self.get_type()
                 ))
             } else {
-                Ok(value)
+                Ok(())
             }
         }
     }
@@ -1734,6 +2101,16 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn stop_signal_emission_by_id(&self, signal_id: SignalId, details: Option<Quark>) {
+        unsafe {
+            gobject_sys::g_signal_stop_emission(
+                self.as_object_ref().to_glib_none().0,
+                signal_id.to_glib(),
+                details.map_or(0, |q| q.to_glib()),
+            );
+        }
+    }
+
     fn disconnect(&self, handler_id: SignalHandlerId) {
         unsafe {
             gobject_sys::g_signal_handler_disconnect(
@@ -1743,6 +2120,65 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn signal_stream<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+    ) -> Result<SignalStream, BoolError> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let handler_id = self.connect_local(signal_name, false, move |values| {
+            // The receiver is only gone if the stream was dropped and we're
+            // disconnected right afterwards
+            let _ = sender.unbounded_send(values.to_vec());
+            None
+        })?;
+
+        Ok(SignalStream {
+            object: unsafe { self.unsafe_cast_ref::<Object>() }.downgrade(),
+            handler_id: Some(handler_id),
+            receiver,
+        })
+    }
+
+    fn wait_for_notify<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+    ) -> Result<NotifyFuture, BoolError> {
+        let property_name = property_name.into();
+        let pspec = self.find_property(property_name).ok_or_else(|| {
+            glib_bool_error!(
+                "Can't find property '{}' for type '{}'",
+                property_name,
+                self.get_type()
+            )
+        })?;
+
+        let (sender, receiver) = oneshot::channel();
+        let sender = ::std::cell::RefCell::new(Some(sender));
+
+        let signal_name = format!("notify::{}", pspec.get_name());
+        let handler_id = self.connect_local(&*signal_name, false, move |values| {
+            // Further notifications until the handler is disconnected are ignored
+            if let Some(sender) = sender.borrow_mut().take() {
+                let obj = values[0]
+                    .get::<Object>()
+                    .expect("Failed to get object")
+                    .expect("Failed to get object");
+                let value = obj
+                    .get_property_by_pspec(&pspec)
+                    .expect("Failed to get property");
+                let _ = sender.send(value);
+            }
+            None
+        })?;
+
+        Ok(NotifyFuture {
+            object: unsafe { self.unsafe_cast_ref::<Object>() }.downgrade(),
+            handler_id: Some(handler_id),
+            receiver,
+        })
+    }
+
     fn connect_notify<F: Fn(&Self, &::ParamSpec) + Send + Sync + 'static>(
         &self,
         name: Option<&str>,
@@ -1791,10 +2227,9 @@ impl<T: ObjectType> ObjectExt for T {
         let property_name = property_name.into();
 
         unsafe {
-            gobject_sys::g_object_notify(
-                self.as_object_ref().to_glib_none().0,
-                property_name.to_glib_none().0,
-            );
+            with_c_str(property_name, |property_name| {
+                gobject_sys::g_object_notify(self.as_object_ref().to_glib_none().0, property_name)
+            });
         }
     }
 
@@ -1855,6 +2290,30 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn connect_after<'a, N, F>(
+        &self,
+        signal_name: N,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        N: Into<&'a str>,
+        F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static,
+    {
+        self.connect(signal_name, true, callback)
+    }
+
+    fn connect_local_after<'a, N, F>(
+        &self,
+        signal_name: N,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        N: Into<&'a str>,
+        F: Fn(&[Value]) -> Option<Value> + 'static,
+    {
+        self.connect_local(signal_name, true, callback)
+    }
+
     unsafe fn connect_unsafe<'a, N, F>(
         &self,
         signal_name: N,
@@ -1866,42 +2325,96 @@ impl<T: ObjectType> ObjectExt for T {
         F: Fn(&[Value]) -> Option<Value>,
     {
         let signal_name: &str = signal_name.into();
-
         let type_ = self.get_type();
 
-        let mut signal_id = 0;
-        let mut signal_detail = 0;
+        let (signal_id, details) = match SignalId::parse_name(signal_name, type_, true) {
+            Some(res) => res,
+            None => {
+                return Err(glib_bool_error!(
+                    "Signal '{}' of type '{}' not found",
+                    signal_name,
+                    type_
+                ));
+            }
+        };
 
-        let found: bool = from_glib(gobject_sys::g_signal_parse_name(
-            signal_name.to_glib_none().0,
-            type_.to_glib(),
-            &mut signal_id,
-            &mut signal_detail,
-            true.to_glib(),
-        ));
+        self.connect_unsafe_detailed(signal_id, details, after, callback)
+    }
+
+    fn connect_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static,
+    {
+        unsafe { self.connect_unsafe_detailed(signal_id, details, after, callback) }
+    }
+
+    fn connect_local_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value> + 'static,
+    {
+        let callback = crate::ThreadGuard::new(callback);
+
+        unsafe {
+            self.connect_unsafe_detailed(signal_id, details, after, move |values| {
+                (callback.get_ref())(values)
+            })
+        }
+    }
+
+    unsafe fn connect_unsafe_detailed<F>(
+        &self,
+        signal_id: SignalId,
+        details: Option<Quark>,
+        after: bool,
+        callback: F,
+    ) -> Result<SignalHandlerId, BoolError>
+    where
+        F: Fn(&[Value]) -> Option<Value>,
+    {
+        let type_ = self.get_type();
 
-        if !found {
+        let mut signal_query = mem::MaybeUninit::zeroed();
+        gobject_sys::g_signal_query(signal_id.to_glib(), signal_query.as_mut_ptr());
+        let signal_query = signal_query.assume_init();
+        if signal_query.signal_id != signal_id.to_glib() {
             return Err(glib_bool_error!(
-                "Signal '{}' of type '{}' not found",
-                signal_name,
+                "Signal id {} of type '{}' not found",
+                signal_id.to_glib(),
                 type_
             ));
         }
 
-        let mut details = mem::MaybeUninit::zeroed();
-        gobject_sys::g_signal_query(signal_id, details.as_mut_ptr());
-        let details = details.assume_init();
-        if details.signal_id != signal_id {
+        let signal_name = signal_id.name();
+        if !type_.is_a(&from_glib(signal_query.itype)) {
             return Err(glib_bool_error!(
                 "Signal '{}' of type '{}' not found",
                 signal_name,
                 type_
             ));
         }
+        if details.is_some() && signal_query.signal_flags & gobject_sys::G_SIGNAL_DETAILED == 0 {
+            return Err(glib_bool_error!(
+                "Signal '{}' of type '{}' does not support details",
+                signal_name,
+                type_
+            ));
+        }
 
         // This is actually G_SIGNAL_TYPE_STATIC_SCOPE
         let return_type: Type =
-            from_glib(details.return_type & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT));
+            from_glib(signal_query.return_type & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT));
         let closure = Closure::new_unsafe(move |values| {
             let ret = callback(values);
 
@@ -1973,8 +2486,8 @@ impl<T: ObjectType> ObjectExt for T {
         });
         let handler = gobject_sys::g_signal_connect_closure_by_id(
             self.as_object_ref().to_glib_none().0,
-            signal_id,
-            signal_detail,
+            signal_id.to_glib(),
+            details.map_or(0, |q| q.to_glib()),
             closure.to_glib_none().0,
             after.to_glib(),
         );
@@ -1997,17 +2510,7 @@ impl<T: ObjectType> ObjectExt for T {
     ) -> Result<Option<Value>, BoolError> {
         let signal_name: &str = signal_name.into();
         unsafe {
-            let type_ = self.get_type();
-
-            let self_v = {
-                let mut v = Value::uninitialized();
-                gobject_sys::g_value_init(v.to_glib_none_mut().0, self.get_type().to_glib());
-                gobject_sys::g_value_set_object(
-                    v.to_glib_none_mut().0,
-                    self.as_object_ref().to_glib_none().0,
-                );
-                v
-            };
+            let self_v = instance_value(self);
 
             let mut args = Iterator::chain(
                 std::iter::once(self_v),
@@ -2015,26 +2518,7 @@ impl<T: ObjectType> ObjectExt for T {
             )
             .collect::<smallvec::SmallVec<[_; 10]>>();
 
-            let (signal_id, signal_detail, return_type) =
-                validate_signal_arguments(type_, signal_name, &mut args[1..])?;
-
-            let mut return_value = Value::uninitialized();
-            if return_type != Type::Unit {
-                gobject_sys::g_value_init(return_value.to_glib_none_mut().0, return_type.to_glib());
-            }
-
-            gobject_sys::g_signal_emitv(
-                mut_override(args.as_ptr()) as *mut gobject_sys::GValue,
-                signal_id,
-                signal_detail,
-                return_value.to_glib_none_mut().0,
-            );
-
-            if return_value.type_() != Type::Unit && return_value.type_() != Type::Invalid {
-                Ok(Some(return_value))
-            } else {
-                Ok(None)
-            }
+            emit_values(self.get_type(), signal_name, &mut args)
         }
     }
 
@@ -2045,44 +2529,29 @@ impl<T: ObjectType> ObjectExt for T {
     ) -> Result<Option<Value>, BoolError> {
         let signal_name: &str = signal_name.into();
         unsafe {
-            let type_ = self.get_type();
-
-            let self_v = {
-                let mut v = Value::uninitialized();
-                gobject_sys::g_value_init(v.to_glib_none_mut().0, self.get_type().to_glib());
-                gobject_sys::g_value_set_object(
-                    v.to_glib_none_mut().0,
-                    self.as_object_ref().to_glib_none().0,
-                );
-                v
-            };
+            let self_v = instance_value(self);
 
-            let mut args = Iterator::chain(std::iter::once(self_v), args.iter().cloned())
+            // The arguments are only borrowed for the emission, so instead of cloning them we
+            // pass shallow copies that must not be unset when they go out of scope. Only their
+            // type is possibly changed during validation, which doesn't affect the originals.
+            let mut args = Iterator::chain(std::iter::once(&self_v), args.iter())
+                .map(|arg| mem::ManuallyDrop::new(ptr::read(arg)))
                 .collect::<smallvec::SmallVec<[_; 10]>>();
 
-            let (signal_id, signal_detail, return_type) =
-                validate_signal_arguments(type_, signal_name, &mut args[1..])?;
-
-            let mut return_value = Value::uninitialized();
-            if return_type != Type::Unit {
-                gobject_sys::g_value_init(return_value.to_glib_none_mut().0, return_type.to_glib());
-            }
-
-            gobject_sys::g_signal_emitv(
-                mut_override(args.as_ptr()) as *mut gobject_sys::GValue,
-                signal_id,
-                signal_detail,
-                return_value.to_glib_none_mut().0,
-            );
-
-            if return_value.type_() != Type::Unit && return_value.type_() != Type::Invalid {
-                Ok(Some(return_value))
-            } else {
-                Ok(None)
-            }
+            emit_values(
+                self.get_type(),
+                signal_name,
+                &mut *(&mut args[..] as *mut [mem::ManuallyDrop<Value>] as *mut [Value]),
+            )
         }
     }
 
+    unsafe fn downgrade_ptr(&self) -> WeakPtr<T> {
+        let mut w = WeakPtr::new();
+        w.set(Some(self));
+        w
+    }
+
     fn downgrade(&self) -> WeakRef<T> {
         unsafe {
             let w = WeakRef(Box::pin(mem::zeroed()), PhantomData);
@@ -2114,6 +2583,19 @@ impl<T: ObjectType> ObjectExt for T {
     }
 }
 
+// Validate that the given property is defined on the given type or one of its ancestors
+fn validate_property_owner(type_: Type, pspec: &::ParamSpec) -> Result<(), BoolError> {
+    if type_.is_a(&pspec.get_owner_type()) {
+        Ok(())
+    } else {
+        Err(glib_bool_error!(
+            "property '{}' of type '{}' not found",
+            pspec.get_name(),
+            type_
+        ))
+    }
+}
+
 // Validate that the given property value has an acceptable type for the given property pspec
 // and if necessary update the value
 fn validate_property_type(
@@ -2196,6 +2678,41 @@ fn validate_property_type(
     Ok(())
 }
 
+unsafe fn instance_value<T: ObjectType>(obj: &T) -> Value {
+    let mut v = Value::uninitialized();
+    gobject_sys::g_value_init(v.to_glib_none_mut().0, obj.get_type().to_glib());
+    gobject_sys::g_value_set_object(v.to_glib_none_mut().0, obj.as_object_ref().to_glib_none().0);
+    v
+}
+
+// `args` contains the instance followed by the signal arguments.
+unsafe fn emit_values(
+    type_: Type,
+    signal_name: &str,
+    args: &mut [Value],
+) -> Result<Option<Value>, BoolError> {
+    let (signal_id, signal_detail, return_type) =
+        validate_signal_arguments(type_, signal_name, &mut args[1..])?;
+
+    let mut return_value = Value::uninitialized();
+    if return_type != Type::Unit {
+        gobject_sys::g_value_init(return_value.to_glib_none_mut().0, return_type.to_glib());
+    }
+
+    gobject_sys::g_signal_emitv(
+        mut_override(args.as_ptr()) as *mut gobject_sys::GValue,
+        signal_id,
+        signal_detail,
+        return_value.to_glib_none_mut().0,
+    );
+
+    if return_value.type_() != Type::Unit && return_value.type_() != Type::Invalid {
+        Ok(Some(return_value))
+    } else {
+        Ok(None)
+    }
+}
+
 fn validate_signal_arguments(
     type_: Type,
     signal_name: &str,
@@ -2319,10 +2836,34 @@ impl ObjectClass {
         unsafe {
             let klass = self as *const _ as *const gobject_sys::GObjectClass;
 
-            from_glib_none(gobject_sys::g_object_class_find_property(
-                klass as *mut _,
-                property_name.to_glib_none().0,
-            ))
+            from_glib_none(with_c_str(property_name, |property_name| {
+                gobject_sys::g_object_class_find_property(klass as *mut _, property_name)
+            }))
+        }
+    }
+
+    /// Returns the properties of the class.
+    ///
+    /// Unlike [`list_properties`](#method.list_properties) this doesn't create a new `Vec`
+    /// every time but caches the properties per type on the first call.
+    ///
+    /// GObject allows installing further properties until the class is derived from, and these
+    /// are not included in the cached list. This must therefore not be called before all
+    /// properties of the class are installed, e.g. from `class_init`.
+    pub fn properties(&self) -> &[::ParamSpec] {
+        let key = ::static_quark!("glib-rs-object-class-properties");
+        let type_ = self.get_type();
+        unsafe {
+            if let Some(pspecs) = type_.get_qdata::<Vec<::ParamSpec>>(key) {
+                return pspecs;
+            }
+
+            // If two threads race here both store the same list, and the one stored first is
+            // leaked but stays valid.
+            type_.set_qdata(key, self.list_properties());
+            type_
+                .get_qdata::<Vec<::ParamSpec>>(key)
+                .expect("property list not cached")
         }
     }
 
@@ -2408,6 +2949,82 @@ impl<T: ObjectType> Default for WeakRef<T> {
 unsafe impl<T: ObjectType + Sync + Sync> Sync for WeakRef<T> {}
 unsafe impl<T: ObjectType + Send + Sync> Send for WeakRef<T> {}
 
+/// A weak pointer to an object, based on `g_object_add_weak_pointer()`.
+///
+/// Unlike [`WeakRef`](struct.WeakRef.html) this doesn't allocate a `GWeakRef` and doesn't
+/// need any locking for upgrading, which makes it considerably cheaper when holding many weak
+/// references. The pointer is cleared when the object is finalized, without any
+/// synchronization with other threads, so this must only be used for objects that are only
+/// ever unreffed on the current thread. For that reason `WeakPtr` is neither `Send` nor `Sync`,
+/// and pointing it to an object is `unsafe`.
+#[derive(Debug)]
+pub struct WeakPtr<T: ObjectType>(Box<glib_sys::gpointer>, PhantomData<*mut T>);
+
+impl<T: ObjectType> WeakPtr<T> {
+    pub fn new() -> WeakPtr<T> {
+        WeakPtr(Box::new(ptr::null_mut()), PhantomData)
+    }
+
+    /// Makes the weak pointer point to `obj`, or to no object.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must only ever be unreffed on the current thread for as long as the `WeakPtr` or
+    /// any of its clones point to it.
+    pub unsafe fn set(&mut self, obj: Option<&T>) {
+        let location: *mut glib_sys::gpointer = &mut *self.0;
+        if !(*location).is_null() {
+            gobject_sys::g_object_remove_weak_pointer(*location as *mut _, location);
+        }
+
+        *location = match obj {
+            Some(obj) => {
+                let ptr = obj.as_object_ref().to_glib_none().0;
+                gobject_sys::g_object_add_weak_pointer(ptr, location);
+                ptr as glib_sys::gpointer
+            }
+            None => ptr::null_mut(),
+        };
+    }
+
+    pub fn upgrade(&self) -> Option<T> {
+        unsafe {
+            let ptr = *self.0 as *mut gobject_sys::GObject;
+            if ptr.is_null() {
+                None
+            } else {
+                let obj: Object = from_glib_none(ptr);
+                Some(T::unsafe_from(obj.into()))
+            }
+        }
+    }
+}
+
+impl<T: ObjectType> Drop for WeakPtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.set(None);
+        }
+    }
+}
+
+impl<T: ObjectType> Clone for WeakPtr<T> {
+    fn clone(&self) -> Self {
+        let mut c = WeakPtr::new();
+        // The object was already allowed to be pointed to by `self`
+        unsafe {
+            c.set(self.upgrade().as_ref());
+        }
+        c
+    }
+}
+
+impl<T: ObjectType> Default for WeakPtr<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A weak reference to the object it was created for that can be sent to
 /// different threads even for object types that don't implement `Send`.
 ///
@@ -2465,6 +3082,75 @@ impl<T: ObjectType> From<WeakRef<T>> for SendWeakRef<T> {
 unsafe impl<T: ObjectType> Sync for SendWeakRef<T> {}
 unsafe impl<T: ObjectType> Send for SendWeakRef<T> {}
 
+/// A `Stream` of signal emissions.
+///
+/// See [`ObjectExt::signal_stream`] for details.
+///
+/// [`ObjectExt::signal_stream`]: trait.ObjectExt.html#tymethod.signal_stream
+#[derive(Debug)]
+pub struct SignalStream {
+    object: WeakRef<Object>,
+    handler_id: Option<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<Vec<Value>>,
+}
+
+impl Stream for SignalStream {
+    type Item = Vec<Value>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Vec<Value>>> {
+        self.receiver.poll_next_unpin(ctx)
+    }
+}
+
+impl Drop for SignalStream {
+    fn drop(&mut self) {
+        if let (Some(object), Some(handler_id)) = (self.object.upgrade(), self.handler_id.take()) {
+            object.disconnect(handler_id);
+        }
+    }
+}
+
+/// A `Future` resolving on the next notification of a property.
+///
+/// See [`ObjectExt::wait_for_notify`] for details.
+///
+/// [`ObjectExt::wait_for_notify`]: trait.ObjectExt.html#tymethod.wait_for_notify
+#[derive(Debug)]
+pub struct NotifyFuture {
+    object: WeakRef<Object>,
+    handler_id: Option<SignalHandlerId>,
+    receiver: oneshot::Receiver<Value>,
+}
+
+impl NotifyFuture {
+    fn disconnect(&mut self) {
+        if let (Some(object), Some(handler_id)) = (self.object.upgrade(), self.handler_id.take()) {
+            object.disconnect(handler_id);
+        }
+    }
+}
+
+impl Future for NotifyFuture {
+    type Output = Option<Value>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Value>> {
+        let res = match self.receiver.poll_unpin(ctx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(res) => res.ok(),
+        };
+
+        self.disconnect();
+
+        Poll::Ready(res)
+    }
+}
+
+impl Drop for NotifyFuture {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
 #[derive(Debug)]
 pub struct BindingBuilder<'a> {
     source: &'a ObjectRef,
@@ -0,0 +1,683 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Command line option parsing with `GOptionContext`.
+//!
+//! Options can either be added to an [`OptionContext`](struct.OptionContext.html) one by one,
+//! or be defined as the fields of a struct with the
+//! [`OptionEntries`](../derive.OptionEntries.html) derive macro.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::{OptionContext, OptionEntry, OptionValue};
+//!
+//! let mut context = OptionContext::new(Some("FILE…"));
+//! context.add_entry(
+//!     OptionEntry::new("verbose", OptionValue::Flag(false))
+//!         .short_name('v')
+//!         .description("Print more output"),
+//! );
+//! context.add_entry(OptionEntry::new("count", OptionValue::Int(1)).arg_description("N"));
+//!
+//! let rest = context.parse(&["prog", "-v", "file.txt"]).unwrap();
+//! assert_eq!(rest, ["prog", "file.txt"]);
+//! assert_eq!(context.value("verbose"), Some(OptionValue::Flag(true)));
+//! assert_eq!(context.value("count"), Some(OptionValue::Int(1)));
+//! ```
+
+use glib_sys;
+use libc::{c_char, c_int};
+use std::ffi::{CString, OsStr, OsString};
+use std::mem;
+use std::path::PathBuf;
+use std::ptr;
+use translate::*;
+use Error;
+use GString;
+use OptionArg;
+use OptionFlags;
+
+/// The value of an option, which also determines the type of its argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    /// An option without argument, `true` if it was given.
+    Flag(bool),
+    /// A string argument.
+    String(Option<String>),
+    /// An integer argument.
+    Int(i32),
+    /// A filename argument, in the GLib filename encoding.
+    Filename(Option<PathBuf>),
+    /// A string argument that can be given multiple times.
+    StringArray(Vec<String>),
+    /// A filename argument that can be given multiple times.
+    FilenameArray(Vec<PathBuf>),
+    /// A floating point argument.
+    Double(f64),
+    /// A 64 bit integer argument.
+    Int64(i64),
+}
+
+impl OptionValue {
+    /// Returns the argument type of the value.
+    pub fn arg(&self) -> OptionArg {
+        match *self {
+            OptionValue::Flag(_) => OptionArg::None,
+            OptionValue::String(_) => OptionArg::String,
+            OptionValue::Int(_) => OptionArg::Int,
+            OptionValue::Filename(_) => OptionArg::Filename,
+            OptionValue::StringArray(_) => OptionArg::StringArray,
+            OptionValue::FilenameArray(_) => OptionArg::FilenameArray,
+            OptionValue::Double(_) => OptionArg::Double,
+            OptionValue::Int64(_) => OptionArg::Int64,
+        }
+    }
+}
+
+/// Conversion of Rust types from and to [`OptionValue`](enum.OptionValue.html).
+///
+/// This is used by the [`OptionEntries`](../derive.OptionEntries.html) derive macro for the
+/// fields of the struct.
+pub trait OptionType: Sized {
+    /// Converts to the option value that is used as default.
+    fn to_option_value(&self) -> OptionValue;
+
+    /// Converts from the parsed option value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value has a different type than the one returned by
+    /// `to_option_value()`.
+    fn from_option_value(value: OptionValue) -> Self;
+}
+
+macro_rules! impl_option_type {
+    ($name:ty, $variant:ident, $value:ident => $to:expr, $from:expr) => {
+        impl OptionType for $name {
+            fn to_option_value(&self) -> OptionValue {
+                let $value = self;
+                OptionValue::$variant($to)
+            }
+
+            fn from_option_value(value: OptionValue) -> Self {
+                match value {
+                    OptionValue::$variant($value) => $from,
+                    value => panic!("Unexpected option value {:?}", value),
+                }
+            }
+        }
+    };
+}
+
+impl_option_type!(bool, Flag, v => *v, v);
+impl_option_type!(i32, Int, v => *v, v);
+impl_option_type!(i64, Int64, v => *v, v);
+impl_option_type!(f64, Double, v => *v, v);
+impl_option_type!(String, String, v => Some(v.clone()), v.unwrap_or_default());
+impl_option_type!(Option<String>, String, v => v.clone(), v);
+impl_option_type!(PathBuf, Filename, v => Some(v.clone()), v.unwrap_or_default());
+impl_option_type!(Option<PathBuf>, Filename, v => v.clone(), v);
+impl_option_type!(Vec<String>, StringArray, v => v.clone(), v);
+impl_option_type!(Vec<PathBuf>, FilenameArray, v => v.clone(), v);
+
+/// An option that can be added to an [`OptionContext`](struct.OptionContext.html).
+#[derive(Debug, Clone)]
+pub struct OptionEntry {
+    long_name: String,
+    short_name: Option<char>,
+    flags: OptionFlags,
+    value: OptionValue,
+    description: Option<String>,
+    arg_description: Option<String>,
+}
+
+impl OptionEntry {
+    /// Creates a new option `--long_name` with the default value `value`.
+    ///
+    /// The type of the value determines the type of the argument.
+    pub fn new(long_name: &str, value: OptionValue) -> Self {
+        OptionEntry {
+            long_name: long_name.to_owned(),
+            short_name: None,
+            flags: OptionFlags::NONE,
+            value,
+            description: None,
+            arg_description: None,
+        }
+    }
+
+    /// Sets the short name of the option, e.g. `'v'` for `-v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `short_name` is not a printable ASCII character or is `'-'`.
+    pub fn short_name(mut self, short_name: char) -> Self {
+        assert!(
+            short_name.is_ascii_graphic() && short_name != '-',
+            "Invalid short name {:?}",
+            short_name
+        );
+        self.short_name = Some(short_name);
+        self
+    }
+
+    /// Sets the flags of the option.
+    pub fn flags(mut self, flags: OptionFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the description of the option for the `--help` output.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Sets the placeholder for the argument in the `--help` output, e.g. `FILE`.
+    pub fn arg_description(mut self, arg_description: &str) -> Self {
+        self.arg_description = Some(arg_description.to_owned());
+        self
+    }
+}
+
+// The values are stored in their C representation, at a stable address that is passed to
+// GLib as `arg_data`. Strings and arrays that were allocated by GLib during parsing replace
+// the default ones and are owned by us. GLib doesn't free the value it replaces, so a value
+// from a previous parse has to be freed by us once it was replaced.
+enum Storage {
+    Flag(glib_sys::gboolean),
+    Int(i32),
+    Int64(i64),
+    Double(f64),
+    String {
+        default: *mut c_char,
+        value: *mut c_char,
+    },
+    Array {
+        default: *mut *mut c_char,
+        value: *mut *mut c_char,
+    },
+}
+
+impl Storage {
+    fn new(value: &OptionValue) -> Self {
+        let string = |default: *mut c_char| Storage::String {
+            default,
+            value: default,
+        };
+        let array = |default: *mut *mut c_char| Storage::Array {
+            default,
+            value: default,
+        };
+        match *value {
+            OptionValue::Flag(v) => Storage::Flag(v.to_glib()),
+            OptionValue::Int(v) => Storage::Int(v),
+            OptionValue::Int64(v) => Storage::Int64(v),
+            OptionValue::Double(v) => Storage::Double(v),
+            OptionValue::String(ref v) => string(v.to_glib_full()),
+            OptionValue::Filename(ref v) => string(v.to_glib_full()),
+            OptionValue::StringArray(ref v) if v.is_empty() => array(ptr::null_mut()),
+            OptionValue::StringArray(ref v) => array(v[..].to_glib_full()),
+            OptionValue::FilenameArray(ref v) if v.is_empty() => array(ptr::null_mut()),
+            OptionValue::FilenameArray(ref v) => array(v[..].to_glib_full()),
+        }
+    }
+
+    fn arg_data(&mut self) -> glib_sys::gpointer {
+        match *self {
+            Storage::Flag(ref mut v) => v as *mut _ as glib_sys::gpointer,
+            Storage::Int(ref mut v) => v as *mut _ as glib_sys::gpointer,
+            Storage::Int64(ref mut v) => v as *mut _ as glib_sys::gpointer,
+            Storage::Double(ref mut v) => v as *mut _ as glib_sys::gpointer,
+            Storage::String { ref mut value, .. } => value as *mut _ as glib_sys::gpointer,
+            Storage::Array { ref mut value, .. } => value as *mut _ as glib_sys::gpointer,
+        }
+    }
+
+    fn value_ptr(&self) -> glib_sys::gpointer {
+        match *self {
+            Storage::String { value, .. } => value as glib_sys::gpointer,
+            Storage::Array { value, .. } => value as glib_sys::gpointer,
+            _ => ptr::null_mut(),
+        }
+    }
+
+    // Frees `previous`, the value before parsing, if it was allocated by GLib and replaced
+    unsafe fn free_replaced(&mut self, previous: glib_sys::gpointer) {
+        match *self {
+            Storage::String { default, value } => {
+                if previous != value as glib_sys::gpointer
+                    && previous != default as glib_sys::gpointer
+                {
+                    glib_sys::g_free(previous);
+                }
+            }
+            Storage::Array { default, value } => {
+                if previous != value as glib_sys::gpointer
+                    && previous != default as glib_sys::gpointer
+                {
+                    glib_sys::g_strfreev(previous as *mut *mut c_char);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    unsafe fn value(&self, arg: OptionArg) -> OptionValue {
+        match (self, arg) {
+            (&Storage::Flag(v), _) => OptionValue::Flag(from_glib(v)),
+            (&Storage::Int(v), _) => OptionValue::Int(v),
+            (&Storage::Int64(v), _) => OptionValue::Int64(v),
+            (&Storage::Double(v), _) => OptionValue::Double(v),
+            (&Storage::String { value, .. }, OptionArg::Filename) => {
+                OptionValue::Filename(from_glib_none(value as *const c_char))
+            }
+            (&Storage::String { value, .. }, _) => {
+                OptionValue::String(from_glib_none(value as *const c_char))
+            }
+            (&Storage::Array { value, .. }, OptionArg::FilenameArray) => {
+                OptionValue::FilenameArray(if value.is_null() {
+                    Vec::new()
+                } else {
+                    FromGlibPtrContainer::from_glib_none(value)
+                })
+            }
+            (&Storage::Array { value, .. }, _) => OptionValue::StringArray(if value.is_null() {
+                Vec::new()
+            } else {
+                FromGlibPtrContainer::from_glib_none(value)
+            }),
+        }
+    }
+}
+
+impl Drop for Storage {
+    fn drop(&mut self) {
+        unsafe {
+            match *self {
+                Storage::String { default, value } => {
+                    if value != default {
+                        glib_sys::g_free(value as glib_sys::gpointer);
+                    }
+                    glib_sys::g_free(default as glib_sys::gpointer);
+                }
+                Storage::Array { default, value } => {
+                    if value != default {
+                        glib_sys::g_strfreev(value);
+                    }
+                    glib_sys::g_strfreev(default);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+struct Entry {
+    long_name: CString,
+    arg: OptionArg,
+    storage: Box<Storage>,
+    // Only referenced by GLib, which doesn't copy the strings
+    _description: Option<CString>,
+    _arg_description: Option<CString>,
+}
+
+/// A parser for command line options, based on `GOptionContext`.
+///
+/// If help options are enabled (the default), parsing `--help` prints the help output and exits
+/// the process.
+pub struct OptionContext {
+    ptr: ptr::NonNull<glib_sys::GOptionContext>,
+    translation_domain: Option<CString>,
+    entries: Vec<Entry>,
+}
+
+impl OptionContext {
+    /// Creates a new option context.
+    ///
+    /// `parameter_string` is shown in the first line of the `--help` output after the program
+    /// name, e.g. `"FILE…"` for a program that takes a list of files as arguments.
+    pub fn new(parameter_string: Option<&str>) -> Self {
+        unsafe {
+            OptionContext {
+                ptr: ptr::NonNull::new(glib_sys::g_option_context_new(
+                    parameter_string.to_glib_none().0,
+                ))
+                .expect("Failed to create option context"),
+                translation_domain: None,
+                entries: Vec::new(),
+            }
+        }
+    }
+
+    /// Sets the text that is shown in the `--help` output before the list of options.
+    pub fn set_summary(&mut self, summary: Option<&str>) {
+        unsafe {
+            glib_sys::g_option_context_set_summary(self.ptr.as_ptr(), summary.to_glib_none().0);
+        }
+    }
+
+    /// Sets the text that is shown in the `--help` output after the list of options.
+    pub fn set_description(&mut self, description: Option<&str>) {
+        unsafe {
+            glib_sys::g_option_context_set_description(
+                self.ptr.as_ptr(),
+                description.to_glib_none().0,
+            );
+        }
+    }
+
+    /// Sets the gettext domain that is used for translating the `--help` output.
+    pub fn set_translation_domain(&mut self, domain: &str) {
+        let domain = CString::new(domain).expect("Translation domain contains nul bytes");
+        unsafe {
+            glib_sys::g_option_context_set_translation_domain(self.ptr.as_ptr(), domain.as_ptr());
+        }
+        self.translation_domain = Some(domain);
+    }
+
+    /// Enables or disables the automatic `--help` options.
+    pub fn set_help_enabled(&mut self, help_enabled: bool) {
+        unsafe {
+            glib_sys::g_option_context_set_help_enabled(self.ptr.as_ptr(), help_enabled.to_glib());
+        }
+    }
+
+    /// Sets whether unknown options are left in the remaining arguments instead of causing an
+    /// error.
+    pub fn set_ignore_unknown_options(&mut self, ignore_unknown: bool) {
+        unsafe {
+            glib_sys::g_option_context_set_ignore_unknown_options(
+                self.ptr.as_ptr(),
+                ignore_unknown.to_glib(),
+            );
+        }
+    }
+
+    /// Adds an option.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the long name or any description contains nul bytes.
+    pub fn add_entry(&mut self, entry: OptionEntry) {
+        let arg = entry.value.arg();
+        let mut storage = Box::new(Storage::new(&entry.value));
+        let long_name = CString::new(entry.long_name).expect("Long name contains nul bytes");
+        let description = entry
+            .description
+            .map(|d| CString::new(d).expect("Description contains nul bytes"));
+        let arg_description = entry
+            .arg_description
+            .map(|d| CString::new(d).expect("Argument description contains nul bytes"));
+
+        unsafe {
+            let entries = [
+                glib_sys::GOptionEntry {
+                    long_name: long_name.as_ptr(),
+                    short_name: entry.short_name.map_or(0, |c| c as u8 as c_char),
+                    flags: entry.flags.to_glib() as c_int,
+                    arg: arg.to_glib(),
+                    arg_data: storage.arg_data(),
+                    description: description.as_ref().map_or(ptr::null(), |d| d.as_ptr()),
+                    arg_description: arg_description.as_ref().map_or(ptr::null(), |d| d.as_ptr()),
+                },
+                mem::zeroed(),
+            ];
+            glib_sys::g_option_context_add_main_entries(
+                self.ptr.as_ptr(),
+                entries.as_ptr(),
+                self.translation_domain
+                    .as_ref()
+                    .map_or(ptr::null(), |d| d.as_ptr()),
+            );
+        }
+
+        self.entries.push(Entry {
+            long_name,
+            arg,
+            storage,
+            _description: description,
+            _arg_description: arg_description,
+        });
+    }
+
+    /// Adds all options defined by `entries`, with their current values as defaults.
+    pub fn add_entries<T: OptionEntries>(&mut self, entries: &T) {
+        entries.add_entries(self);
+    }
+
+    /// Parses the command line arguments `args`, including the program name.
+    ///
+    /// Returns the remaining arguments that are not options, including the program name.
+    ///
+    /// This can be called multiple times, options that are not given again keep the value of the
+    /// previous successful parse.
+    pub fn parse<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
+        &mut self,
+        args: I,
+    ) -> Result<Vec<OsString>, Error> {
+        unsafe {
+            let args: Vec<*mut c_char> = args
+                .into_iter()
+                .map(|arg| arg.as_ref().to_glib_full())
+                .collect();
+            // GLib only reorders the pointers, so the strings are freed from `args` afterwards
+            let mut argv = args.clone();
+            argv.push(ptr::null_mut());
+            let mut argc: c_int = args.len().into_glib();
+            let mut argv_ptr = argv.as_mut_ptr();
+            let mut error = ptr::null_mut();
+            let previous: Vec<_> = self
+                .entries
+                .iter()
+                .map(|entry| entry.storage.value_ptr())
+                .collect();
+            let _ = glib_sys::g_option_context_parse(
+                self.ptr.as_ptr(),
+                &mut argc,
+                &mut argv_ptr,
+                &mut error,
+            );
+            for (entry, previous) in self.entries.iter_mut().zip(previous) {
+                entry.storage.free_replaced(previous);
+            }
+            let ret = if error.is_null() {
+                Ok((0..argc as usize)
+                    .map(|i| from_glib_none(*argv_ptr.add(i) as *const c_char))
+                    .collect())
+            } else {
+                Err(from_glib_full(error))
+            };
+            for arg in args {
+                glib_sys::g_free(arg as glib_sys::gpointer);
+            }
+            ret
+        }
+    }
+
+    /// Returns the value of the option `long_name`.
+    ///
+    /// This is the default value unless the option was given on the command line. Returns
+    /// `None` if there is no such option.
+    pub fn value(&self, long_name: &str) -> Option<OptionValue> {
+        self.entries
+            .iter()
+            .find(|entry| entry.long_name.as_bytes() == long_name.as_bytes())
+            .map(|entry| unsafe { entry.storage.value(entry.arg) })
+    }
+
+    /// Returns the `--help` output.
+    ///
+    /// If `main_help` is `false`, only the usage line is included.
+    pub fn help(&self, main_help: bool) -> GString {
+        unsafe {
+            from_glib_full(glib_sys::g_option_context_get_help(
+                self.ptr.as_ptr(),
+                main_help.to_glib(),
+                ptr::null_mut(),
+            ))
+        }
+    }
+}
+
+impl Drop for OptionContext {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_option_context_free(self.ptr.as_ptr()) }
+    }
+}
+
+/// A set of command line options, usually implemented with the
+/// [`OptionEntries`](../derive.OptionEntries.html) derive macro.
+pub trait OptionEntries {
+    /// Adds an option for each field to `context`, with the current field values as defaults.
+    fn add_entries(&self, context: &mut OptionContext);
+
+    /// Updates the fields from the values parsed by `context`.
+    fn update_from(&mut self, context: &OptionContext);
+
+    /// Parses `args` into the fields and returns the remaining arguments that are not
+    /// options, including the program name.
+    ///
+    /// `parameter_string` is used as described for [`OptionContext::new`].
+    ///
+    /// [`OptionContext::new`]: struct.OptionContext.html#method.new
+    fn parse_args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
+        &mut self,
+        parameter_string: Option<&str>,
+        args: I,
+    ) -> Result<Vec<OsString>, Error>
+    where
+        Self: Sized,
+    {
+        let mut context = OptionContext::new(parameter_string);
+        context.add_entries(self);
+        let rest = context.parse(args)?;
+        self.update_from(&context);
+        Ok(rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let mut context = OptionContext::new(Some("- test program"));
+        context.add_entry(
+            OptionEntry::new("verbose", OptionValue::Flag(false))
+                .short_name('v')
+                .description("Be verbose"),
+        );
+        context.add_entry(OptionEntry::new("count", OptionValue::Int(1)).arg_description("N"));
+        context.add_entry(OptionEntry::new("ratio", OptionValue::Double(0.5)));
+        context.add_entry(OptionEntry::new(
+            "name",
+            OptionValue::String(Some("default".into())),
+        ));
+        context.add_entry(OptionEntry::new("output", OptionValue::Filename(None)));
+        context.add_entry(OptionEntry::new(
+            "include",
+            OptionValue::StringArray(vec![]),
+        ));
+
+        let rest = context
+            .parse(&[
+                "prog",
+                "-v",
+                "--count=3",
+                "file",
+                "--name",
+                "foo",
+                "--include",
+                "a",
+                "--include=b",
+            ])
+            .unwrap();
+        assert_eq!(rest, ["prog", "file"]);
+        assert_eq!(context.value("verbose"), Some(OptionValue::Flag(true)));
+        assert_eq!(context.value("count"), Some(OptionValue::Int(3)));
+        assert_eq!(context.value("ratio"), Some(OptionValue::Double(0.5)));
+        assert_eq!(
+            context.value("name"),
+            Some(OptionValue::String(Some("foo".into())))
+        );
+        assert_eq!(context.value("output"), Some(OptionValue::Filename(None)));
+        assert_eq!(
+            context.value("include"),
+            Some(OptionValue::StringArray(vec!["a".into(), "b".into()]))
+        );
+        assert_eq!(context.value("unknown"), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut context = OptionContext::new(None);
+        context.add_entry(OptionEntry::new("count", OptionValue::Int(1)));
+        assert!(context.parse(&["prog", "--count=abc"]).is_err());
+        assert!(context.parse(&["prog", "--unknown"]).is_err());
+
+        context.set_ignore_unknown_options(true);
+        assert_eq!(
+            context.parse(&["prog", "--unknown"]).unwrap(),
+            ["prog", "--unknown"]
+        );
+    }
+
+    #[test]
+    fn parse_twice() {
+        let mut context = OptionContext::new(None);
+        context.add_entry(OptionEntry::new(
+            "name",
+            OptionValue::String(Some("default".into())),
+        ));
+        context.add_entry(OptionEntry::new(
+            "include",
+            OptionValue::StringArray(vec!["default".into()]),
+        ));
+
+        context
+            .parse(&["prog", "--name=foo", "--include=a"])
+            .unwrap();
+        context.parse(&["prog", "--name=bar"]).unwrap();
+        assert_eq!(
+            context.value("name"),
+            Some(OptionValue::String(Some("bar".into())))
+        );
+        assert_eq!(
+            context.value("include"),
+            Some(OptionValue::StringArray(vec!["a".into()]))
+        );
+
+        context.parse(&["prog", "--include=b"]).unwrap();
+        assert!(context
+            .parse(&["prog", "--include=c", "--unknown"])
+            .is_err());
+        assert_eq!(
+            context.value("name"),
+            Some(OptionValue::String(Some("bar".into())))
+        );
+        assert_eq!(
+            context.value("include"),
+            Some(OptionValue::StringArray(vec!["b".into()]))
+        );
+    }
+
+    #[test]
+    fn help() {
+        let mut context = OptionContext::new(Some("FILE"));
+        context.set_summary(Some("Summary text"));
+        context.add_entry(
+            OptionEntry::new("verbose", OptionValue::Flag(false))
+                .short_name('v')
+                .description("Be verbose"),
+        );
+        let help = context.help(true);
+        assert!(help.contains("FILE"));
+        assert!(help.contains("Summary text"));
+        assert!(help.contains("-v, --verbose"));
+        assert!(help.contains("Be verbose"));
+    }
+}
@@ -57,6 +57,10 @@ unsafe impl Send for ParamSpec {}
 unsafe impl Sync for ParamSpec {}
 
 impl ParamSpec {
+    pub fn is<T: ParamSpecType>(&self) -> bool {
+        self.get_type() == T::static_type()
+    }
+
     pub fn downcast<T: ParamSpecType>(self) -> Result<T, ParamSpec> {
         unsafe {
             if self.get_type() == T::static_type() {
@@ -99,9 +103,12 @@ impl ParamSpec {
 
     pub fn get_blurb(&self) -> &str {
         unsafe {
-            CStr::from_ptr(gobject_sys::g_param_spec_get_blurb(self.to_glib_none().0))
-                .to_str()
-                .unwrap()
+            let blurb = gobject_sys::g_param_spec_get_blurb(self.to_glib_none().0);
+            if blurb.is_null() {
+                ""
+            } else {
+                CStr::from_ptr(blurb).to_str().unwrap()
+            }
         }
     }
 
@@ -881,6 +888,16 @@ define_param_spec_numeric!(
     |x| x
 );
 
+impl ParamSpecFloat {
+    pub fn get_epsilon(&self) -> f32 {
+        unsafe {
+            let ptr = self.to_glib_none().0;
+
+            (*ptr).epsilon
+        }
+    }
+}
+
 define_param_spec_numeric!(
     ParamSpecDouble,
     gobject_sys::GParamSpecDouble,
@@ -890,6 +907,16 @@ define_param_spec_numeric!(
     |x| x
 );
 
+impl ParamSpecDouble {
+    pub fn get_epsilon(&self) -> f64 {
+        unsafe {
+            let ptr = self.to_glib_none().0;
+
+            (*ptr).epsilon
+        }
+    }
+}
+
 define_param_spec!(
     ParamSpecString,
     gobject_sys::GParamSpecString,
@@ -984,6 +1011,16 @@ define_param_spec!(
     21
 );
 
+impl ParamSpecGType {
+    pub fn get_is_a_type(&self) -> Type {
+        unsafe {
+            let ptr = self.to_glib_none().0;
+
+            from_glib((*ptr).is_a_type)
+        }
+    }
+}
+
 define_param_spec!(
     ParamSpecVariant,
     gobject_sys::GParamSpecVariant,
@@ -1011,9 +1048,416 @@ impl ParamSpecVariant {
     }
 }
 
+macro_rules! define_builder {
+    (
+        $(#[$attr:meta])*
+        $rust_type:ident, $builder:ident, $ffi_fn:ident,
+        ($($req:ident: $req_ty:ty),*),
+        {$($(#[$opt_attr:meta])* $opt:ident: $opt_ty:ty = $opt_default:expr),* $(,)?},
+        |$this:ident| ($($arg:expr),*)
+    ) => {
+        $(#[$attr])*
+        ///
+        /// The property is readable and writable by default.
+        #[must_use]
+        pub struct $builder<'a> {
+            name: &'a str,
+            nick: Option<&'a str>,
+            blurb: Option<&'a str>,
+            flags: ParamFlags,
+            $($req: $req_ty,)*
+            $($opt: $opt_ty,)*
+        }
+
+        impl $rust_type {
+            /// Creates a builder for a param spec called `name`.
+            pub fn builder<'a>(name: &'a str, $($req: $req_ty),*) -> $builder<'a> {
+                $builder {
+                    name,
+                    nick: None,
+                    blurb: None,
+                    flags: ParamFlags::READWRITE,
+                    $($opt: $opt_default,)*
+                    $($req,)*
+                }
+            }
+        }
+
+        impl<'a> $builder<'a> {
+            /// Sets the human readable name of the property.
+            pub fn nick(mut self, nick: &'a str) -> Self {
+                self.nick = Some(nick);
+                self
+            }
+
+            /// Sets the description of the property.
+            pub fn blurb(mut self, blurb: &'a str) -> Self {
+                self.blurb = Some(blurb);
+                self
+            }
+
+            /// Replaces all flags of the property.
+            pub fn flags(mut self, flags: ParamFlags) -> Self {
+                self.flags = flags;
+                self
+            }
+
+            /// Makes the property readable and writable.
+            pub fn readwrite(mut self) -> Self {
+                self.flags |= ParamFlags::READWRITE;
+                self
+            }
+
+            /// Makes the property readable but not writable.
+            pub fn read_only(mut self) -> Self {
+                self.flags.remove(ParamFlags::WRITABLE);
+                self.flags |= ParamFlags::READABLE;
+                self
+            }
+
+            /// Makes the property writable but not readable.
+            pub fn write_only(mut self) -> Self {
+                self.flags.remove(ParamFlags::READABLE);
+                self.flags |= ParamFlags::WRITABLE;
+                self
+            }
+
+            /// Sets the property during construction.
+            pub fn construct(mut self) -> Self {
+                self.flags |= ParamFlags::CONSTRUCT;
+                self
+            }
+
+            /// Only allows setting the property during construction.
+            pub fn construct_only(mut self) -> Self {
+                self.flags |= ParamFlags::CONSTRUCT_ONLY;
+                self
+            }
+
+            /// Only emits `notify` when explicitly requested instead of on every set.
+            pub fn explicit_notify(mut self) -> Self {
+                self.flags |= ParamFlags::EXPLICIT_NOTIFY;
+                self
+            }
+
+            /// Marks the property as deprecated.
+            pub fn deprecated(mut self) -> Self {
+                self.flags |= ParamFlags::DEPRECATED;
+                self
+            }
+
+            $(
+                $(#[$opt_attr])*
+                pub fn $opt(mut self, $opt: $opt_ty) -> Self {
+                    self.$opt = $opt;
+                    self
+                }
+            )*
+
+            /// Creates the param spec, e.g. for returning it from the function of a
+            /// `subclass::Property`.
+            pub fn build(self) -> ParamSpec {
+                let $this = &self;
+                unsafe {
+                    from_glib_none(gobject_sys::$ffi_fn(
+                        $this.name.to_glib_none().0,
+                        $this.nick.to_glib_none().0,
+                        $this.blurb.to_glib_none().0,
+                        $($arg,)*
+                        $this.flags.to_glib(),
+                    ))
+                }
+            }
+        }
+    };
+}
+
+macro_rules! define_builder_numeric {
+    ($(#[$attr:meta])* $rust_type:ident, $builder:ident, $ffi_fn:ident, $value_type:ty, $min:expr, $max:expr, $default:expr) => {
+        define_builder!(
+            $(#[$attr])*
+            $rust_type,
+            $builder,
+            $ffi_fn,
+            (),
+            {
+                /// Sets the minimum value, the smallest representable value by default.
+                minimum: $value_type = $min,
+                /// Sets the maximum value, the largest representable value by default.
+                maximum: $value_type = $max,
+                /// Sets the default value, zero by default.
+                default_value: $value_type = $default,
+            },
+            |this| (this.minimum, this.maximum, this.default_value)
+        );
+    };
+}
+
+define_builder!(
+    /// A builder for [`ParamSpecBoolean`](struct.ParamSpecBoolean.html).
+    ParamSpecBoolean,
+    ParamSpecBooleanBuilder,
+    g_param_spec_boolean,
+    (),
+    {
+        /// Sets the default value, `false` by default.
+        default_value: bool = false,
+    },
+    |this| (this.default_value.to_glib())
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecChar`](struct.ParamSpecChar.html).
+    ParamSpecChar,
+    ParamSpecCharBuilder,
+    g_param_spec_char,
+    i8,
+    ::std::i8::MIN,
+    ::std::i8::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecUChar`](struct.ParamSpecUChar.html).
+    ParamSpecUChar,
+    ParamSpecUCharBuilder,
+    g_param_spec_uchar,
+    u8,
+    ::std::u8::MIN,
+    ::std::u8::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecInt`](struct.ParamSpecInt.html).
+    ParamSpecInt,
+    ParamSpecIntBuilder,
+    g_param_spec_int,
+    i32,
+    ::std::i32::MIN,
+    ::std::i32::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecUInt`](struct.ParamSpecUInt.html).
+    ParamSpecUInt,
+    ParamSpecUIntBuilder,
+    g_param_spec_uint,
+    u32,
+    ::std::u32::MIN,
+    ::std::u32::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecLong`](struct.ParamSpecLong.html).
+    ParamSpecLong,
+    ParamSpecLongBuilder,
+    g_param_spec_long,
+    libc::c_long,
+    libc::c_long::min_value(),
+    libc::c_long::max_value(),
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecULong`](struct.ParamSpecULong.html).
+    ParamSpecULong,
+    ParamSpecULongBuilder,
+    g_param_spec_ulong,
+    libc::c_ulong,
+    libc::c_ulong::min_value(),
+    libc::c_ulong::max_value(),
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecInt64`](struct.ParamSpecInt64.html).
+    ParamSpecInt64,
+    ParamSpecInt64Builder,
+    g_param_spec_int64,
+    i64,
+    ::std::i64::MIN,
+    ::std::i64::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecUInt64`](struct.ParamSpecUInt64.html).
+    ParamSpecUInt64,
+    ParamSpecUInt64Builder,
+    g_param_spec_uint64,
+    u64,
+    ::std::u64::MIN,
+    ::std::u64::MAX,
+    0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecFloat`](struct.ParamSpecFloat.html).
+    ParamSpecFloat,
+    ParamSpecFloatBuilder,
+    g_param_spec_float,
+    f32,
+    ::std::f32::MIN,
+    ::std::f32::MAX,
+    0.0
+);
+
+define_builder_numeric!(
+    /// A builder for [`ParamSpecDouble`](struct.ParamSpecDouble.html).
+    ParamSpecDouble,
+    ParamSpecDoubleBuilder,
+    g_param_spec_double,
+    f64,
+    ::std::f64::MIN,
+    ::std::f64::MAX,
+    0.0
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecUnichar`](struct.ParamSpecUnichar.html).
+    ParamSpecUnichar,
+    ParamSpecUnicharBuilder,
+    g_param_spec_unichar,
+    (),
+    {
+        /// Sets the default value, `'\0'` by default.
+        default_value: char = '\0',
+    },
+    |this| (this.default_value.to_glib())
+);
+
+fn first_enum_value(enum_type: Type) -> i32 {
+    ::EnumClass::new(enum_type)
+        .and_then(|class| class.get_values().first().map(|value| value.get_value()))
+        .unwrap_or(0)
+}
+
+define_builder!(
+    /// A builder for [`ParamSpecEnum`](struct.ParamSpecEnum.html).
+    ParamSpecEnum,
+    ParamSpecEnumBuilder,
+    g_param_spec_enum,
+    (enum_type: Type),
+    {
+        /// Sets the default value, the first value of the enum by default.
+        default_value: i32 = first_enum_value(enum_type),
+    },
+    |this| (this.enum_type.to_glib(), this.default_value)
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecFlags`](struct.ParamSpecFlags.html).
+    ParamSpecFlags,
+    ParamSpecFlagsBuilder,
+    g_param_spec_flags,
+    (flags_type: Type),
+    {
+        /// Sets the default value, no flags by default.
+        default_value: u32 = 0,
+    },
+    |this| (this.flags_type.to_glib(), this.default_value)
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecString`](struct.ParamSpecString.html).
+    ParamSpecString,
+    ParamSpecStringBuilder,
+    g_param_spec_string,
+    (),
+    {
+        /// Sets the default value, `None` by default.
+        default_value: Option<&'a str> = None,
+    },
+    |this| (this.default_value.to_glib_none().0)
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecParam`](struct.ParamSpecParam.html).
+    ParamSpecParam,
+    ParamSpecParamBuilder,
+    g_param_spec_param,
+    (param_type: Type),
+    {},
+    |this| (this.param_type.to_glib())
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecBoxed`](struct.ParamSpecBoxed.html).
+    ParamSpecBoxed,
+    ParamSpecBoxedBuilder,
+    g_param_spec_boxed,
+    (boxed_type: Type),
+    {},
+    |this| (this.boxed_type.to_glib())
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecPointer`](struct.ParamSpecPointer.html).
+    ParamSpecPointer,
+    ParamSpecPointerBuilder,
+    g_param_spec_pointer,
+    (),
+    {},
+    |this| ()
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecValueArray`](struct.ParamSpecValueArray.html).
+    ParamSpecValueArray,
+    ParamSpecValueArrayBuilder,
+    g_param_spec_value_array,
+    (),
+    {
+        /// Sets the param spec of the elements, `None` by default.
+        element_spec: Option<&'a ParamSpec> = None,
+    },
+    |this| (this.element_spec.to_glib_none().0)
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecObject`](struct.ParamSpecObject.html).
+    ParamSpecObject,
+    ParamSpecObjectBuilder,
+    g_param_spec_object,
+    (object_type: Type),
+    {},
+    |this| (this.object_type.to_glib())
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecGType`](struct.ParamSpecGType.html).
+    ParamSpecGType,
+    ParamSpecGTypeBuilder,
+    g_param_spec_gtype,
+    (),
+    {
+        /// Sets the type the values have to be derived from, `Type::Unit` for any type by
+        /// default.
+        is_a_type: Type = Type::Unit,
+    },
+    |this| (this.is_a_type.to_glib())
+);
+
+define_builder!(
+    /// A builder for [`ParamSpecVariant`](struct.ParamSpecVariant.html).
+    ParamSpecVariant,
+    ParamSpecVariantBuilder,
+    g_param_spec_variant,
+    (type_: &'a ::VariantTy),
+    {
+        /// Sets the default value, `None` by default.
+        default_value: Option<&'a ::Variant> = None,
+    },
+    |this| (this.type_.to_glib_none().0, this.default_value.to_glib_none().0)
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ToVariant;
 
     #[test]
     fn test_param_spec_string() {
@@ -1044,4 +1488,174 @@ mod tests {
             .expect("Not a string param spec");
         assert_eq!(pspec.get_default_value(), Some("default"));
     }
+
+    #[test]
+    fn test_param_spec_numeric() {
+        let pspec = ParamSpec::double(
+            "double",
+            "nick",
+            "blurb",
+            -1.0,
+            1.0,
+            0.5,
+            ParamFlags::READWRITE,
+        );
+        assert!(pspec.is::<ParamSpecDouble>());
+        assert!(!pspec.is::<ParamSpecFloat>());
+
+        let pspec = pspec
+            .downcast::<ParamSpecDouble>()
+            .expect("Not a double param spec");
+        assert_eq!(pspec.get_minimum(), -1.0);
+        assert_eq!(pspec.get_maximum(), 1.0);
+        assert_eq!(pspec.get_default_value(), 0.5);
+        assert!(pspec.get_epsilon() > 0.0);
+
+        let pspec = ParamSpec::uint("uint", "nick", "blurb", 1, 10, 5, ParamFlags::READWRITE);
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecUInt>()
+            .expect("Not a uint param spec");
+        assert_eq!(pspec_ref.get_minimum(), 1);
+        assert_eq!(pspec_ref.get_maximum(), 10);
+        assert_eq!(pspec_ref.get_default_value(), 5);
+        assert!(pspec.downcast_ref::<ParamSpecInt>().is_none());
+    }
+
+    #[test]
+    fn test_param_spec_builder() {
+        let pspec = ParamSpecInt::builder("count")
+            .minimum(0)
+            .default_value(1)
+            .read_only()
+            .build();
+        assert_eq!(pspec.get_name(), "count");
+        assert_eq!(pspec.get_nick(), "count");
+        assert_eq!(pspec.get_blurb(), "");
+        assert_eq!(pspec.get_flags(), ParamFlags::READABLE);
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecInt>()
+            .expect("Not an int param spec");
+        assert_eq!(pspec_ref.get_minimum(), 0);
+        assert_eq!(pspec_ref.get_maximum(), ::std::i32::MAX);
+        assert_eq!(pspec_ref.get_default_value(), 1);
+
+        let pspec = ParamSpecString::builder("name")
+            .nick("Name")
+            .blurb("Name of this object")
+            .default_value(Some("default"))
+            .construct_only()
+            .build();
+        assert_eq!(pspec.get_nick(), "Name");
+        assert_eq!(pspec.get_blurb(), "Name of this object");
+        assert_eq!(
+            pspec.get_flags(),
+            ParamFlags::READWRITE | ParamFlags::CONSTRUCT_ONLY
+        );
+        assert_eq!(
+            pspec
+                .downcast_ref::<ParamSpecString>()
+                .unwrap()
+                .get_default_value(),
+            Some("default")
+        );
+
+        let pspec = ParamSpecBoolean::builder("constructed")
+            .nick("Constructed")
+            .blurb("True if the constructed() virtual method was called")
+            .read_only()
+            .build();
+        assert_eq!(pspec.get_flags(), ParamFlags::READABLE);
+        assert_eq!(pspec.get_value_type(), Type::Bool);
+        assert!(!pspec
+            .downcast_ref::<ParamSpecBoolean>()
+            .unwrap()
+            .get_default_value());
+
+        let pspec = ParamSpecFlags::builder("flags", ::BindingFlags::static_type())
+            .default_value(::BindingFlags::SYNC_CREATE.bits())
+            .build();
+        assert_eq!(
+            pspec
+                .downcast_ref::<ParamSpecFlags>()
+                .unwrap()
+                .get_default_value(),
+            ::BindingFlags::SYNC_CREATE.bits()
+        );
+
+        let pspec = ParamSpecGType::builder("type").build();
+        assert_eq!(
+            pspec
+                .downcast_ref::<ParamSpecGType>()
+                .unwrap()
+                .get_is_a_type(),
+            Type::Unit
+        );
+    }
+
+    #[test]
+    fn test_param_spec_kinds() {
+        let pspec = ParamSpec::pointer("pointer", "nick", "blurb", ParamFlags::READWRITE);
+        assert!(pspec.is::<ParamSpecPointer>());
+        assert_eq!(pspec.get_value_type(), Type::Pointer);
+
+        let pspec = ParamSpec::gtype(
+            "gtype",
+            "nick",
+            "blurb",
+            Type::BaseObject,
+            ParamFlags::READWRITE,
+        );
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecGType>()
+            .expect("Not a gtype param spec");
+        assert_eq!(pspec_ref.get_is_a_type(), Type::BaseObject);
+
+        let pspec = ParamSpec::unichar("unichar", "nick", "blurb", 'ä', ParamFlags::READWRITE);
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecUnichar>()
+            .expect("Not a unichar param spec");
+        assert_eq!(pspec_ref.get_default_value(), 'ä');
+
+        let element_spec =
+            ParamSpec::int("element", "nick", "blurb", 0, 10, 0, ParamFlags::READWRITE);
+        let pspec = ParamSpec::value_array(
+            "value-array",
+            "nick",
+            "blurb",
+            &element_spec,
+            ParamFlags::READWRITE,
+        );
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecValueArray>()
+            .expect("Not a value array param spec");
+        assert_eq!(pspec_ref.get_element_spec(), Some(element_spec));
+        assert_eq!(pspec_ref.get_fixed_n_elements(), 0);
+
+        let default_value = 42i32.to_variant();
+        let pspec = ParamSpec::variant(
+            "variant",
+            "nick",
+            "blurb",
+            default_value.type_(),
+            Some(&default_value),
+            ParamFlags::READWRITE,
+        );
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecVariant>()
+            .expect("Not a variant param spec");
+        assert_eq!(pspec_ref.get_type(), Some(default_value.type_()));
+        assert_eq!(pspec_ref.get_default_value(), Some(default_value));
+
+        let pspec = ParamSpec::override_("override", &pspec);
+        let pspec_ref = pspec
+            .downcast_ref::<ParamSpecOverride>()
+            .expect("Not an override param spec");
+        assert_eq!(pspec_ref.get_overridden().get_name(), "variant");
+        assert_eq!(
+            pspec
+                .get_redirect_target()
+                .map(|p| p.is::<ParamSpecVariant>()),
+            Some(true)
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Traits and essential types intended for blanket imports.
 
 pub use {
-    Cast, Continue, IsA, IsClassFor, ObjectExt, ObjectType, ParamSpecType, StaticType,
+    Cast, Continue, GUnicharExt, IsA, IsClassFor, ObjectExt, ObjectType, ParamSpecType, StaticType,
     StaticVariantType, ToSendValue, ToValue, ToVariant,
 };
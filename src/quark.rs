@@ -5,6 +5,7 @@
 use glib_sys;
 use std::ffi::CStr;
 use std::fmt;
+use std::sync::atomic::{self, AtomicU32};
 use translate::*;
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
@@ -13,7 +14,7 @@ pub struct Quark(glib_sys::GQuark);
 
 impl Quark {
     pub fn from_string(s: &str) -> Quark {
-        unsafe { from_glib(glib_sys::g_quark_from_string(s.to_glib_none().0)) }
+        unsafe { from_glib(with_c_str(s, |s| glib_sys::g_quark_from_string(s))) }
     }
 
     #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -25,14 +26,72 @@ impl Quark {
         }
     }
 
-    pub fn try_string(s: &str) -> Option<Quark> {
+    /// Returns the quark for `s` if it was interned before, without interning it.
+    pub fn try_from_string(s: &str) -> Option<Quark> {
         unsafe {
-            match glib_sys::g_quark_try_string(s.to_glib_none().0) {
+            match with_c_str(s, |s| glib_sys::g_quark_try_string(s)) {
                 0 => None,
                 x => Some(from_glib(x)),
             }
         }
     }
+
+    #[deprecated(note = "Use `Quark::try_from_string` instead")]
+    pub fn try_string(s: &str) -> Option<Quark> {
+        Quark::try_from_string(s)
+    }
+}
+
+/// Lazily interned quark for a static string, see [`static_quark!`](macro.static_quark.html).
+#[doc(hidden)]
+pub struct StaticQuark {
+    name: &'static str,
+    quark: AtomicU32,
+}
+
+impl StaticQuark {
+    /// `name` must be nul-terminated.
+    pub const fn new(name: &'static str) -> StaticQuark {
+        StaticQuark {
+            name,
+            quark: AtomicU32::new(0),
+        }
+    }
+
+    pub fn get(&self) -> Quark {
+        let quark = self.quark.load(atomic::Ordering::Relaxed);
+        if quark != 0 {
+            return Quark(quark);
+        }
+
+        assert!(self.name.ends_with('\0'));
+        let quark = unsafe { glib_sys::g_quark_from_static_string(self.name.as_ptr() as *const _) };
+        self.quark.store(quark, atomic::Ordering::Relaxed);
+        Quark(quark)
+    }
+}
+
+/// Returns the [`Quark`](struct.Quark.html) for a string literal.
+///
+/// The quark is interned on first use and then cached in a static, which avoids a hash table
+/// lookup on every call.
+///
+/// ```
+/// #[macro_use]
+/// extern crate glib;
+///
+/// # fn main() {
+/// let quark = static_quark!("my-quark");
+/// assert_eq!(quark, glib::Quark::from_string("my-quark"));
+/// assert_eq!(quark.to_string(), "my-quark");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! static_quark {
+    ($name:expr) => {{
+        static QUARK: $crate::StaticQuark = $crate::StaticQuark::new(concat!($name, "\0"));
+        QUARK.get()
+    }};
 }
 
 impl fmt::Debug for Quark {
@@ -56,3 +115,27 @@ impl ToGlib for Quark {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quark() {
+        assert_eq!(Quark::try_from_string("glib-rs-test-quark-unused"), None);
+        let quark = Quark::from_string("glib-rs-test-quark");
+        assert_eq!(Quark::try_from_string("glib-rs-test-quark"), Some(quark));
+        assert_eq!(quark.to_string(), "glib-rs-test-quark");
+    }
+
+    #[test]
+    fn test_static_quark() {
+        fn get() -> Quark {
+            static_quark!("glib-rs-test-static-quark")
+        }
+
+        let quark = get();
+        assert_eq!(get(), quark);
+        assert_eq!(Quark::from_string("glib-rs-test-static-quark"), quark);
+    }
+}
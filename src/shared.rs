@@ -58,6 +58,12 @@ macro_rules! glib_shared_wrapper {
         #[derive(Clone)]
         pub struct $name($crate::shared::Shared<$ffi_name, $name>);
 
+        $crate::glib_wrapper_raw_ptr_impl!($name, $ffi_name, |this| {
+            let ptr = $crate::translate::ToGlibPtr::<*mut $ffi_name>::to_glib_none(&this).0;
+            ::std::mem::forget(this);
+            ptr
+        });
+
         #[doc(hidden)]
         impl $crate::shared::SharedMemoryManager<$ffi_name> for $name {
             #[inline]
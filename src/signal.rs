@@ -8,9 +8,12 @@ use glib_sys::{gboolean, gpointer};
 use gobject_sys::{self, GCallback};
 use libc::{c_char, c_ulong, c_void};
 use object::ObjectType;
+use std::ffi::CStr;
 use std::mem;
-use std::num::NonZeroU64;
-use translate::{from_glib, FromGlib, ToGlib, ToGlibPtr};
+use std::num::{NonZeroU32, NonZeroU64};
+use translate::{from_glib, with_c_str, FromGlib, IntoGlib, ToGlib, ToGlibPtr};
+use Quark;
+use Type;
 
 /// The id of a signal that is returned by `connect`.
 #[derive(Debug, Eq, PartialEq)]
@@ -21,15 +24,99 @@ impl ToGlib for SignalHandlerId {
 
     #[inline]
     fn to_glib(&self) -> c_ulong {
-        self.0.get() as c_ulong
+        self.0.to_glib().into_glib()
     }
 }
 
 impl FromGlib<c_ulong> for SignalHandlerId {
     #[inline]
     fn from_glib(val: c_ulong) -> SignalHandlerId {
-        assert_ne!(val, 0);
-        SignalHandlerId(unsafe { NonZeroU64::new_unchecked(val as u64) })
+        SignalHandlerId(from_glib(val as u64))
+    }
+}
+
+/// The id of a signal of a type.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SignalId(NonZeroU32);
+
+impl SignalId {
+    /// Looks up the signal `name` of `type_` or any of its ancestors or interfaces.
+    pub fn lookup(name: &str, type_: Type) -> Option<SignalId> {
+        unsafe {
+            match with_c_str(name, |name| {
+                gobject_sys::g_signal_lookup(name, type_.to_glib())
+            }) {
+                0 => None,
+                id => Some(from_glib(id)),
+            }
+        }
+    }
+
+    /// Parses a detailed signal name like `notify::name` of `type_` into the signal id and
+    /// the detail.
+    ///
+    /// If `force_detail_quark` is `true`, the quark for the detail is created if it does not
+    /// exist yet.
+    pub fn parse_name(
+        name: &str,
+        type_: Type,
+        force_detail_quark: bool,
+    ) -> Option<(SignalId, Option<Quark>)> {
+        unsafe {
+            let mut signal_id = 0;
+            let mut detail = 0;
+            let found: bool = from_glib(with_c_str(name, |name| {
+                gobject_sys::g_signal_parse_name(
+                    name,
+                    type_.to_glib(),
+                    &mut signal_id,
+                    &mut detail,
+                    force_detail_quark.to_glib(),
+                )
+            }));
+            if !found {
+                return None;
+            }
+            let detail = if detail == 0 {
+                None
+            } else {
+                Some(from_glib(detail))
+            };
+            Some((from_glib(signal_id), detail))
+        }
+    }
+
+    /// Returns the name of the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal id is not valid.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn name(&self) -> &'static str {
+        unsafe {
+            let name = gobject_sys::g_signal_name(self.to_glib());
+            assert!(!name.is_null(), "Invalid signal id {}", self.to_glib());
+            // Signal names are interned and never freed, and consist of ASCII characters only
+            CStr::from_ptr(name).to_str().unwrap()
+        }
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for SignalId {
+    type GlibType = u32;
+
+    #[inline]
+    fn to_glib(&self) -> u32 {
+        self.0.to_glib()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<u32> for SignalId {
+    #[inline]
+    fn from_glib(val: u32) -> SignalId {
+        SignalId(from_glib(val))
     }
 }
 
@@ -55,6 +142,53 @@ pub unsafe fn connect_raw<F>(
     signal_name: *const c_char,
     trampoline: GCallback,
     closure: *mut F,
+) -> SignalHandlerId {
+    connect_raw_full(receiver, signal_name, trampoline, closure, 0)
+}
+
+/// Connects `closure` to the signal `signal_name` of `instance`, using `trampoline` for
+/// calling it.
+///
+/// This is the common entry point for implementing typed `connect_*` functions. `closure` is
+/// moved into a `Box` that is freed again once the signal handler is disconnected or `instance`
+/// is finalized. If `after` is `true`, the handler is called after the default handler of the
+/// signal instead of before.
+///
+/// This panics if `instance` has no signal called `signal_name`.
+///
+/// # Safety
+///
+/// `trampoline` must be the pointer of an `unsafe extern "C" fn` with the C signature of the
+/// signal, where the last argument is the user data. The user data is a pointer to `F` that is
+/// only valid for the duration of the call.
+pub unsafe fn connect_raw_typed<T: ObjectType, F: 'static>(
+    instance: &T,
+    signal_name: &str,
+    trampoline: GCallback,
+    closure: F,
+    after: bool,
+) -> SignalHandlerId {
+    let flags = if after {
+        gobject_sys::G_CONNECT_AFTER
+    } else {
+        0
+    };
+    let closure = Box::into_raw(Box::new(closure));
+    connect_raw_full(
+        instance.as_object_ref().to_glib_none().0,
+        signal_name.to_glib_none().0,
+        trampoline,
+        closure,
+        flags,
+    )
+}
+
+unsafe fn connect_raw_full<F>(
+    receiver: *mut gobject_sys::GObject,
+    signal_name: *const c_char,
+    trampoline: GCallback,
+    closure: *mut F,
+    flags: gobject_sys::GConnectFlags,
 ) -> SignalHandlerId {
     unsafe extern "C" fn destroy_closure<F>(ptr: *mut c_void, _: *mut gobject_sys::GClosure) {
         // destroy
@@ -68,9 +202,13 @@ pub unsafe fn connect_raw<F>(
         trampoline,
         closure as *mut _,
         Some(destroy_closure::<F>),
-        0,
+        flags,
     );
-    assert!(handle > 0);
+    if handle == 0 {
+        // The closure is not owned by the signal handler
+        Box::<F>::from_raw(closure);
+    }
+    assert!(handle > 0, "Failed to connect signal handler");
     from_glib(handle)
 }
 
@@ -110,3 +248,58 @@ pub fn signal_stop_emission_by_name<T: ObjectType>(instance: &T, signal_name: &s
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use Object;
+    use ObjectExt;
+    use ParamFlags;
+    use ParamSpec;
+    use StaticType;
+
+    #[test]
+    fn test_connect_raw_typed() {
+        unsafe extern "C" fn notify_trampoline<F: Fn(&ParamSpec)>(
+            _this: *mut gobject_sys::GObject,
+            param_spec: *mut gobject_sys::GParamSpec,
+            f: gpointer,
+        ) {
+            let f: &F = &*(f as *const F);
+            f(&::translate::from_glib_borrow(param_spec))
+        }
+
+        fn connect<F: Fn(&ParamSpec) + 'static>(obj: &Object, f: F) -> SignalHandlerId {
+            unsafe {
+                connect_raw_typed(
+                    obj,
+                    "notify",
+                    Some(mem::transmute::<_, unsafe extern "C" fn()>(
+                        notify_trampoline::<F> as *const (),
+                    )),
+                    f,
+                    false,
+                )
+            }
+        }
+
+        let obj = Object::new(Object::static_type(), &[]).unwrap();
+        let pspec = ParamSpec::boolean("foo", "Foo", "Foo", false, ParamFlags::READWRITE);
+
+        let count = Rc::new(Cell::new(0));
+        let count_clone = count.clone();
+        let handler_id = connect(&obj, move |pspec| {
+            assert_eq!(pspec.get_name(), "foo");
+            count_clone.set(count_clone.get() + 1);
+        });
+
+        obj.emit("notify", &[&pspec]).unwrap();
+        assert_eq!(count.get(), 1);
+
+        // The closure is freed again on disconnect
+        signal_handler_disconnect(&obj, handler_id);
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+}
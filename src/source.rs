@@ -6,12 +6,16 @@ use glib_sys::{self, gboolean, gpointer};
 #[cfg(all(not(unix), feature = "dox"))]
 use libc::c_int as RawFd;
 use std::cell::RefCell;
+use std::cmp;
 use std::mem::transmute;
 use std::num::NonZeroU32;
 #[cfg(unix)]
 use std::os::unix::io::RawFd;
+use std::ptr;
 use std::time::Duration;
-use translate::{from_glib, from_glib_full, FromGlib, ToGlib, ToGlibPtr};
+use translate::{
+    from_glib, from_glib_full, DurationIntoGlib, FromGlib, FromGlibPtrNone, ToGlib, ToGlibPtr,
+};
 #[cfg(any(unix, feature = "dox"))]
 use IOCondition;
 
@@ -22,13 +26,36 @@ use Source;
 #[derive(Debug, Eq, PartialEq)]
 pub struct SourceId(NonZeroU32);
 
+impl SourceId {
+    /// Removes the source with this id from the default main context.
+    ///
+    /// Different to `source_remove()`, this returns an error instead of logging a critical
+    /// warning if the source was already removed, e.g. because its callback returned
+    /// `Continue(false)`. As source ids are reused, the id must not be used anymore
+    /// once the source is gone and is consumed here for that reason.
+    pub fn remove(self) -> Result<(), ::BoolError> {
+        unsafe {
+            let source =
+                glib_sys::g_main_context_find_source_by_id(ptr::null_mut(), self.to_glib());
+            if source.is_null() {
+                Err(glib_bool_error!(
+                    "Failed to remove source, it was already removed"
+                ))
+            } else {
+                glib_sys::g_source_destroy(source);
+                Ok(())
+            }
+        }
+    }
+}
+
 #[doc(hidden)]
 impl ToGlib for SourceId {
     type GlibType = u32;
 
     #[inline]
     fn to_glib(&self) -> u32 {
-        self.0.get()
+        self.0.to_glib()
     }
 }
 
@@ -36,8 +63,7 @@ impl ToGlib for SourceId {
 impl FromGlib<u32> for SourceId {
     #[inline]
     fn from_glib(val: u32) -> SourceId {
-        assert_ne!(val, 0);
-        SourceId(unsafe { NonZeroU32::new_unchecked(val) })
+        SourceId(from_glib(val))
     }
 }
 
@@ -48,6 +74,17 @@ pub struct Pid(pub glib_sys::GPid);
 unsafe impl Send for Pid {}
 unsafe impl Sync for Pid {}
 
+impl Pid {
+    /// Closes the process handle.
+    ///
+    /// On Windows the handle of a spawned child must be closed once it isn't needed anymore,
+    /// e.g. in the callback of [`child_watch_add`](fn.child_watch_add.html). On other
+    /// platforms this does nothing.
+    pub fn close(self) {
+        unsafe { glib_sys::g_spawn_close_pid(self.0) }
+    }
+}
+
 #[doc(hidden)]
 impl ToGlib for Pid {
     type GlibType = glib_sys::GPid;
@@ -141,6 +178,17 @@ fn into_raw_unix_fd<F: FnMut(RawFd, IOCondition) -> Continue + 'static>(func: F)
     Box::into_raw(func) as gpointer
 }
 
+fn fnmut_callback_wrapper<F: FnOnce() + 'static>(func: F) -> impl FnMut() -> Continue + 'static {
+    let mut func = Some(func);
+    move || {
+        let func = func
+            .take()
+            .expect("GSource closure called after returning Continue(false)");
+        func();
+        Continue(false)
+    }
+}
+
 /// Adds a closure to be called by the default main loop when it's idle.
 ///
 /// `func` will be called repeatedly until it returns `Continue(false)`.
@@ -188,6 +236,38 @@ where
     }
 }
 
+/// Adds a closure to be called by the default main loop when it's idle.
+///
+/// `func` will be called only once.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+pub fn idle_add_once<F>(func: F) -> SourceId
+where
+    F: FnOnce() + Send + 'static,
+{
+    idle_add(fnmut_callback_wrapper(func))
+}
+
+/// Adds a closure to be called by the default main loop when it's idle.
+///
+/// `func` will be called only once.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+///
+/// Different to `idle_add_once()`, this does not require `func` to be
+/// `Send` but can only be called from the thread that owns the main context.
+///
+/// This function panics if called from a different thread than the one that
+/// owns the main context.
+pub fn idle_add_local_once<F>(func: F) -> SourceId
+where
+    F: FnOnce() + 'static,
+{
+    idle_add_local(fnmut_callback_wrapper(func))
+}
+
 /// Adds a closure to be called by the default main loop at regular intervals
 /// with millisecond granularity.
 ///
@@ -205,7 +285,7 @@ where
     unsafe {
         from_glib(glib_sys::g_timeout_add_full(
             glib_sys::G_PRIORITY_DEFAULT,
-            interval.as_millis() as _,
+            interval.into_glib_millis(),
             Some(trampoline::<F>),
             into_raw(func),
             Some(destroy_closure::<F>),
@@ -237,7 +317,7 @@ where
         assert!(MainContext::default().is_owner());
         from_glib(glib_sys::g_timeout_add_full(
             glib_sys::G_PRIORITY_DEFAULT,
-            interval.as_millis() as _,
+            interval.into_glib_millis(),
             Some(trampoline::<F>),
             into_raw(func),
             Some(destroy_closure::<F>),
@@ -245,6 +325,42 @@ where
     }
 }
 
+/// Adds a closure to be called by the default main loop after the given
+/// `interval` with millisecond granularity.
+///
+/// `func` will be called only once. Precise timing is not guaranteed, the
+/// timeout may be delayed by other events.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+pub fn timeout_add_once<F>(interval: Duration, func: F) -> SourceId
+where
+    F: FnOnce() + Send + 'static,
+{
+    timeout_add(interval, fnmut_callback_wrapper(func))
+}
+
+/// Adds a closure to be called by the default main loop after the given
+/// `interval` with millisecond granularity.
+///
+/// `func` will be called only once. Precise timing is not guaranteed, the
+/// timeout may be delayed by other events.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+///
+/// Different to `timeout_add_once()`, this does not require `func` to be
+/// `Send` but can only be called from the thread that owns the main context.
+///
+/// This function panics if called from a different thread than the one that
+/// owns the main context.
+pub fn timeout_add_local_once<F>(interval: Duration, func: F) -> SourceId
+where
+    F: FnOnce() + 'static,
+{
+    timeout_add_local(interval, fnmut_callback_wrapper(func))
+}
+
 /// Adds a closure to be called by the default main loop at regular intervals
 /// with second granularity.
 ///
@@ -300,10 +416,48 @@ where
     }
 }
 
+/// Adds a closure to be called by the default main loop after the given
+/// `interval` with second granularity.
+///
+/// `func` will be called only once. Precise timing is not guaranteed, the
+/// timeout may be delayed by other events.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+pub fn timeout_add_seconds_once<F>(interval: u32, func: F) -> SourceId
+where
+    F: FnOnce() + Send + 'static,
+{
+    timeout_add_seconds(interval, fnmut_callback_wrapper(func))
+}
+
+/// Adds a closure to be called by the default main loop after the given
+/// `interval` with second granularity.
+///
+/// `func` will be called only once. Precise timing is not guaranteed, the
+/// timeout may be delayed by other events.
+///
+/// The default main loop almost always is the main loop of the main thread.
+/// Thus the closure is called on the main thread.
+///
+/// Different to `timeout_add_seconds_once()`, this does not require `func` to be
+/// `Send` but can only be called from the thread that owns the main context.
+///
+/// This function panics if called from a different thread than the one that
+/// owns the main context.
+pub fn timeout_add_seconds_local_once<F>(interval: u32, func: F) -> SourceId
+where
+    F: FnOnce() + 'static,
+{
+    timeout_add_seconds_local(interval, fnmut_callback_wrapper(func))
+}
+
 /// Adds a closure to be called by the main loop the returned `Source` is attached to when a child
 /// process exits.
 ///
-/// `func` will be called when `pid` exits
+/// `func` will be called when `pid` exits, with the pid and the exit status of the
+/// process. On UNIX this is the raw wait status as returned by `waitpid()`, on Windows
+/// the exit code of the process.
 pub fn child_watch_add<F>(pid: Pid, func: F) -> SourceId
 where
     F: FnMut(Pid, i32) + Send + 'static,
@@ -322,7 +476,9 @@ where
 /// Adds a closure to be called by the main loop the returned `Source` is attached to when a child
 /// process exits.
 ///
-/// `func` will be called when `pid` exits
+/// `func` will be called when `pid` exits, with the pid and the exit status of the
+/// process. On UNIX this is the raw wait status as returned by `waitpid()`, on Windows
+/// the exit code of the process.
 ///
 /// Different to `child_watch_add()`, this does not require `func` to be
 /// `Send` but can only be called from the thread that owns the main context.
@@ -351,6 +507,10 @@ where
 /// `func` will be called repeatedly every time `signum` is raised until it
 /// returns `Continue(false)`.
 ///
+/// Only `SIGHUP`, `SIGINT`, `SIGTERM`, `SIGUSR1`, `SIGUSR2` and `SIGWINCH` are
+/// supported. The closure is called from the main loop and not from the actual
+/// signal handler, so it is not limited to async-signal-safe functions.
+///
 /// The default main loop almost always is the main loop of the main thread.
 /// Thus the closure is called on the main thread.
 pub fn unix_signal_add<F>(signum: i32, func: F) -> SourceId
@@ -374,6 +534,10 @@ where
 /// `func` will be called repeatedly every time `signum` is raised until it
 /// returns `Continue(false)`.
 ///
+/// Only `SIGHUP`, `SIGINT`, `SIGTERM`, `SIGUSR1`, `SIGUSR2` and `SIGWINCH` are
+/// supported. The closure is called from the main loop and not from the actual
+/// signal handler, so it is not limited to async-signal-safe functions.
+///
 /// The default main loop almost always is the main loop of the main thread.
 /// Thus the closure is called on the main thread.
 ///
@@ -457,11 +621,14 @@ where
 
 /// Removes the source with the given id `source_id` from the default main context.
 ///
-/// It is a programmer error to attempt to remove a non-existent source.
+/// It is a programmer error to attempt to remove a non-existent source, use
+/// [`SourceId::remove`] if the source might already be removed.
 /// Note: source id are reused.
 ///
 /// For historical reasons, the native function always returns true, so we
 /// ignore it here.
+///
+/// [`SourceId::remove`]: struct.SourceId.html#method.remove
 #[allow(clippy::needless_pass_by_value)]
 pub fn source_remove(source_id: SourceId) {
     unsafe {
@@ -469,11 +636,110 @@ pub fn source_remove(source_id: SourceId) {
     }
 }
 
+/// A handle to an attached source that can be sent to other threads.
+///
+/// Unlike a [`SourceId`], which can only be used for removing the source from the
+/// main context it was attached to, this allows to change the source's configuration
+/// and to query whether it is still attached. Removing it via the handle is safe even
+/// if the source was already removed in the meantime.
+///
+/// [`SourceId`]: struct.SourceId.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceHandle(Source);
+
+impl SourceHandle {
+    /// Looks up the source with the given `source_id` in `context`, or in the default main
+    /// context if `None` is passed.
+    pub fn from_id(context: Option<&MainContext>, source_id: &SourceId) -> Option<SourceHandle> {
+        unsafe {
+            Option::<Source>::from_glib_none(glib_sys::g_main_context_find_source_by_id(
+                context.to_glib_none().0,
+                source_id.to_glib(),
+            ))
+            .map(SourceHandle)
+        }
+    }
+
+    /// Returns the underlying source.
+    pub fn source(&self) -> &Source {
+        &self.0
+    }
+
+    /// Returns `true` if the source is still attached to a main context.
+    pub fn is_attached(&self) -> bool {
+        !self.0.is_destroyed() && self.0.get_context().is_some()
+    }
+
+    /// Removes the source from its main context.
+    ///
+    /// This does nothing if the source was already removed.
+    pub fn remove(self) {
+        self.0.destroy();
+    }
+
+    /// Sets the priority of the source.
+    pub fn set_priority(&self, priority: Priority) {
+        self.0.set_priority(priority);
+    }
+
+    /// Sets a name for the source, used in debugging and profiling.
+    pub fn set_name(&self, name: &str) {
+        self.0.set_name(name);
+    }
+
+    /// Sets the monotonic time at which the source will be dispatched.
+    ///
+    /// See [`Source::set_ready_time()`](struct.Source.html#method.set_ready_time).
+    pub fn set_ready_time(&self, ready_time: i64) {
+        self.0.set_ready_time(ready_time);
+    }
+}
+
+impl From<Source> for SourceHandle {
+    fn from(source: Source) -> SourceHandle {
+        SourceHandle(source)
+    }
+}
+
 /// The priority of sources
 ///
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Priorities are ordered by their importance, i.e. a priority that compares greater than
+/// another one is dispatched first. This is the opposite of the ordering of the underlying
+/// integer values, where smaller values mean higher priority.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Priority(i32);
 
+impl Priority {
+    /// Returns the underlying integer value as used by GLib.
+    pub fn value(self) -> i32 {
+        self.0
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Priority) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Priority) -> cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl From<i32> for Priority {
+    fn from(value: i32) -> Priority {
+        Priority(value)
+    }
+}
+
+impl From<Priority> for i32 {
+    fn from(priority: Priority) -> i32 {
+        priority.0
+    }
+}
+
 #[doc(hidden)]
 impl ToGlib for Priority {
     type GlibType = i32;
@@ -546,7 +812,7 @@ where
     F: FnMut() -> Continue + Send + 'static,
 {
     unsafe {
-        let source = glib_sys::g_timeout_source_new(interval.as_millis() as _);
+        let source = glib_sys::g_timeout_source_new(interval.into_glib_millis());
         glib_sys::g_source_set_callback(
             source,
             Some(trampoline::<F>),
@@ -599,7 +865,9 @@ where
 /// Adds a closure to be called by the main loop the returned `Source` is attached to when a child
 /// process exits.
 ///
-/// `func` will be called when `pid` exits
+/// `func` will be called when `pid` exits, with the pid and the exit status of the
+/// process. On UNIX this is the raw wait status as returned by `waitpid()`, on Windows
+/// the exit code of the process.
 pub fn child_watch_source_new<F>(
     pid: Pid,
     name: Option<&str>,
@@ -701,6 +969,35 @@ where
 }
 
 impl Source {
+    /// Sets the priority of the source.
+    ///
+    /// While the main loop is being run, a source will be dispatched if it is ready to be
+    /// dispatched and no sources at a higher priority are ready to be dispatched.
+    pub fn set_priority(&self, priority: Priority) {
+        unsafe {
+            glib_sys::g_source_set_priority(self.to_glib_none().0, priority.to_glib());
+        }
+    }
+
+    /// Sets a name for the source, used in debugging and profiling.
+    pub fn set_name(&self, name: &str) {
+        unsafe {
+            glib_sys::g_source_set_name(self.to_glib_none().0, name.to_glib_none().0);
+        }
+    }
+
+    /// Sets the monotonic time at which the source will be dispatched.
+    ///
+    /// A value of -1 means that the source is never woken up because of its ready time, 0
+    /// means that it is dispatched immediately. Other values are in the clock domain of
+    /// [`MonotonicTime`](struct.MonotonicTime.html), e.g.
+    /// `(MonotonicTime::now() + Duration::from_secs(1)).into()`.
+    pub fn set_ready_time(&self, ready_time: i64) {
+        unsafe {
+            glib_sys::g_source_set_ready_time(self.to_glib_none().0, ready_time);
+        }
+    }
+
     pub fn attach(&self, context: Option<&MainContext>) -> SourceId {
         unsafe {
             from_glib(glib_sys::g_source_attach(
@@ -710,12 +1007,94 @@ impl Source {
         }
     }
 
+    /// Removes the source with the given id from the default main context.
+    ///
+    /// Returns an error if the source was already removed, see [`SourceId::remove`].
+    ///
+    /// [`SourceId::remove`]: struct.SourceId.html#method.remove
     pub fn remove(tag: SourceId) -> Result<(), ::BoolError> {
-        unsafe {
-            glib_result_from_gboolean!(
-                glib_sys::g_source_remove(tag.to_glib()),
-                "Failed to remove source"
-            )
+        tag.remove()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_ord() {
+        assert!(PRIORITY_HIGH > PRIORITY_DEFAULT);
+        assert!(PRIORITY_DEFAULT > PRIORITY_HIGH_IDLE);
+        assert!(PRIORITY_HIGH_IDLE > PRIORITY_DEFAULT_IDLE);
+        assert!(PRIORITY_DEFAULT_IDLE > PRIORITY_LOW);
+
+        assert_eq!(
+            Priority::from(glib_sys::G_PRIORITY_DEFAULT),
+            PRIORITY_DEFAULT
+        );
+        assert_eq!(i32::from(PRIORITY_LOW), glib_sys::G_PRIORITY_LOW);
+    }
+
+    #[test]
+    fn test_source_id_remove() {
+        let source_id = timeout_add_seconds(1000, || Continue(true));
+        let raw_id = source_id.to_glib();
+        assert!(source_id.remove().is_ok());
+
+        // Removing an already removed source fails instead of logging a critical
+        let source_id: SourceId = from_glib(raw_id);
+        assert!(source_id.remove().is_err());
+    }
+
+    #[test]
+    fn test_ready_time() {
+        let source = idle_source_new(None, PRIORITY_DEFAULT_IDLE, || Continue(true));
+        let ready_time = ::MonotonicTime::now() + Duration::from_secs(1);
+        source.set_ready_time(ready_time.into());
+        assert_eq!(::MonotonicTime::from(source.get_ready_time()), ready_time);
+    }
+
+    #[test]
+    fn test_add_once() {
+        use futures_util::future;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let c = MainContext::default();
+        let count = Arc::new(AtomicUsize::new(0));
+        let local_count = Rc::new(Cell::new(0));
+
+        // The local variants can only be called while owning the default main context
+        let source_ids = c.block_on(future::lazy(|_| {
+            let inc = || {
+                let count = count.clone();
+                move || {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            };
+            let local_inc = || {
+                let local_count = local_count.clone();
+                move || local_count.set(local_count.get() + 1)
+            };
+
+            vec![
+                idle_add_once(inc()),
+                idle_add_local_once(local_inc()),
+                timeout_add_once(Duration::from_millis(10), inc()),
+                timeout_add_local_once(Duration::from_millis(10), local_inc()),
+                timeout_add_seconds_once(0, inc()),
+                timeout_add_seconds_local_once(0, local_inc()),
+            ]
+        }));
+
+        assert!(c.run_until(
+            || count.load(Ordering::SeqCst) == 3 && local_count.get() == 3,
+            Duration::from_secs(10)
+        ));
+        for source_id in &source_ids {
+            assert!(c.find_source_by_id(source_id).is_none());
         }
     }
 }
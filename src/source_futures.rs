@@ -9,12 +9,20 @@ use futures_core::task;
 use futures_core::task::Poll;
 use futures_util::future::FutureExt;
 use futures_util::stream::StreamExt;
+#[cfg(all(not(unix), feature = "dox"))]
+use libc::c_int as RawFd;
+use std::error;
+use std::fmt;
 use std::marker::Unpin;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 use std::pin;
 use std::pin::Pin;
 use std::time::Duration;
 
 use Continue;
+#[cfg(any(unix, feature = "dox"))]
+use IOCondition;
 use MainContext;
 use Priority;
 use Source;
@@ -152,9 +160,56 @@ pub fn timeout_future_seconds_with_priority(
     }))
 }
 
+/// Error returned by [`future_with_timeout`] if the timeout elapsed before the `Future` resolved.
+///
+/// [`future_with_timeout`]: fn.future_with_timeout.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeoutError;
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("Future timed out")
+    }
+}
+
+impl error::Error for TimeoutError {}
+
+/// Create a `Future` that resolves to the output of `fut`, or to `TimeoutError` if `fut`
+/// did not resolve within the given duration.
+///
+/// `fut` is dropped if the timeout elapsed first.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn future_with_timeout<T, F: Future<Output = T>>(
+    value: Duration,
+    fut: F,
+) -> impl Future<Output = Result<T, TimeoutError>> {
+    future_with_timeout_with_priority(::PRIORITY_DEFAULT, value, fut)
+}
+
+/// Create a `Future` that resolves to the output of `fut`, or to `TimeoutError` if `fut`
+/// did not resolve within the given duration.
+///
+/// `fut` is dropped if the timeout elapsed first.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn future_with_timeout_with_priority<T, F: Future<Output = T>>(
+    priority: Priority,
+    value: Duration,
+    fut: F,
+) -> impl Future<Output = Result<T, TimeoutError>> {
+    let timeout = timeout_future_with_priority(priority, value);
+    futures_util::future::select(Box::pin(fut), timeout).map(|res| match res {
+        futures_util::future::Either::Left((value, _)) => Ok(value),
+        futures_util::future::Either::Right(_) => Err(TimeoutError),
+    })
+}
+
 /// Create a `Future` that will resolve once the child process with the given pid exits
 ///
-/// The `Future` will resolve to the pid of the child process and the exit code.
+/// The `Future` will resolve to the pid of the child process and the exit code. On UNIX
+/// this is the raw wait status as returned by `waitpid()`, on Windows the exit code of the
+/// process.
 ///
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn child_watch_future(
@@ -165,7 +220,9 @@ pub fn child_watch_future(
 
 /// Create a `Future` that will resolve once the child process with the given pid exits
 ///
-/// The `Future` will resolve to the pid of the child process and the exit code.
+/// The `Future` will resolve to the pid of the child process and the exit code. On UNIX
+/// this is the raw wait status as returned by `waitpid()`, on Windows the exit code of the
+/// process.
 ///
 /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
 pub fn child_watch_future_with_priority(
@@ -205,6 +262,63 @@ pub fn unix_signal_future_with_priority(
     }))
 }
 
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor reaches one of the
+/// given IO conditions
+///
+/// The `Future` will resolve to the IO condition of the file descriptor at that time.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_future(
+    fd: RawFd,
+    condition: IOCondition,
+) -> Pin<Box<dyn Future<Output = IOCondition> + Send + 'static>> {
+    unix_fd_future_with_priority(::PRIORITY_DEFAULT, fd, condition)
+}
+
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor reaches one of the
+/// given IO conditions
+///
+/// The `Future` will resolve to the IO condition of the file descriptor at that time.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn unix_fd_future_with_priority(
+    priority: Priority,
+    fd: RawFd,
+    condition: IOCondition,
+) -> Pin<Box<dyn Future<Output = IOCondition> + Send + 'static>> {
+    Box::pin(SourceFuture::new(move |send| {
+        let mut send = Some(send);
+        ::unix_fd_source_new(fd, condition, None, priority, move |_, condition| {
+            let _ = send.take().unwrap().send(condition);
+            Continue(false)
+        })
+    }))
+}
+
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor is readable, or an
+/// error or hang-up happened on it
+///
+/// The `Future` will resolve to the IO condition of the file descriptor at that time.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn fd_readable(fd: RawFd) -> Pin<Box<dyn Future<Output = IOCondition> + Send + 'static>> {
+    unix_fd_future(fd, IOCondition::IN | IOCondition::ERR | IOCondition::HUP)
+}
+
+#[cfg(any(unix, feature = "dox"))]
+/// Create a `Future` that will resolve once the given UNIX file descriptor is writable, or an
+/// error or hang-up happened on it
+///
+/// The `Future` will resolve to the IO condition of the file descriptor at that time.
+///
+/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+pub fn fd_writable(fd: RawFd) -> Pin<Box<dyn Future<Output = IOCondition> + Send + 'static>> {
+    unix_fd_future(fd, IOCondition::OUT | IOCondition::ERR | IOCondition::HUP)
+}
+
 /// Represents a `Stream` around a `glib::Source`. The stream will
 /// be provide all values that are provided by the source
 pub struct SourceStream<F, T> {
@@ -295,14 +409,18 @@ impl<T, F> Drop for SourceStream<T, F> {
 
 /// Create a `Stream` that will provide a value every given number of milliseconds.
 ///
-/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+///
+/// The underlying timeout source is destroyed once the `Stream` is dropped.
 pub fn interval_stream(value: Duration) -> Pin<Box<dyn Stream<Item = ()> + Send + 'static>> {
     interval_stream_with_priority(::PRIORITY_DEFAULT, value)
 }
 
 /// Create a `Stream` that will provide a value every given number of milliseconds.
 ///
-/// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+/// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+///
+/// The underlying timeout source is destroyed once the `Stream` is dropped.
 pub fn interval_stream_with_priority(
     priority: Priority,
     value: Duration,
@@ -321,6 +439,8 @@ pub fn interval_stream_with_priority(
 /// Create a `Stream` that will provide a value every given number of seconds.
 ///
 /// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+///
+/// The underlying timeout source is destroyed once the `Stream` is dropped.
 pub fn interval_stream_seconds(value: u32) -> Pin<Box<dyn Stream<Item = ()> + Send + 'static>> {
     interval_stream_seconds_with_priority(::PRIORITY_DEFAULT, value)
 }
@@ -328,6 +448,8 @@ pub fn interval_stream_seconds(value: u32) -> Pin<Box<dyn Stream<Item = ()> + Se
 /// Create a `Stream` that will provide a value every given number of seconds.
 ///
 /// The `Stream` must be spawned on an `Executor` backed by a `glib::MainContext`.
+///
+/// The underlying timeout source is destroyed once the `Stream` is dropped.
 pub fn interval_stream_seconds_with_priority(
     priority: Priority,
     value: u32,
@@ -383,6 +505,23 @@ mod tests {
         c.block_on(timeout_future(Duration::from_millis(20)));
     }
 
+    #[test]
+    fn test_future_with_timeout() {
+        let c = MainContext::new();
+
+        let res = c.block_on(future_with_timeout(
+            Duration::from_millis(20),
+            futures_util::future::pending::<()>(),
+        ));
+        assert_eq!(res, Err(TimeoutError));
+
+        let res = c.block_on(future_with_timeout(
+            Duration::from_millis(1000),
+            futures_util::future::ready(123),
+        ));
+        assert_eq!(res, Ok(123));
+    }
+
     #[test]
     fn test_timeout_send() {
         let c = MainContext::new();
@@ -420,6 +559,90 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn test_interval_drop() {
+        let c = MainContext::new();
+
+        c.block_on(
+            interval_stream(Duration::from_millis(10))
+                .take(1)
+                .for_each(|()| futures_util::future::ready(())),
+        );
+
+        // The timeout source must be gone once the stream was dropped, otherwise
+        // it would be dispatched here
+        thread::sleep(Duration::from_millis(20));
+        let _acquire = c.acquire().unwrap();
+        assert!(!c.iteration(false));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fd_readable() {
+        let c = MainContext::new();
+
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+        let condition = c.block_on(fd_writable(fds[1]));
+        assert!(condition.contains(IOCondition::OUT));
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(
+                unsafe { libc::write(fds[1], b"a".as_ptr() as *const _, 1) },
+                1
+            );
+        });
+
+        let condition = c.block_on(fd_readable(fds[0]));
+        assert!(condition.contains(IOCondition::IN));
+
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_child_watch() {
+        use std::process::Command;
+
+        let c = MainContext::new();
+
+        let child = Command::new("sh")
+            .args(&["-c", "exit 3"])
+            .spawn()
+            .expect("Failed to spawn child process");
+        let pid = ::Pid(child.id() as _);
+
+        let (res_pid, status) = c.block_on(child_watch_future(pid));
+        assert_eq!(res_pid, pid);
+        assert!(libc::WIFEXITED(status));
+        assert_eq!(libc::WEXITSTATUS(status), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_signal_stream() {
+        let c = MainContext::new();
+
+        let mut stream = unix_signal_stream(libc::SIGUSR1);
+
+        // Poll once so that the signal source is attached and the signal handler installed
+        c.block_on(futures_util::future::poll_fn(|ctx| {
+            assert!(stream.poll_next_unpin(ctx).is_pending());
+            Poll::Ready(())
+        }));
+
+        unsafe {
+            libc::raise(libc::SIGUSR1);
+        }
+
+        assert_eq!(c.block_on(stream.next()), Some(()));
+    }
+
     #[test]
     fn test_timeout_and_channel() {
         let c = MainContext::default();
@@ -0,0 +1,439 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Spawning of child processes.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use glib::SpawnBuilder;
+//!
+//! let output = SpawnBuilder::new("ls")
+//!     .arg("-l")
+//!     .working_directory("/")
+//!     .spawn_sync()
+//!     .unwrap();
+//! output.check_exit_status().unwrap();
+//! println!("{}", String::from_utf8_lossy(&output.stdout));
+//! ```
+
+use futures_core::future::Future;
+use glib_sys;
+use libc::c_char;
+use std::ffi::{CStr, OsStr, OsString};
+use std::fs::File;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::FromRawFd;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::ptr;
+use translate::*;
+use Error;
+use Pid;
+use SpawnFlags;
+
+type ChildSetup = Option<Box<dyn FnMut() + 'static>>;
+
+// The closure is only called by reference here as freeing it in the forked child is not
+// async-signal-safe. It is freed in the parent together with the builder instead.
+unsafe extern "C" fn child_setup_trampoline(user_data: glib_sys::gpointer) {
+    let child_setup = &mut *(user_data as *mut ChildSetup);
+    if let Some(ref mut child_setup) = *child_setup {
+        child_setup();
+    }
+}
+
+/// A builder for spawning a child process with `g_spawn_async_with_pipes()` or
+/// `g_spawn_sync()`.
+///
+/// By default the program is searched in `PATH`, the child inherits the environment and the
+/// working directory of the parent, and its stdin is connected to `/dev/null`.
+pub struct SpawnBuilder {
+    argv: Vec<OsString>,
+    envp: Option<Vec<OsString>>,
+    working_directory: Option<PathBuf>,
+    flags: SpawnFlags,
+    child_setup: ChildSetup,
+    pipe_stdin: bool,
+    pipe_stdout: bool,
+    pipe_stderr: bool,
+}
+
+impl SpawnBuilder {
+    /// Creates a new builder for running `program`.
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
+        SpawnBuilder {
+            argv: vec![program.as_ref().to_owned()],
+            envp: None,
+            working_directory: None,
+            flags: SpawnFlags::SEARCH_PATH,
+            child_setup: None,
+            pipe_stdin: false,
+            pipe_stdout: false,
+            pipe_stderr: false,
+        }
+    }
+
+    /// Adds an argument for the program.
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> Self {
+        self.argv.push(arg.as_ref().to_owned());
+        self
+    }
+
+    /// Adds multiple arguments for the program.
+    pub fn args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, args: I) -> Self {
+        self.argv
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    /// Sets the complete environment of the child as a list of `KEY=VALUE` entries.
+    pub fn envp<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, envp: I) -> Self {
+        self.envp = Some(
+            envp.into_iter()
+                .map(|entry| entry.as_ref().to_owned())
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets the environment variable `key` to `value` in the environment of the child.
+    ///
+    /// All other variables are inherited from the parent unless the environment was replaced
+    /// with [`envp`](#method.envp).
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        let key = key.as_ref().as_bytes();
+        let envp = self.envp.get_or_insert_with(|| {
+            ::std::env::vars_os()
+                .map(|(key, value)| {
+                    let mut entry = key;
+                    entry.push("=");
+                    entry.push(value);
+                    entry
+                })
+                .collect()
+        });
+        envp.retain(|entry| {
+            let entry = entry.as_bytes();
+            !(entry.starts_with(key) && entry.get(key.len()) == Some(&b'='))
+        });
+        let mut entry = OsStr::from_bytes(key).to_owned();
+        entry.push("=");
+        entry.push(value);
+        envp.push(entry);
+        self
+    }
+
+    /// Sets the working directory of the child.
+    pub fn working_directory<P: AsRef<Path>>(mut self, working_directory: P) -> Self {
+        self.working_directory = Some(working_directory.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the flags for spawning the child, replacing the default of
+    /// `SpawnFlags::SEARCH_PATH`.
+    pub fn flags(mut self, flags: SpawnFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets a function that is called in the child after `fork()` and before `exec()`.
+    ///
+    /// # Safety
+    ///
+    /// The function runs in the forked child while other threads of the parent might have
+    /// held locks, e.g. of the memory allocator. It must only call async-signal-safe functions,
+    /// which excludes allocating memory or using most of the standard library.
+    pub unsafe fn child_setup<F: FnMut() + 'static>(mut self, child_setup: F) -> Self {
+        self.child_setup = Some(Box::new(child_setup));
+        self
+    }
+
+    /// Connects the stdin of the child to a pipe, available as [`Child::stdin`].
+    ///
+    /// Only supported by [`spawn`](#method.spawn).
+    ///
+    /// [`Child::stdin`]: struct.Child.html#structfield.stdin
+    pub fn pipe_stdin(mut self) -> Self {
+        self.pipe_stdin = true;
+        self
+    }
+
+    /// Connects the stdout of the child to a pipe, available as [`Child::stdout`].
+    ///
+    /// [`spawn_sync`](#method.spawn_sync) always captures stdout.
+    ///
+    /// [`Child::stdout`]: struct.Child.html#structfield.stdout
+    pub fn pipe_stdout(mut self) -> Self {
+        self.pipe_stdout = true;
+        self
+    }
+
+    /// Connects the stderr of the child to a pipe, available as [`Child::stderr`].
+    ///
+    /// [`spawn_sync`](#method.spawn_sync) always captures stderr.
+    ///
+    /// [`Child::stderr`]: struct.Child.html#structfield.stderr
+    pub fn pipe_stderr(mut self) -> Self {
+        self.pipe_stderr = true;
+        self
+    }
+
+    /// Spawns the child without waiting for it to exit.
+    ///
+    /// The child is spawned with `SpawnFlags::DO_NOT_REAP_CHILD`: it must be waited for with
+    /// [`Child::wait_future`](struct.Child.html#method.wait_future) or
+    /// [`child_watch_add`](fn.child_watch_add.html), otherwise it stays around as a zombie
+    /// process after it exited.
+    pub fn spawn(mut self) -> Result<Child, Error> {
+        unsafe {
+            let argv: Stash<*mut *mut c_char, [OsString]> = self.argv[..].to_glib_none();
+            let envp: Option<Stash<*mut *mut c_char, [OsString]>> =
+                self.envp.as_ref().map(|envp| envp[..].to_glib_none());
+            let mut pid = 0;
+            let mut stdin = -1;
+            let mut stdout = -1;
+            let mut stderr = -1;
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_spawn_async_with_pipes(
+                self.working_directory.to_glib_none().0,
+                argv.0,
+                envp.as_ref().map_or(ptr::null_mut(), |envp| envp.0),
+                (self.flags | SpawnFlags::DO_NOT_REAP_CHILD).to_glib(),
+                self.child_setup
+                    .as_ref()
+                    .map(|_| child_setup_trampoline as _),
+                &mut self.child_setup as *mut ChildSetup as glib_sys::gpointer,
+                &mut pid,
+                if self.pipe_stdin {
+                    &mut stdin
+                } else {
+                    ptr::null_mut()
+                },
+                if self.pipe_stdout {
+                    &mut stdout
+                } else {
+                    ptr::null_mut()
+                },
+                if self.pipe_stderr {
+                    &mut stderr
+                } else {
+                    ptr::null_mut()
+                },
+                &mut error,
+            );
+            if error.is_null() {
+                let file_from_fd = |fd| {
+                    if fd == -1 {
+                        None
+                    } else {
+                        Some(File::from_raw_fd(fd))
+                    }
+                };
+                Ok(Child {
+                    pid: from_glib(pid),
+                    stdin: file_from_fd(stdin),
+                    stdout: file_from_fd(stdout),
+                    stderr: file_from_fd(stderr),
+                })
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    /// Spawns the child and waits for it to exit, capturing its stdout and stderr.
+    ///
+    /// The captured output ends at the first nul byte, as `g_spawn_sync()` only returns
+    /// nul-terminated strings. Stdin is not supported.
+    pub fn spawn_sync(mut self) -> Result<SpawnOutput, Error> {
+        unsafe {
+            let argv: Stash<*mut *mut c_char, [OsString]> = self.argv[..].to_glib_none();
+            let envp: Option<Stash<*mut *mut c_char, [OsString]>> =
+                self.envp.as_ref().map(|envp| envp[..].to_glib_none());
+            let mut stdout = ptr::null_mut();
+            let mut stderr = ptr::null_mut();
+            let mut wait_status = 0;
+            let mut error = ptr::null_mut();
+            let _ = glib_sys::g_spawn_sync(
+                self.working_directory.to_glib_none().0,
+                argv.0,
+                envp.as_ref().map_or(ptr::null_mut(), |envp| envp.0),
+                self.flags.to_glib(),
+                self.child_setup
+                    .as_ref()
+                    .map(|_| child_setup_trampoline as _),
+                &mut self.child_setup as *mut ChildSetup as glib_sys::gpointer,
+                &mut stdout,
+                &mut stderr,
+                &mut wait_status,
+                &mut error,
+            );
+            let stdout = output_from_glib_full(stdout);
+            let stderr = output_from_glib_full(stderr);
+            if error.is_null() {
+                Ok(SpawnOutput {
+                    wait_status,
+                    stdout,
+                    stderr,
+                })
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+}
+
+unsafe fn output_from_glib_full(ptr: *mut c_char) -> Vec<u8> {
+    if ptr.is_null() {
+        Vec::new()
+    } else {
+        let output = CStr::from_ptr(ptr).to_bytes().to_vec();
+        glib_sys::g_free(ptr as *mut _);
+        output
+    }
+}
+
+/// A child process spawned with [`SpawnBuilder::spawn`](struct.SpawnBuilder.html#method.spawn).
+#[derive(Debug)]
+pub struct Child {
+    pid: Pid,
+    /// The pipe to the stdin of the child, if requested.
+    pub stdin: Option<File>,
+    /// The pipe from the stdout of the child, if requested.
+    pub stdout: Option<File>,
+    /// The pipe from the stderr of the child, if requested.
+    pub stderr: Option<File>,
+}
+
+impl Child {
+    /// Returns the process ID of the child.
+    pub fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// Creates a `Future` that resolves to the wait status of the child once it exited.
+    ///
+    /// The wait status can be checked with
+    /// [`spawn_check_exit_status`](fn.spawn_check_exit_status.html).
+    ///
+    /// The `Future` must be spawned on an `Executor` backed by a `glib::MainContext`.
+    pub fn wait_future(&self) -> Pin<Box<dyn Future<Output = (Pid, i32)> + Send + 'static>> {
+        ::child_watch_future(self.pid)
+    }
+}
+
+/// The result of [`SpawnBuilder::spawn_sync`](struct.SpawnBuilder.html#method.spawn_sync).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnOutput {
+    /// The raw wait status of the child.
+    pub wait_status: i32,
+    /// The captured stdout of the child.
+    pub stdout: Vec<u8>,
+    /// The captured stderr of the child.
+    pub stderr: Vec<u8>,
+}
+
+impl SpawnOutput {
+    /// Returns an error if the child did not exit successfully.
+    pub fn check_exit_status(&self) -> Result<(), Error> {
+        ::spawn_check_exit_status(self.wait_status)
+    }
+}
+
+/// Parses `command_line` with [`shell_parse_argv`](fn.shell_parse_argv.html), spawns the
+/// program found in `PATH` and waits for it to exit, capturing its stdout and stderr.
+///
+/// The same limitations as for [`SpawnBuilder::spawn_sync`] apply.
+///
+/// [`SpawnBuilder::spawn_sync`]: struct.SpawnBuilder.html#method.spawn_sync
+pub fn spawn_command_line_sync<P: AsRef<OsStr>>(command_line: P) -> Result<SpawnOutput, Error> {
+    unsafe {
+        let mut stdout = ptr::null_mut();
+        let mut stderr = ptr::null_mut();
+        let mut wait_status = 0;
+        let mut error = ptr::null_mut();
+        let _ = glib_sys::g_spawn_command_line_sync(
+            command_line.as_ref().to_glib_none().0,
+            &mut stdout,
+            &mut stderr,
+            &mut wait_status,
+            &mut error,
+        );
+        let stdout = output_from_glib_full(stdout);
+        let stderr = output_from_glib_full(stderr);
+        if error.is_null() {
+            Ok(SpawnOutput {
+                wait_status,
+                stdout,
+                stderr,
+            })
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use MainContext;
+
+    #[test]
+    fn spawn_sync() {
+        let output = SpawnBuilder::new("sh")
+            .args(&["-c", "echo $GLIB_RS_TEST; pwd; echo error >&2; exit 3"])
+            .env("GLIB_RS_TEST", "hello")
+            .working_directory("/")
+            .spawn_sync()
+            .unwrap();
+        assert_eq!(output.stdout, b"hello\n/\n");
+        assert_eq!(output.stderr, b"error\n");
+        assert!(libc::WIFEXITED(output.wait_status));
+        assert_eq!(libc::WEXITSTATUS(output.wait_status), 3);
+        assert!(output.check_exit_status().is_err());
+
+        assert!(SpawnBuilder::new("/nonexistent/program")
+            .spawn_sync()
+            .is_err());
+    }
+
+    #[test]
+    fn command_line() {
+        let output = spawn_command_line_sync("sh -c 'echo \"a  b\"; exit 0'").unwrap();
+        assert_eq!(output.stdout, b"a  b\n");
+        assert!(output.check_exit_status().is_ok());
+
+        let output = spawn_command_line_sync("false").unwrap();
+        assert!(::spawn_check_exit_status(output.wait_status).is_err());
+
+        assert!(spawn_command_line_sync("'unterminated").is_err());
+    }
+
+    #[test]
+    fn spawn_pipes() {
+        let mut child = SpawnBuilder::new("cat")
+            .pipe_stdin()
+            .pipe_stdout()
+            .spawn()
+            .unwrap();
+        assert!(child.stderr.is_none());
+
+        child.stdin.take().unwrap().write_all(b"hello").unwrap();
+        let mut output = String::new();
+        child
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "hello");
+
+        let c = MainContext::new();
+        let (pid, wait_status) = c.block_on(child.wait_future());
+        assert_eq!(pid, child.pid());
+        assert!(::spawn_check_exit_status(wait_status).is_ok());
+    }
+}
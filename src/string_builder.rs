@@ -9,6 +9,7 @@ use std::cmp;
 use std::convert;
 use std::fmt;
 use std::hash;
+use std::mem;
 use std::ops;
 use std::ptr;
 use std::slice;
@@ -17,7 +18,13 @@ use translate::*;
 
 glib_wrapper! {
     /// A mutable text buffer that grows automatically.
-    pub struct String(Boxed<glib_sys::GString>);
+    ///
+    /// This wraps the C `GString` and is useful for assembling strings that are passed to or
+    /// returned from C APIs taking a `GString*`. Use [`into_gstring`] to convert it into a
+    /// [`GString`](struct.GString.html) without copying.
+    ///
+    /// [`into_gstring`]: #method.into_gstring
+    pub struct StringBuilder(Boxed<glib_sys::GString>);
 
     match fn {
         copy => |ptr| gobject_sys::g_boxed_copy(glib_sys::g_gstring_get_type(), ptr as *mut _) as *mut glib_sys::GString,
@@ -26,11 +33,14 @@ glib_wrapper! {
     }
 }
 
-unsafe impl Send for String {}
-unsafe impl Sync for String {}
+#[deprecated(note = "Use `StringBuilder` instead")]
+pub type String = StringBuilder;
 
-impl String {
-    pub fn new<T: AsRef<[u8]>>(data: T) -> String {
+unsafe impl Send for StringBuilder {}
+unsafe impl Sync for StringBuilder {}
+
+impl StringBuilder {
+    pub fn new<T: AsRef<[u8]>>(data: T) -> StringBuilder {
         let bytes = data.as_ref();
         unsafe {
             from_glib_full(glib_sys::g_string_new_len(
@@ -103,28 +113,55 @@ impl String {
     pub fn to_string_lossy(&self) -> borrow::Cow<str> {
         ::std::string::String::from_utf8_lossy(self.as_ref())
     }
+
+    /// Converts the builder into a `GString` without copying the data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contained data is not valid UTF-8.
+    pub fn into_gstring(mut self) -> ::GString {
+        assert!(self.to_str().is_ok(), "invalid UTF-8 in StringBuilder");
+        unsafe {
+            let ptr = self.to_glib_none_mut().0;
+            mem::forget(self);
+            from_glib_full(glib_sys::g_string_free(ptr, glib_sys::GFALSE))
+        }
+    }
 }
 
-impl Default for String {
+impl Default for StringBuilder {
     /// Creates a new empty string.
-    fn default() -> String {
+    fn default() -> StringBuilder {
         unsafe { from_glib_full(glib_sys::g_string_new(ptr::null())) }
     }
 }
 
-impl fmt::Debug for String {
+impl fmt::Debug for StringBuilder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.to_string_lossy())
     }
 }
 
-impl fmt::Display for String {
+impl fmt::Display for StringBuilder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.to_string_lossy())
     }
 }
 
-impl PartialEq for String {
+impl fmt::Write for StringBuilder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.append(s);
+        Ok(())
+    }
+}
+
+impl From<StringBuilder> for ::GString {
+    fn from(s: StringBuilder) -> ::GString {
+        s.into_gstring()
+    }
+}
+
+impl PartialEq for StringBuilder {
     fn eq(&self, other: &Self) -> bool {
         unsafe {
             from_glib(glib_sys::g_string_equal(
@@ -135,21 +172,21 @@ impl PartialEq for String {
     }
 }
 
-impl Eq for String {}
+impl Eq for StringBuilder {}
 
-impl cmp::PartialOrd for String {
+impl cmp::PartialOrd for StringBuilder {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl cmp::Ord for String {
+impl cmp::Ord for StringBuilder {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         self.as_ref().cmp(other.as_ref())
     }
 }
 
-impl hash::Hash for String {
+impl hash::Hash for StringBuilder {
     fn hash<H>(&self, state: &mut H)
     where
         H: hash::Hasher,
@@ -158,7 +195,7 @@ impl hash::Hash for String {
     }
 }
 
-impl convert::AsRef<[u8]> for String {
+impl convert::AsRef<[u8]> for StringBuilder {
     fn as_ref(&self) -> &[u8] {
         let ptr: *const u8 = (*self.0).str as _;
         let len: usize = (*self.0).len;
@@ -166,7 +203,7 @@ impl convert::AsRef<[u8]> for String {
     }
 }
 
-impl ops::Deref for String {
+impl ops::Deref for StringBuilder {
     type Target = [u8];
 
     fn deref(&self) -> &[u8] {
@@ -180,35 +217,35 @@ impl ops::Deref for String {
 mod tests {
     #[test]
     fn append() {
-        let mut s = ::String::new("");
+        let mut s = ::StringBuilder::new("");
         s.append("Hello").append(" ").append("there!");
         assert_eq!(&*s, b"Hello there!");
     }
 
     #[test]
     fn insert() {
-        let mut s = ::String::new("foobaz");
+        let mut s = ::StringBuilder::new("foobaz");
         s.insert(3, "bar");
         assert_eq!(&*s, b"foobarbaz");
     }
 
     #[test]
     fn overwrite() {
-        let mut s = ::String::new("abc");
+        let mut s = ::StringBuilder::new("abc");
         s.overwrite(2, "de");
         assert_eq!(&*s, b"abde");
     }
 
     #[test]
     fn prepend() {
-        let mut s = ::String::new("456");
+        let mut s = ::StringBuilder::new("456");
         s.prepend("123");
         assert_eq!(&*s, b"123456");
     }
 
     #[test]
     fn truncate() {
-        let mut s = ::String::new("12345");
+        let mut s = ::StringBuilder::new("12345");
         s.truncate(10);
         assert_eq!(&*s, b"12345");
         s.truncate(2);
@@ -217,21 +254,21 @@ mod tests {
 
     #[test]
     fn default() {
-        let s1: ::String = Default::default();
+        let s1: ::StringBuilder = Default::default();
         assert_eq!(&*s1, b"");
     }
 
     #[test]
     fn display() {
-        let s: ::String = ::String::new("This is a string.");
+        let s: ::StringBuilder = ::StringBuilder::new("This is a string.");
         assert_eq!(&format!("{}", s), "This is a string.");
     }
 
     #[test]
     fn eq() {
-        let a1 = ::String::new("a");
-        let a2 = ::String::new("a");
-        let b = ::String::new("b");
+        let a1 = ::StringBuilder::new("a");
+        let a2 = ::StringBuilder::new("a");
+        let b = ::StringBuilder::new("b");
         assert_eq!(a1, a1);
         assert_eq!(a1, a2);
         assert_ne!(a1, b);
@@ -240,8 +277,37 @@ mod tests {
 
     #[test]
     fn invalid_utf8() {
-        let s = ::String::new(b"Hello \xF0\x90\x80World");
+        let s = ::StringBuilder::new(b"Hello \xF0\x90\x80World");
         assert!(s.to_str().is_err());
         assert_eq!(s.to_string_lossy(), "Hello �World");
     }
+
+    #[test]
+    fn write() {
+        use std::fmt::Write;
+
+        let mut s = ::StringBuilder::default();
+        write!(s, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+        assert_eq!(&*s, b"1 + 2 = 3");
+    }
+
+    #[test]
+    fn into_gstring() {
+        let mut s = ::StringBuilder::new("abc");
+        s.append("def");
+        let gstring = s.into_gstring();
+        assert_eq!(gstring.as_str(), "abcdef");
+    }
+
+    #[test]
+    fn into_glib_ptr() {
+        use translate::ToGlibPtr;
+
+        let s = ::StringBuilder::new("abc");
+        let orig_ptr: *const ::glib_sys::GString = s.to_glib_none().0;
+        let ptr = s.into_glib_ptr();
+        assert_eq!(ptr as *const _, orig_ptr);
+        let s = unsafe { ::StringBuilder::from_glib_ptr_full(ptr) };
+        assert_eq!(&*s, b"abc");
+    }
 }
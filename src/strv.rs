@@ -0,0 +1,373 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! # Examples
+//!
+//! ```
+//! use glib::StrV;
+//!
+//! let strv = StrV::from(vec![String::from("a"), String::from("b")]);
+//! assert_eq!(strv.len(), 2);
+//! assert_eq!(strv.get(1).unwrap().to_str(), Ok("b"));
+//! assert_eq!(strv, ["a", "b"][..]);
+//! ```
+
+use glib_sys;
+use gobject_sys;
+use libc::c_char;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::str::Utf8Error;
+use translate::*;
+use types::{StaticType, Type};
+use value::{FromValueOptional, SetValue, Value};
+
+/// An owned `NULL`-terminated array of strings (`gchar **`).
+///
+/// Unlike `Vec<String>` or `Vec<GString>`, converting from the C representation doesn't copy the
+/// strings and iterating borrows them directly from the array. As the strings are not guaranteed
+/// to be valid UTF-8 they are returned as `CStr`.
+pub struct StrV {
+    ptr: ptr::NonNull<*mut c_char>,
+    len: usize,
+}
+
+unsafe impl Send for StrV {}
+unsafe impl Sync for StrV {}
+
+impl StrV {
+    /// Creates a new empty array.
+    pub fn new() -> StrV {
+        unsafe {
+            let ptr = glib_sys::g_malloc0(mem::size_of::<*mut c_char>()) as *mut *mut c_char;
+            StrV::from_raw(ptr, 0)
+        }
+    }
+
+    unsafe fn from_raw(ptr: *mut *mut c_char, len: usize) -> StrV {
+        StrV {
+            ptr: ptr::NonNull::new(ptr).expect("NULL string array"),
+            len,
+        }
+    }
+
+    /// Returns the number of strings in the array.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the array contains no strings.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the string at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        self.as_slice()
+            .get(index)
+            .map(|&ptr| unsafe { CStr::from_ptr(ptr) })
+    }
+
+    /// Returns an iterator over the strings of the array.
+    pub fn iter(&self) -> StrVIter {
+        StrVIter(self.as_slice().iter())
+    }
+
+    /// Returns the `NULL`-terminated array.
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptr.as_ptr() as *const *const c_char
+    }
+
+    fn as_slice(&self) -> &[*mut c_char] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for StrV {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_strfreev(self.ptr.as_ptr()) }
+    }
+}
+
+impl Clone for StrV {
+    fn clone(&self) -> StrV {
+        unsafe { StrV::from_raw(glib_sys::g_strdupv(self.ptr.as_ptr()), self.len) }
+    }
+}
+
+impl Default for StrV {
+    fn default() -> StrV {
+        StrV::new()
+    }
+}
+
+impl fmt::Debug for StrV {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for StrV {
+    fn eq(&self, other: &StrV) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for StrV {}
+
+impl<T: AsRef<str>> PartialEq<[T]> for StrV {
+    fn eq(&self, other: &[T]) -> bool {
+        self.iter()
+            .map(CStr::to_bytes)
+            .eq(other.iter().map(|s| s.as_ref().as_bytes()))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for StrV {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> StrV {
+        let ptrs: Vec<*mut c_char> = iter.into_iter().map(|s| s.to_glib_full()).collect();
+        let len = ptrs.len();
+        unsafe {
+            let ptr =
+                glib_sys::g_malloc(mem::size_of::<*mut c_char>() * (len + 1)) as *mut *mut c_char;
+            ptr::copy_nonoverlapping(ptrs.as_ptr(), ptr, len);
+            ptr::write(ptr.add(len), ptr::null_mut());
+            StrV::from_raw(ptr, len)
+        }
+    }
+}
+
+impl FromIterator<String> for StrV {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> StrV {
+        let strings: Vec<String> = iter.into_iter().collect();
+        strings.iter().map(String::as_str).collect()
+    }
+}
+
+impl<'a> Extend<&'a str> for StrV {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        let ptrs: Vec<*mut c_char> = iter.into_iter().map(|s| s.to_glib_full()).collect();
+        if ptrs.is_empty() {
+            return;
+        }
+        let len = self.len + ptrs.len();
+        unsafe {
+            let ptr = glib_sys::g_realloc(
+                self.ptr.as_ptr() as *mut _,
+                mem::size_of::<*mut c_char>() * (len + 1),
+            ) as *mut *mut c_char;
+            ptr::copy_nonoverlapping(ptrs.as_ptr(), ptr.add(self.len), ptrs.len());
+            ptr::write(ptr.add(len), ptr::null_mut());
+            self.ptr = ptr::NonNull::new_unchecked(ptr);
+            self.len = len;
+        }
+    }
+}
+
+impl Extend<String> for StrV {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        let strings: Vec<String> = iter.into_iter().collect();
+        self.extend(strings.iter().map(String::as_str));
+    }
+}
+
+impl From<Vec<String>> for StrV {
+    fn from(v: Vec<String>) -> StrV {
+        v.iter().map(String::as_str).collect()
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for StrV {
+    fn from(v: &'a [&'a str]) -> StrV {
+        v.iter().cloned().collect()
+    }
+}
+
+impl TryFrom<StrV> for Vec<String> {
+    type Error = Utf8Error;
+
+    fn try_from(v: StrV) -> Result<Vec<String>, Utf8Error> {
+        v.iter().map(|s| s.to_str().map(String::from)).collect()
+    }
+}
+
+/// Iterator over the strings of a [`StrV`](struct.StrV.html).
+#[derive(Debug)]
+pub struct StrVIter<'a>(slice::Iter<'a, *mut c_char>);
+
+impl<'a> Iterator for StrVIter<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<&'a CStr> {
+        self.0.next().map(|&ptr| unsafe { CStr::from_ptr(ptr) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for StrVIter<'a> {
+    fn next_back(&mut self) -> Option<&'a CStr> {
+        self.0
+            .next_back()
+            .map(|&ptr| unsafe { CStr::from_ptr(ptr) })
+    }
+}
+
+impl<'a> ExactSizeIterator for StrVIter<'a> {}
+
+impl<'a> IntoIterator for &'a StrV {
+    type Item = &'a CStr;
+    type IntoIter = StrVIter<'a>;
+
+    fn into_iter(self) -> StrVIter<'a> {
+        self.iter()
+    }
+}
+
+impl GlibPtrDefault for StrV {
+    type GlibType = *mut *mut c_char;
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *const *const c_char> for StrV {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *const *const c_char, Self> {
+        Stash(self.as_ptr(), self)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *const *const c_char {
+        unsafe { glib_sys::g_strdupv(self.ptr.as_ptr()) as *const *const c_char }
+    }
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *mut *mut c_char> for StrV {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut *mut c_char, Self> {
+        Stash(self.ptr.as_ptr(), self)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut *mut c_char {
+        unsafe { glib_sys::g_strdupv(self.ptr.as_ptr()) }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut *mut c_char> for StrV {
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut *mut c_char) -> Self {
+        assert!(!ptr.is_null());
+        StrV::from_raw(ptr, c_ptr_array_len(ptr as *const *mut c_char))
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*mut *mut c_char> for StrV {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut *mut c_char) -> Self {
+        assert!(!ptr.is_null());
+        from_glib_full(glib_sys::g_strdupv(ptr))
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*const *const c_char> for StrV {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *const *const c_char) -> Self {
+        from_glib_none(ptr as *mut *mut c_char)
+    }
+}
+
+impl StaticType for StrV {
+    fn static_type() -> Type {
+        unsafe { from_glib(glib_sys::g_strv_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for StrV {
+    unsafe fn from_value_optional(value: &'a Value) -> Option<Self> {
+        let ptr = gobject_sys::g_value_dup_boxed(value.to_glib_none().0) as *mut *mut c_char;
+        if ptr.is_null() {
+            None
+        } else {
+            Some(from_glib_full(ptr))
+        }
+    }
+}
+
+impl SetValue for StrV {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        gobject_sys::g_value_set_boxed(value.to_glib_none_mut().0, this.as_ptr() as *const _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::ToValue;
+
+    #[test]
+    fn test_strv() {
+        let strv: StrV = vec!["a", "bc", "øäöü"].into_iter().collect();
+        assert_eq!(strv.len(), 3);
+        assert_eq!(strv.get(1).map(CStr::to_bytes), Some(&b"bc"[..]));
+        assert_eq!(strv.get(3), None);
+        assert_eq!(strv, ["a", "bc", "øäöü"][..]);
+
+        let ptr: *mut *mut c_char = strv.to_glib_full();
+        let copy: StrV = unsafe { from_glib_full(ptr) };
+        assert_eq!(copy, strv);
+
+        let v = Vec::<String>::try_from(copy).unwrap();
+        assert_eq!(StrV::from(v), strv);
+
+        let empty = StrV::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty, StrV::default());
+    }
+
+    #[test]
+    fn test_strv_extend() {
+        let mut strv = StrV::new();
+        strv.extend(vec!["a", "b"]);
+        strv.extend(vec!["c".to_string()]);
+        assert_eq!(strv, ["a", "b", "c"][..]);
+        assert_eq!(
+            strv.iter().rev().map(CStr::to_bytes).collect::<Vec<_>>(),
+            [&b"c"[..], b"b", b"a"]
+        );
+    }
+
+    #[test]
+    fn test_strv_non_utf8() {
+        let ptrs: [*const c_char; 2] = [b"\xff\0".as_ptr() as *const _, ptr::null()];
+        let strv: StrV = unsafe { from_glib_none(ptrs.as_ptr()) };
+        assert_eq!(strv.get(0).map(CStr::to_bytes), Some(&b"\xff"[..]));
+        assert_ne!(strv, ["a"][..]);
+        let _ = format!("{:?}", strv);
+        assert!(Vec::<String>::try_from(strv).is_err());
+    }
+
+    #[test]
+    fn test_strv_value() {
+        let strv = StrV::from(&["a", "b"][..]);
+        let value = strv.to_value();
+        assert_eq!(value.type_(), StrV::static_type());
+        assert_eq!(value.get::<StrV>().unwrap(), Some(strv));
+    }
+}
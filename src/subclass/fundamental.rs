@@ -0,0 +1,295 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Module for registering new fundamental types.
+//!
+//! Fundamental types are the roots of type hierarchies, like `GObject` or `GParamSpec`. They
+//! are needed by bindings of libraries that build their own object system on top of the GLib
+//! type system, e.g. for `GstMiniObject`-style reference counted types that should be
+//! storable in a [`Value`](../../value/struct.Value.html).
+
+use glib_sys;
+use gobject_sys;
+use libc::c_char;
+use std::ffi::CString;
+use std::mem;
+use std::ptr;
+use translate::*;
+use {Type, TypeFlags, TypeFundamentalFlags};
+
+/// Hooks for storing instances of a fundamental type in a `Value`.
+///
+/// Values of the type hold a single, possibly `NULL`, pointer to an instance, and these hooks
+/// manage the references of the instances when values are copied or freed.
+///
+/// See [`FundamentalType::value_table`](struct.FundamentalType.html#method.value_table).
+///
+/// # Safety
+///
+/// Every reference returned by `ref_` must stay valid until it is passed to `unref`.
+pub unsafe trait ValueTableHooks: 'static {
+    /// Returns a new reference to the non-`NULL` instance `ptr`.
+    unsafe fn ref_(ptr: glib_sys::gpointer) -> glib_sys::gpointer;
+
+    /// Releases a reference to the non-`NULL` instance `ptr`.
+    unsafe fn unref(ptr: glib_sys::gpointer);
+}
+
+unsafe extern "C" fn value_init(value: *mut gobject_sys::GValue) {
+    (*value).data[0].v_pointer = ptr::null_mut();
+}
+
+unsafe extern "C" fn value_free<V: ValueTableHooks>(value: *mut gobject_sys::GValue) {
+    let ptr = (*value).data[0].v_pointer;
+    if !ptr.is_null() {
+        V::unref(ptr);
+    }
+}
+
+unsafe extern "C" fn value_copy<V: ValueTableHooks>(
+    src_value: *const gobject_sys::GValue,
+    dest_value: *mut gobject_sys::GValue,
+) {
+    let ptr = (*src_value).data[0].v_pointer;
+    (*dest_value).data[0].v_pointer = if ptr.is_null() { ptr } else { V::ref_(ptr) };
+}
+
+unsafe extern "C" fn value_peek_pointer(value: *const gobject_sys::GValue) -> glib_sys::gpointer {
+    (*value).data[0].v_pointer
+}
+
+unsafe extern "C" fn collect_value<V: ValueTableHooks>(
+    value: *mut gobject_sys::GValue,
+    _n_collect_values: u32,
+    collect_values: *mut gobject_sys::GTypeCValue,
+    _collect_flags: u32,
+) -> *mut c_char {
+    let ptr = (*collect_values).v_pointer;
+    (*value).data[0].v_pointer = if ptr.is_null() { ptr } else { V::ref_(ptr) };
+
+    ptr::null_mut()
+}
+
+unsafe extern "C" fn lcopy_value<V: ValueTableHooks>(
+    value: *const gobject_sys::GValue,
+    _n_collect_values: u32,
+    collect_values: *mut gobject_sys::GTypeCValue,
+    collect_flags: u32,
+) -> *mut c_char {
+    let dest = (*collect_values).v_pointer as *mut glib_sys::gpointer;
+    if dest.is_null() {
+        return glib_sys::g_strdup(b"value location passed as NULL\0".as_ptr() as *const _);
+    }
+
+    let ptr = (*value).data[0].v_pointer;
+    *dest = if ptr.is_null() || collect_flags & gobject_sys::G_VALUE_NOCOPY_CONTENTS as u32 != 0 {
+        ptr
+    } else {
+        V::ref_(ptr)
+    };
+
+    ptr::null_mut()
+}
+
+/// A builder for registering a new fundamental type.
+///
+/// ```
+/// use glib::subclass::fundamental::{FundamentalType, ValueTableHooks};
+///
+/// struct Hooks;
+///
+/// unsafe impl ValueTableHooks for Hooks {
+///     unsafe fn ref_(ptr: glib::glib_sys::gpointer) -> glib::glib_sys::gpointer {
+///         // Increase the reference count of the instance here
+///         ptr
+///     }
+///
+///     unsafe fn unref(_ptr: glib::glib_sys::gpointer) {
+///         // Decrease the reference count of the instance here
+///     }
+/// }
+///
+/// let type_ = FundamentalType::new("MyMiniObject")
+///     .value_table::<Hooks>()
+///     .register();
+/// assert_eq!(type_.parent(), None);
+/// ```
+pub struct FundamentalType {
+    name: String,
+    flags: TypeFlags,
+    fundamental_flags: TypeFundamentalFlags,
+    info: gobject_sys::GTypeInfo,
+    value_table: Option<gobject_sys::GTypeValueTable>,
+}
+
+impl FundamentalType {
+    /// Creates a new builder for a fundamental type called `name`.
+    ///
+    /// This name must be unique in the whole process.
+    pub fn new(name: &str) -> Self {
+        FundamentalType {
+            name: name.to_string(),
+            flags: TypeFlags::empty(),
+            fundamental_flags: TypeFundamentalFlags::empty(),
+            info: unsafe { mem::zeroed() },
+            value_table: None,
+        }
+    }
+
+    /// Sets the flags of the type, e.g. `TypeFlags::ABSTRACT`.
+    pub fn flags(mut self, flags: TypeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the fundamental flags of the type.
+    ///
+    /// `TypeFundamentalFlags::CLASSED` and `TypeFundamentalFlags::INSTANTIATABLE` are added
+    /// automatically by [`class`](#method.class) and [`instance`](#method.instance).
+    pub fn fundamental_flags(mut self, flags: TypeFundamentalFlags) -> Self {
+        self.fundamental_flags = flags;
+        self
+    }
+
+    /// Makes the type classed, with `C` as class struct and `class_init` called for
+    /// initializing it.
+    ///
+    /// # Safety
+    ///
+    /// `C` must be `#[repr(C)]` with `GTypeClass` as its first field, and `class_init` must
+    /// be safe to call with a zero-initialized `C`.
+    pub unsafe fn class<C>(mut self, class_init: gobject_sys::GClassInitFunc) -> Self {
+        assert!(mem::size_of::<C>() >= mem::size_of::<gobject_sys::GTypeClass>());
+        self.fundamental_flags |= TypeFundamentalFlags::CLASSED;
+        self.info.class_size = mem::size_of::<C>() as u16;
+        self.info.class_init = class_init;
+        self
+    }
+
+    /// Makes the type instantiatable, with `I` as instance struct and `instance_init` called
+    /// for initializing new instances.
+    ///
+    /// # Safety
+    ///
+    /// `I` must be `#[repr(C)]` with `GTypeInstance` as its first field, and `instance_init`
+    /// must be safe to call with a zero-initialized `I`.
+    pub unsafe fn instance<I>(mut self, instance_init: gobject_sys::GInstanceInitFunc) -> Self {
+        assert!(mem::size_of::<I>() >= mem::size_of::<gobject_sys::GTypeInstance>());
+        self.fundamental_flags |= TypeFundamentalFlags::INSTANTIATABLE;
+        self.info.instance_size = mem::size_of::<I>() as u16;
+        self.info.instance_init = instance_init;
+        self
+    }
+
+    /// Allows storing instances of the type in a `Value`, with `V` managing their references.
+    pub fn value_table<V: ValueTableHooks>(mut self) -> Self {
+        self.value_table = Some(gobject_sys::GTypeValueTable {
+            value_init: Some(value_init),
+            value_free: Some(value_free::<V>),
+            value_copy: Some(value_copy::<V>),
+            value_peek_pointer: Some(value_peek_pointer),
+            collect_format: b"p\0".as_ptr() as *const _,
+            collect_value: Some(collect_value::<V>),
+            lcopy_format: b"p\0".as_ptr() as *const _,
+            lcopy_value: Some(lcopy_value::<V>),
+        });
+        self
+    }
+
+    /// Registers the type.
+    ///
+    /// This must be called only once and will panic on a second call, or if there are no
+    /// fundamental type IDs left.
+    pub fn register(mut self) -> Type {
+        unsafe {
+            let type_name = CString::new(self.name).unwrap();
+            if gobject_sys::g_type_from_name(type_name.as_ptr()) != gobject_sys::G_TYPE_INVALID {
+                panic!(
+                    "Type {} has already been registered",
+                    type_name.to_str().unwrap()
+                );
+            }
+
+            let type_id = gobject_sys::g_type_fundamental_next();
+            if type_id == gobject_sys::G_TYPE_INVALID {
+                panic!("No fundamental type IDs left");
+            }
+
+            // The value table is copied by GLib, so it only has to live until registration
+            self.info.value_table = self
+                .value_table
+                .as_ref()
+                .map_or(ptr::null(), |value_table| value_table as *const _);
+            let fundamental_info = gobject_sys::GTypeFundamentalInfo {
+                type_flags: self.fundamental_flags.to_glib(),
+            };
+
+            from_glib(gobject_sys::g_type_register_fundamental(
+                type_id,
+                type_name.as_ptr(),
+                &self.info,
+                &fundamental_info,
+                self.flags.to_glib(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use Value;
+
+    struct RefCounted {
+        ref_count: AtomicUsize,
+    }
+
+    struct Hooks;
+
+    unsafe impl ValueTableHooks for Hooks {
+        unsafe fn ref_(ptr: glib_sys::gpointer) -> glib_sys::gpointer {
+            (*(ptr as *const RefCounted))
+                .ref_count
+                .fetch_add(1, Ordering::SeqCst);
+            ptr
+        }
+
+        unsafe fn unref(ptr: glib_sys::gpointer) {
+            (*(ptr as *const RefCounted))
+                .ref_count
+                .fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn register() {
+        let type_ = FundamentalType::new("GlibTestFundamental")
+            .value_table::<Hooks>()
+            .register();
+        assert_eq!(type_.parent(), None);
+        assert_eq!(Type::from_name("GlibTestFundamental"), Some(type_));
+        assert_eq!(type_.name(), "GlibTestFundamental");
+
+        let instance = RefCounted {
+            ref_count: AtomicUsize::new(1),
+        };
+        let mut value = Value::from_type(type_);
+        unsafe {
+            let ptr = &instance as *const RefCounted as glib_sys::gpointer;
+            (*value.to_glib_none_mut().0).data[0].v_pointer = Hooks::ref_(ptr);
+            assert_eq!(
+                gobject_sys::g_value_peek_pointer(value.to_glib_none().0),
+                ptr
+            );
+        }
+        assert_eq!(instance.ref_count.load(Ordering::SeqCst), 2);
+
+        let copy = value.clone();
+        assert_eq!(instance.ref_count.load(Ordering::SeqCst), 3);
+        drop(value);
+        drop(copy);
+        assert_eq!(instance.ref_count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -268,6 +268,11 @@ pub mod object;
 #[macro_use]
 pub mod boxed;
 
+pub mod fundamental;
+
+#[macro_use]
+pub mod test_object;
+
 pub mod prelude {
     //! Prelude that re-exports all important traits from this crate.
     pub use super::boxed::BoxedType;
@@ -279,6 +284,7 @@ pub mod prelude {
 }
 
 pub use self::boxed::register_boxed_type;
+pub use self::fundamental::FundamentalType;
 pub use self::interface::register_interface;
 pub use self::object::Property;
 pub use self::types::{
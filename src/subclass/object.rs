@@ -351,6 +351,22 @@ mod test {
         }
     }
 
+    glib_wrapper! {
+        @display
+        pub struct ChildObjectWrapper(ObjectSubclass<ChildObject, ChildObjectWrapperClass>);
+    }
+
+    glib_wrapper! {
+        @no_debug
+        pub struct ChildObjectNoDebug(ObjectSubclass<ChildObject, ChildObjectNoDebugClass>);
+    }
+
+    impl fmt::Debug for ChildObjectNoDebug {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("ChildObjectNoDebug")
+        }
+    }
+
     static PROPERTIES: [Property; 4] = [
         Property("name", |name| {
             ::ParamSpec::string(
@@ -423,7 +439,12 @@ mod test {
                 SignalFlags::RUN_LAST | SignalFlags::ACTION,
                 &[String::static_type()],
                 String::static_type(),
-                |_, args| {
+                |token, args| {
+                    assert_eq!(token.signal_id().name(), "change-name");
+                    assert_eq!(token.detail(), None);
+                    assert!(token.run_type().contains(SignalFlags::RUN_LAST));
+                    assert_eq!(*token.instance(), args[0].get::<Object>().unwrap().unwrap());
+
                     let obj = args[0]
                         .get::<Object>()
                         .expect("Failed to get args[0]")
@@ -528,6 +549,10 @@ mod test {
         fn type_init(type_: &mut subclass::InitializingType<Self>) {
             type_.add_prerequisite::<Object>();
         }
+
+        fn interface_init(&mut self) {
+            self.install_properties(&PROPERTIES[..1]);
+        }
     }
 
     // Usually this would be implemented on a Rust wrapper type defined
@@ -568,6 +593,121 @@ mod test {
         assert!(weak.upgrade().is_none());
     }
 
+    #[test]
+    fn test_interface_properties() {
+        let iface = ::InterfaceRef::from_type(DummyInterface::static_type())
+            .expect("Failed to get interface vtable");
+        assert_eq!(iface.get_type(), DummyInterface::static_type());
+
+        let pspecs = iface.list_properties();
+        assert_eq!(pspecs.len(), 1);
+        assert_eq!(pspecs[0].get_name(), "name");
+        assert!(iface.has_property("name", Some(String::static_type())));
+        assert!(iface.find_property("constructed").is_none());
+
+        assert!(::InterfaceRef::from_type(Object::static_type()).is_none());
+        assert!(::InterfaceRef::from_type(Type::BaseInterface).is_none());
+    }
+
+    #[test]
+    fn test_cached_properties() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+        let klass = obj.get_object_class();
+
+        let pspecs = klass.properties();
+        assert_eq!(pspecs, &klass.list_properties()[..]);
+        assert_eq!(pspecs.len(), PROPERTIES.len());
+        assert_eq!(pspecs.as_ptr(), klass.properties().as_ptr());
+    }
+
+    #[test]
+    fn test_weak_ptr() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+
+        // The object is only used on this thread
+        let weak = unsafe { obj.downgrade_ptr() };
+        assert_eq!(weak.upgrade().as_ref(), Some(&obj));
+        let weak_clone = weak.clone();
+        let mut weak_none = weak.clone();
+        unsafe {
+            weak_none.set(None);
+        }
+        assert_eq!(weak_none.upgrade(), None);
+        assert_eq!(obj.ref_count(), 1);
+
+        drop(obj);
+        assert_eq!(weak.upgrade(), None);
+        assert_eq!(weak_clone.upgrade(), None);
+    }
+
+    #[test]
+    fn test_wrapper_options() {
+        let obj = Object::new(ChildObject::get_type(), &[])
+            .expect("Object::new failed")
+            .downcast::<ChildObjectWrapper>()
+            .unwrap();
+
+        let display = obj.to_string();
+        assert!(display.contains("ChildObject"), "{}", display);
+        assert!(format!("{:?}", obj).starts_with("ChildObjectWrapper("));
+
+        let obj = obj
+            .upcast::<Object>()
+            .downcast::<ChildObjectNoDebug>()
+            .unwrap();
+        assert_eq!(format!("{:?}", obj), "ChildObjectNoDebug");
+    }
+
+    #[test]
+    fn test_raw_ptr() {
+        let obj = Object::new(ChildObject::get_type(), &[]).expect("Object::new failed");
+        assert_eq!(obj.ref_count(), 1);
+
+        let borrowed = unsafe { Object::from_glib_ptr_none(obj.as_ptr()) };
+        assert_eq!(obj.ref_count(), 2);
+        drop(borrowed);
+
+        let ptr = obj.into_glib_ptr();
+        let obj = unsafe { Object::from_glib_ptr_full(ptr) };
+        assert_eq!(obj.ref_count(), 1);
+        assert_eq!(obj.as_ptr(), ptr);
+    }
+
+    #[cfg(feature = "leak_tracker")]
+    #[test]
+    fn test_leak_report() {
+        // Only used by this test, so that an object of another test that is allocated at the
+        // same address isn't mistaken for ours
+        ::test_object! {
+            pub struct LeakTracked(LeakTrackedClass) {}
+        }
+
+        let find = |ptr| {
+            ::leak_report().into_iter().find(|tracked| {
+                tracked.as_ptr() == ptr && tracked.get_type() == LeakTracked::static_type()
+            })
+        };
+
+        let obj = LeakTracked::new();
+        let ptr = obj.as_ptr();
+        let tracked = find(ptr).expect("object not tracked");
+        assert_eq!(tracked.get_wrapper_count(), 1);
+
+        let obj_clone = obj.clone();
+        assert_eq!(find(ptr).unwrap().get_wrapper_count(), 2);
+        drop(obj_clone);
+        assert_eq!(find(ptr).unwrap().get_wrapper_count(), 1);
+
+        // Disposed objects are still reported until they are finalized
+        unsafe {
+            gobject_sys::g_object_run_dispose(ptr);
+        }
+        assert!(find(ptr).is_some());
+
+        drop(obj);
+        assert!(find(ptr).is_none());
+    }
+
     #[test]
     fn test_create_child_object() {
         let type_ = ChildObject::get_type();
@@ -663,6 +803,56 @@ mod test {
         assert!(obj.set_property("child", &child).is_ok());
     }
 
+    #[test]
+    fn test_property_by_pspec() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+        let pspec = obj
+            .find_property("name")
+            .expect("Failed to find 'name' property");
+
+        assert!(obj.set_property_by_pspec(&pspec, &"test").is_ok());
+        assert_eq!(
+            obj.get_property_by_pspec(&pspec)
+                .expect("Failed to get 'name' property")
+                .get::<&str>()
+                .expect("Failed to get str from 'name' property"),
+            Some("test")
+        );
+
+        let pspec = obj
+            .find_property("constructed")
+            .expect("Failed to find 'constructed' property");
+        assert_eq!(
+            obj.set_property_by_pspec(&pspec, &false)
+                .err()
+                .expect("Failed to set 'constructed' property")
+                .to_string(),
+            "property 'constructed' of type 'SimpleObject' is not writable",
+        );
+
+        let mut value = 0i32.to_value();
+        obj.get_property_into("name", &mut value)
+            .expect("Failed to get 'name' property");
+        assert_eq!(value.get::<&str>(), Ok(Some("test")));
+        obj.set_property("name", &"other").unwrap();
+        obj.get_property_into("name", &mut value)
+            .expect("Failed to get 'name' property");
+        assert_eq!(value.get::<&str>(), Ok(Some("other")));
+        obj.get_property_by_pspec_into(&pspec, &mut value)
+            .expect("Failed to get 'constructed' property");
+        assert_eq!(value.get::<bool>(), Ok(Some(true)));
+
+        let child = Object::new(ChildObject::get_type(), &[]).expect("Object::new failed");
+        assert_eq!(
+            child
+                .get_property_by_pspec(&pspec)
+                .err()
+                .expect("Got 'constructed' property of ChildObject")
+                .to_string(),
+            "property 'constructed' of type 'ChildObject' not found",
+        );
+    }
+
     #[test]
     fn test_signals() {
         use std::sync::atomic::{AtomicBool, Ordering};
@@ -707,6 +897,177 @@ mod test {
             .expect("Failed to get str from emit");
         assert_eq!(old_name, Some("old-name".to_string()));
         assert!(name_changed_triggered.load(Ordering::Relaxed));
+
+        // The arguments are still valid after being borrowed for the emission
+        let args = ["new-name".to_value()];
+        let old_name = obj
+            .emit_generic("change-name", &args)
+            .expect("Failed to emit")
+            .expect("Failed to get value from emit")
+            .get::<String>()
+            .expect("Failed to get str from emit");
+        assert_eq!(old_name, Some("new-name".to_string()));
+        assert_eq!(args[0].get::<&str>(), Ok(Some("new-name")));
+    }
+
+    #[test]
+    fn test_connect_after() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let order_clone = order.clone();
+        obj.connect_local_after("name-changed", move |_| {
+            order_clone.borrow_mut().push("after");
+            None
+        })
+        .expect("Failed to connect on 'name-changed'");
+        let order_clone = order.clone();
+        obj.connect_local("name-changed", false, move |_| {
+            order_clone.borrow_mut().push("before");
+            None
+        })
+        .expect("Failed to connect on 'name-changed'");
+
+        obj.set_property("name", &"new-name")
+            .expect("Failed to set 'name' property");
+        assert_eq!(*order.borrow(), vec!["before", "after"]);
+    }
+
+    #[test]
+    fn test_connect_detailed() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+
+        let (signal_id, details) =
+            ::SignalId::parse_name("notify::name", obj.get_type(), false).unwrap();
+        assert_eq!(
+            signal_id,
+            ::SignalId::lookup("notify", obj.get_type()).unwrap()
+        );
+        assert_eq!(signal_id.name(), "notify");
+        assert_eq!(details, Some(::Quark::from_string("name")));
+
+        let count = Rc::new(Cell::new(0));
+        let count_clone = count.clone();
+        obj.connect_local_detailed(signal_id, details, false, move |_| {
+            count_clone.set(count_clone.get() + 1);
+            None
+        })
+        .expect("Failed to connect on 'notify::name'");
+
+        obj.notify("name");
+        obj.notify("construct-name");
+        assert_eq!(count.get(), 1);
+
+        // Details are only accepted for detailed signals, and invalid ids are rejected
+        let name_changed = ::SignalId::lookup("name-changed", obj.get_type()).unwrap();
+        assert!(obj
+            .connect_local_detailed(name_changed, details, false, |_| None)
+            .is_err());
+        let invalid: ::SignalId = from_glib(::std::u32::MAX);
+        assert!(obj
+            .connect_local_detailed(invalid, None, false, |_| None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_stop_signal_emission_by_id() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+        let signal_id = ::SignalId::lookup("name-changed", obj.get_type()).unwrap();
+
+        let obj_weak = obj.downgrade();
+        obj.connect_local("name-changed", false, move |_| {
+            let obj = obj_weak.upgrade().unwrap();
+            obj.stop_signal_emission_by_id(signal_id, None);
+            None
+        })
+        .expect("Failed to connect on 'name-changed'");
+
+        let called = Rc::new(Cell::new(false));
+        let called_clone = called.clone();
+        obj.connect_local("name-changed", false, move |_| {
+            called_clone.set(true);
+            None
+        })
+        .expect("Failed to connect on 'name-changed'");
+
+        obj.set_property("name", &"new-name")
+            .expect("Failed to set 'name' property");
+        assert!(!called.get());
+    }
+
+    #[test]
+    fn test_signal_stream() {
+        use futures_util::stream::StreamExt;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+
+        let mut stream = obj
+            .signal_stream("name-changed")
+            .expect("Failed to connect on 'name-changed'");
+
+        obj.set_property("name", &"first")
+            .expect("Failed to set 'name' property");
+        obj.set_property("name", &"second")
+            .expect("Failed to set 'name' property");
+
+        let c = ::MainContext::new();
+        for expected in &["first", "second"] {
+            let args = c.block_on(stream.next()).expect("Stream ended");
+            assert_eq!(args[0].get::<Object>(), Ok(Some(obj.clone())));
+            assert_eq!(args[1].get::<&str>(), Ok(Some(*expected)));
+        }
+
+        let signal_id = ::SignalId::lookup("name-changed", obj.get_type()).unwrap();
+        let has_handler = |obj: &Object| unsafe {
+            from_glib::<_, bool>(gobject_sys::g_signal_has_handler_pending(
+                obj.to_glib_none().0,
+                signal_id.to_glib(),
+                0,
+                glib_sys::GTRUE,
+            ))
+        };
+        assert!(has_handler(&obj));
+
+        // Dropping the stream disconnects the handler again
+        drop(stream);
+        assert!(!has_handler(&obj));
+        obj.set_property("name", &"third")
+            .expect("Failed to set 'name' property");
+    }
+
+    #[test]
+    fn test_wait_for_notify() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).expect("Object::new failed");
+
+        assert!(obj.wait_for_notify("does-not-exist").is_err());
+
+        let future = obj
+            .wait_for_notify("name")
+            .expect("Failed to connect on 'notify::name'");
+
+        obj.set_property("name", &"first")
+            .expect("Failed to set 'name' property");
+        obj.set_property("name", &"second")
+            .expect("Failed to set 'name' property");
+
+        let c = ::MainContext::new();
+        let value = c.block_on(future).expect("Object was finalized");
+        assert_eq!(value.get::<&str>(), Ok(Some("first")));
+
+        let future = obj
+            .wait_for_notify("name")
+            .expect("Failed to connect on 'notify::name'");
+        drop(obj);
+        assert!(c.block_on(future).is_none());
     }
 
     #[test]
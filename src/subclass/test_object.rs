@@ -0,0 +1,337 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Module for quickly defining dummy `GObject` subclasses in tests.
+//!
+//! See [`test_object!`](../../macro.test_object.html) for details.
+
+use libc::{c_long, c_ulong};
+use {EnumClass, EnumValue, ParamFlags, ParamSpec, Type, Value};
+
+#[doc(hidden)]
+pub fn param_spec(name: &str, type_: Type) -> ParamSpec {
+    let flags = ParamFlags::READWRITE;
+
+    match type_ {
+        Type::Bool => ParamSpec::boolean(name, name, name, false, flags),
+        Type::I8 => ParamSpec::char(name, name, name, ::std::i8::MIN, ::std::i8::MAX, 0, flags),
+        Type::U8 => ParamSpec::uchar(name, name, name, ::std::u8::MIN, ::std::u8::MAX, 0, flags),
+        Type::I32 => ParamSpec::int(name, name, name, ::std::i32::MIN, ::std::i32::MAX, 0, flags),
+        Type::U32 => ParamSpec::uint(name, name, name, ::std::u32::MIN, ::std::u32::MAX, 0, flags),
+        Type::ILong => ParamSpec::long(
+            name,
+            name,
+            name,
+            c_long::min_value(),
+            c_long::max_value(),
+            0,
+            flags,
+        ),
+        Type::ULong => ParamSpec::ulong(
+            name,
+            name,
+            name,
+            c_ulong::min_value(),
+            c_ulong::max_value(),
+            0,
+            flags,
+        ),
+        Type::I64 => ParamSpec::int64(name, name, name, ::std::i64::MIN, ::std::i64::MAX, 0, flags),
+        Type::U64 => {
+            ParamSpec::uint64(name, name, name, ::std::u64::MIN, ::std::u64::MAX, 0, flags)
+        }
+        Type::F32 => ParamSpec::float(
+            name,
+            name,
+            name,
+            ::std::f32::MIN,
+            ::std::f32::MAX,
+            0.0,
+            flags,
+        ),
+        Type::F64 => ParamSpec::double(
+            name,
+            name,
+            name,
+            ::std::f64::MIN,
+            ::std::f64::MAX,
+            0.0,
+            flags,
+        ),
+        Type::String => ParamSpec::string(name, name, name, None, flags),
+        t if t.is_a(&Type::BaseEnum) => {
+            ParamSpec::enum_(name, name, name, t, first_enum_value(t).unwrap_or(0), flags)
+        }
+        t if t.is_a(&Type::BaseFlags) => ParamSpec::flags(name, name, name, t, 0, flags),
+        t if t.is_a(&Type::BaseBoxed) => ParamSpec::boxed(name, name, name, t, flags),
+        t if t.is_a(&Type::BaseObject) => ParamSpec::object(name, name, name, t, flags),
+        t if t.is_a(&Type::BaseParamSpec) => ParamSpec::param(name, name, name, t, flags),
+        _ => panic!("Unsupported property type {}", type_),
+    }
+}
+
+#[doc(hidden)]
+pub fn default_value(type_: Type) -> Value {
+    if type_.is_a(&Type::BaseEnum) {
+        if let Some(value) = first_enum_value(type_) {
+            return EnumClass::new(type_).unwrap().to_value(value).unwrap();
+        }
+    }
+
+    Value::from_type(type_)
+}
+
+// 0 is not necessarily a valid value of the enum, so the first value is used as default instead
+fn first_enum_value(type_: Type) -> Option<i32> {
+    EnumClass::new(type_).and_then(|class| class.get_values().first().map(EnumValue::get_value))
+}
+
+/// Defines and registers a dummy `GObject` subclass for tests.
+///
+/// The subclass directly derives from `glib::Object`, and for the given wrapper type and class
+/// type names the same items as with [`glib_wrapper!`](macro.glib_wrapper.html) are generated,
+/// together with a `new()` function for creating new instances. Every property is readable and
+/// writable and simply stores its value, signals have no class handler. The registered type name
+/// is derived from the module path of the invocation and the wrapper type's name so that it is
+/// unique within the process.
+///
+/// Supported property types are all fundamental types with the exception of pointers and
+/// variants, and all enum, flags, boxed and object types.
+///
+/// ```
+/// #[macro_use]
+/// extern crate glib;
+/// use glib::prelude::*;
+///
+/// test_object! {
+///     pub struct Dummy(DummyClass) {
+///         properties {
+///             "name": String,
+///             "count": i32,
+///         }
+///         signals {
+///             "changed"(i32),
+///             "query"() -> bool,
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let obj = Dummy::new();
+///     obj.set_property("count", &3i32).unwrap();
+///     assert_eq!(obj.get_property("count").unwrap().get_some::<i32>(), Ok(3));
+///     assert!(obj.upcast_ref::<glib::Object>().is::<Dummy>());
+/// }
+/// ```
+#[macro_export]
+macro_rules! test_object {
+    (@ret_type) => {
+        $crate::Type::Unit
+    };
+
+    (@ret_type $ret_type:ty) => {
+        <$ret_type as $crate::StaticType>::static_type()
+    };
+
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident($rust_class_name:ident) {
+            $(properties {
+                $($prop_name:literal: $prop_type:ty),* $(,)?
+            })?
+            $(signals {
+                $($signal_name:literal($($arg_type:ty),*) $(-> $ret_type:ty)?),* $(,)?
+            })?
+        }
+    ) => {
+        $crate::glib_object_wrapper!(@object [$($attr)*] $name, $crate::gobject_sys::GObject, $crate::gobject_sys::GObjectClass, $rust_class_name,
+            @get_type {
+                struct Imp {
+                    values: ::std::cell::RefCell<Vec<$crate::Value>>,
+                }
+
+                impl $crate::subclass::types::ObjectSubclass for Imp {
+                    const NAME: &'static str = stringify!($name);
+                    type ParentType = $crate::Object;
+                    type Instance = $crate::subclass::simple::InstanceStruct<Self>;
+                    type Class = $crate::subclass::simple::ClassStruct<Self>;
+
+                    fn type_data() -> ::std::ptr::NonNull<$crate::subclass::TypeData> {
+                        static mut DATA: $crate::subclass::TypeData = $crate::subclass::TypeData {
+                            type_: $crate::Type::Invalid,
+                            parent_class: ::std::ptr::null_mut(),
+                            interface_data: ::std::ptr::null_mut(),
+                            private_offset: 0,
+                        };
+
+                        unsafe { ::std::ptr::NonNull::new_unchecked(&mut DATA) }
+                    }
+
+                    fn get_type() -> $crate::Type {
+                        static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+
+                        ONCE.call_once(|| {
+                            let type_name = format!(
+                                "{}-{}",
+                                module_path!().replace("::", "-"),
+                                stringify!($name)
+                            );
+                            $crate::subclass::types::register_type_with_name::<Self>(&type_name);
+                        });
+
+                        unsafe {
+                            let data = Self::type_data();
+                            let type_ = data.as_ref().get_type();
+                            assert_ne!(type_, $crate::Type::Invalid);
+
+                            type_
+                        }
+                    }
+
+                    fn class_init(klass: &mut Self::Class) {
+                        use $crate::subclass::object::ObjectClassSubclassExt;
+
+                        let properties: &[$crate::subclass::Property] = &[$($(
+                            $crate::subclass::Property($prop_name, |name| {
+                                $crate::subclass::test_object::param_spec(
+                                    name,
+                                    <$prop_type as $crate::StaticType>::static_type(),
+                                )
+                            }),
+                        )*)?];
+                        klass.install_properties(properties);
+
+                        $($(
+                            klass.add_signal(
+                                $signal_name,
+                                $crate::SignalFlags::RUN_LAST,
+                                &[$(<$arg_type as $crate::StaticType>::static_type()),*],
+                                $crate::test_object!(@ret_type $($ret_type)?),
+                            );
+                        )*)?
+                    }
+
+                    fn new() -> Self {
+                        Imp {
+                            values: ::std::cell::RefCell::new(vec![$($(
+                                $crate::subclass::test_object::default_value(
+                                    <$prop_type as $crate::StaticType>::static_type(),
+                                ),
+                            )*)?]),
+                        }
+                    }
+                }
+
+                impl $crate::subclass::object::ObjectImpl for Imp {
+                    fn set_property(&self, _obj: &$crate::Object, id: usize, value: &$crate::Value) {
+                        self.values.borrow_mut()[id] = value.clone();
+                    }
+
+                    fn get_property(&self, _obj: &$crate::Object, id: usize) -> Result<$crate::Value, ()> {
+                        Ok(self.values.borrow()[id].clone())
+                    }
+                }
+
+                $crate::translate::ToGlib::to_glib(
+                    &<Imp as $crate::subclass::types::ObjectSubclass>::get_type(),
+                )
+            },
+            @extends [], @implements [], @options []);
+
+        impl $name {
+            /// Creates a new instance with default property values.
+            #[allow(clippy::new_without_default)]
+            pub fn new() -> Self {
+                $crate::object::Cast::downcast(
+                    $crate::Object::new(<$name as $crate::StaticType>::static_type(), &[])
+                        .expect("Failed to create test object"),
+                )
+                .unwrap()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use Object;
+
+    test_object! {
+        pub struct TestDummy(TestDummyClass) {
+            properties {
+                "name": String,
+                "count": i32,
+                "enabled": bool,
+                "other": Object,
+            }
+            signals {
+                "changed"(i32),
+                "query"() -> bool,
+            }
+        }
+    }
+
+    test_object! {
+        pub struct TestEmpty(TestEmptyClass) {}
+    }
+
+    #[test]
+    fn test_properties() {
+        let obj = TestDummy::new();
+        assert!(TestDummy::static_type().name().ends_with("-TestDummy"));
+        assert_ne!(TestDummy::static_type(), TestEmpty::static_type());
+
+        assert_eq!(obj.get_property("name").unwrap().get::<String>(), Ok(None));
+        assert_eq!(obj.get_property("count").unwrap().get_some::<i32>(), Ok(0));
+
+        obj.set_property("name", &"test").unwrap();
+        obj.set_property("count", &42i32).unwrap();
+        obj.set_property("enabled", &true).unwrap();
+        obj.set_property("other", &TestEmpty::new()).unwrap();
+        assert_eq!(
+            obj.get_property("name").unwrap().get::<&str>(),
+            Ok(Some("test"))
+        );
+        assert_eq!(obj.get_property("count").unwrap().get_some::<i32>(), Ok(42));
+        assert_eq!(
+            obj.get_property("enabled").unwrap().get_some::<bool>(),
+            Ok(true)
+        );
+        assert!(obj
+            .get_property("other")
+            .unwrap()
+            .get::<Object>()
+            .unwrap()
+            .unwrap()
+            .is::<TestEmpty>());
+
+        assert!(obj.set_property("count", &"wrong type").is_err());
+        assert!(TestEmpty::new().get_property("name").is_err());
+    }
+
+    #[test]
+    fn test_signals() {
+        let obj = TestDummy::new();
+        let count = Rc::new(Cell::new(0));
+
+        let count_clone = count.clone();
+        obj.connect_local("changed", false, move |args| {
+            count_clone.set(args[1].get_some::<i32>().unwrap());
+            None
+        })
+        .unwrap();
+        obj.connect_local("query", false, |_| Some(true.to_value()))
+            .unwrap();
+
+        obj.emit("changed", &[&3i32]).unwrap();
+        assert_eq!(count.get(), 3);
+        assert_eq!(
+            obj.emit("query", &[]).unwrap().unwrap().get_some::<bool>(),
+            Ok(true)
+        );
+    }
+}
@@ -459,6 +459,16 @@ unsafe extern "C" fn finalize<T: ObjectSubclass>(obj: *mut gobject_sys::GObject)
 ///
 /// [`glib_object_subclass!`]: ../../macro.glib_object_subclass.html
 pub fn register_type<T: ObjectSubclass>() -> Type
+where
+    <<T as ObjectSubclass>::ParentType as ObjectType>::RustClassType: IsSubclassable<T>,
+{
+    register_type_with_name::<T>(T::NAME)
+}
+
+// Like `register_type()` but with a type name that is only known at runtime, used by the
+// `test_object!` macro to create unique type names
+#[doc(hidden)]
+pub fn register_type_with_name<T: ObjectSubclass>(type_name: &str) -> Type
 where
     <<T as ObjectSubclass>::ParentType as ObjectType>::RustClassType: IsSubclassable<T>,
 {
@@ -475,7 +485,7 @@ where
     unsafe {
         use std::ffi::CString;
 
-        let type_name = CString::new(T::NAME).unwrap();
+        let type_name = CString::new(type_name).unwrap();
         if gobject_sys::g_type_from_name(type_name.as_ptr()) != gobject_sys::G_TYPE_INVALID {
             panic!(
                 "Type {} has already been registered",
@@ -540,6 +550,11 @@ pub(crate) unsafe fn add_signal(
 pub struct SignalInvocationHint(gobject_sys::GSignalInvocationHint);
 
 impl SignalInvocationHint {
+    /// Returns the id of the signal that is emitted.
+    pub fn signal_id(&self) -> ::SignalId {
+        from_glib(self.0.signal_id)
+    }
+
     pub fn detail(&self) -> ::Quark {
         from_glib(self.0.detail)
     }
@@ -552,6 +567,7 @@ impl SignalInvocationHint {
 impl fmt::Debug for SignalInvocationHint {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         f.debug_struct("SignalInvocationHint")
+            .field("signal_id", &self.signal_id())
             .field("detail", &self.detail())
             .field("run_type", &self.run_type())
             .finish()
@@ -605,10 +621,46 @@ pub(crate) unsafe fn add_signal_with_accumulator<F>(
 
 pub struct SignalClassHandlerToken(*mut gobject_sys::GTypeInstance);
 
+impl SignalClassHandlerToken {
+    /// Returns the instance the signal is emitted on.
+    pub fn instance(&self) -> Borrowed<::Object> {
+        unsafe { ::Object::from_glib_borrow(self.0 as *mut gobject_sys::GObject) }
+    }
+
+    /// Returns the id of the signal that is currently emitted.
+    pub fn signal_id(&self) -> ::SignalId {
+        self.invocation_hint().signal_id()
+    }
+
+    /// Returns the detail of the signal that is currently emitted, if any.
+    pub fn detail(&self) -> Option<::Quark> {
+        match self.invocation_hint().detail().to_glib() {
+            0 => None,
+            detail => Some(from_glib(detail)),
+        }
+    }
+
+    /// Returns the stage of the current emission, i.e. one of `SignalFlags::RUN_FIRST`,
+    /// `SignalFlags::RUN_LAST` or `SignalFlags::RUN_CLEANUP`.
+    pub fn run_type(&self) -> SignalFlags {
+        self.invocation_hint().run_type()
+    }
+
+    /// Returns the invocation hint of the current emission.
+    pub fn invocation_hint(&self) -> &SignalInvocationHint {
+        unsafe {
+            let ihint = gobject_sys::g_signal_get_invocation_hint(self.0 as *mut _);
+            assert!(!ihint.is_null());
+            &*(ihint as *const SignalInvocationHint)
+        }
+    }
+}
+
 impl fmt::Debug for SignalClassHandlerToken {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        f.debug_tuple("SignalClassHandlerToken")
-            .field(&unsafe { ::Object::from_glib_borrow(self.0 as *mut gobject_sys::GObject) })
+        f.debug_struct("SignalClassHandlerToken")
+            .field("instance", &*self.instance())
+            .field("invocation_hint", self.invocation_hint())
             .finish()
     }
 }
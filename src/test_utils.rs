@@ -0,0 +1,314 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Bindings for the GLib testing framework.
+//!
+//! This allows writing test programs that behave like the tests of C libraries based on GLib,
+//! e.g. for installed-tests, with the same command line options and TAP output. Such a test
+//! program has to be built without the default Rust test harness, i.e. with `harness = false`
+//! in its `[[test]]` section of `Cargo.toml`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #[macro_use]
+//! extern crate glib;
+//!
+//! use glib::test_utils::{self, TestSubprocessFlags};
+//! use glib::LogLevel;
+//!
+//! fn main() {
+//!     test_utils::init();
+//!
+//!     test_utils::add_func("/myapp/warning", || {
+//!         test_utils::expect_message(Some("myapp"), LogLevel::Warning, "*invalid*");
+//!         g_warning!("myapp", "invalid input");
+//!         g_test_assert_expected_messages!();
+//!     });
+//!
+//!     test_utils::add_func("/myapp/crash", || {
+//!         if test_utils::subprocess() {
+//!             panic!("crashed");
+//!         }
+//!         test_utils::trap_subprocess(None, 0, TestSubprocessFlags::empty());
+//!         g_test_trap_assert_failed!();
+//!         g_test_trap_assert_stderr!("*crashed*");
+//!     });
+//!
+//!     std::process::exit(test_utils::run());
+//! }
+//! ```
+
+use glib_sys;
+use libc::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use translate::*;
+use LogLevel;
+
+bitflags! {
+    /// Flags for [`trap_subprocess`](fn.trap_subprocess.html).
+    pub struct TestSubprocessFlags: u32 {
+        const INHERIT_STDIN = glib_sys::G_TEST_SUBPROCESS_INHERIT_STDIN;
+        const INHERIT_STDOUT = glib_sys::G_TEST_SUBPROCESS_INHERIT_STDOUT;
+        const INHERIT_STDERR = glib_sys::G_TEST_SUBPROCESS_INHERIT_STDERR;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for TestSubprocessFlags {
+    type GlibType = glib_sys::GTestSubprocessFlags;
+
+    fn to_glib(&self) -> glib_sys::GTestSubprocessFlags {
+        self.bits()
+    }
+}
+
+/// Initializes the testing framework with the command line arguments of the process.
+///
+/// This must be called once before any other function of this module. It also makes warnings
+/// and critical warnings fatal.
+pub fn init() {
+    let args = ::std::env::args_os().collect::<Vec<_>>();
+    unsafe {
+        let mut argc = args.len() as c_int;
+        // GLib keeps pointers to the arguments, so they are intentionally leaked here
+        let mut argv: *mut *mut c_char =
+            ToGlibContainerFromSlice::to_glib_full_from_slice(&args[..]);
+        glib_sys::g_test_init(&mut argc, &mut argv, ptr::null::<c_char>());
+    }
+}
+
+/// Adds `func` as test case with the given `path`, e.g. `"/myapp/feature"`.
+///
+/// A panic inside `func` marks the test case as failed.
+pub fn add_func<F: Fn() + 'static>(path: &str, func: F) {
+    unsafe extern "C" fn func_trampoline<F: Fn() + 'static>(data: glib_sys::gconstpointer) {
+        let func: &F = &*(data as *const F);
+        if panic::catch_unwind(AssertUnwindSafe(func)).is_err() {
+            glib_sys::g_test_fail();
+        }
+    }
+
+    unsafe extern "C" fn destroy_func<F: Fn() + 'static>(data: glib_sys::gpointer) {
+        let _ = Box::from_raw(data as *mut F);
+    }
+
+    unsafe {
+        glib_sys::g_test_add_data_func_full(
+            path.to_glib_none().0,
+            Box::into_raw(Box::new(func)) as glib_sys::gpointer,
+            Some(func_trampoline::<F>),
+            Some(destroy_func::<F>),
+        );
+    }
+}
+
+/// Runs all test cases selected on the command line and returns the exit code for the process.
+pub fn run() -> i32 {
+    unsafe { glib_sys::g_test_run() }
+}
+
+/// Expects a message with the given domain and level whose text matches `pattern`, with `*` and
+/// `?` as wildcards, to be logged next.
+///
+/// Such messages are not printed and are not fatal. Whether all expected messages were logged
+/// is checked with [`g_test_assert_expected_messages!`](../macro.g_test_assert_expected_messages.html).
+pub fn expect_message(log_domain: Option<&str>, log_level: LogLevel, pattern: &str) {
+    unsafe {
+        glib_sys::g_test_expect_message(
+            log_domain.to_glib_none().0,
+            log_level.to_glib(),
+            pattern.to_glib_none().0,
+        );
+    }
+}
+
+/// Runs the test case at `test_path`, or the current one if `None`, in a subprocess.
+///
+/// The subprocess is killed after `usec_timeout` microseconds unless that is 0. Afterwards its
+/// result can be checked with [`g_test_trap_assert_passed!`](../macro.g_test_trap_assert_passed.html)
+/// and related macros.
+pub fn trap_subprocess(test_path: Option<&str>, usec_timeout: u64, flags: TestSubprocessFlags) {
+    unsafe {
+        glib_sys::g_test_trap_subprocess(test_path.to_glib_none().0, usec_timeout, flags.to_glib());
+    }
+}
+
+/// Returns `true` if the last subprocess of [`trap_subprocess`](fn.trap_subprocess.html) passed.
+pub fn trap_has_passed() -> bool {
+    unsafe { from_glib(glib_sys::g_test_trap_has_passed()) }
+}
+
+/// Returns `true` if the last subprocess of [`trap_subprocess`](fn.trap_subprocess.html) was
+/// killed because of its timeout.
+pub fn trap_reached_timeout() -> bool {
+    unsafe { from_glib(glib_sys::g_test_trap_reached_timeout()) }
+}
+
+/// Returns `true` if the current process is a subprocess started by
+/// [`trap_subprocess`](fn.trap_subprocess.html).
+pub fn subprocess() -> bool {
+    unsafe { from_glib(glib_sys::g_test_subprocess()) }
+}
+
+/// Marks the current test case as failed without aborting it.
+pub fn fail() {
+    unsafe { glib_sys::g_test_fail() }
+}
+
+/// Returns `true` if the current test case has failed.
+pub fn failed() -> bool {
+    unsafe { from_glib(glib_sys::g_test_failed()) }
+}
+
+/// Marks the current test case as skipped, with an optional reason.
+pub fn skip(msg: Option<&str>) {
+    unsafe { glib_sys::g_test_skip(msg.to_glib_none().0) }
+}
+
+#[doc(hidden)]
+pub fn assert_expected_messages(file: &str, line: u32, func: &str) {
+    unsafe {
+        glib_sys::g_test_assert_expected_messages_internal(
+            ptr::null(),
+            file.to_glib_none().0,
+            line as c_int,
+            func.to_glib_none().0,
+        );
+    }
+}
+
+// The assertion flags of `g_test_trap_assertions()`, as used by the macros in gtestutils.h
+#[doc(hidden)]
+pub const TRAP_ASSERT_PASSED: u64 = 0;
+#[doc(hidden)]
+pub const TRAP_ASSERT_FAILED: u64 = 1;
+#[doc(hidden)]
+pub const TRAP_ASSERT_STDOUT: u64 = 2;
+#[doc(hidden)]
+pub const TRAP_ASSERT_STDOUT_UNMATCHED: u64 = 3;
+#[doc(hidden)]
+pub const TRAP_ASSERT_STDERR: u64 = 4;
+#[doc(hidden)]
+pub const TRAP_ASSERT_STDERR_UNMATCHED: u64 = 5;
+
+#[doc(hidden)]
+pub fn trap_assertions(
+    file: &str,
+    line: u32,
+    func: &str,
+    assertion_flags: u64,
+    pattern: Option<&str>,
+) {
+    unsafe {
+        glib_sys::g_test_trap_assertions(
+            ptr::null(),
+            file.to_glib_none().0,
+            line as c_int,
+            func.to_glib_none().0,
+            assertion_flags,
+            pattern.to_glib_none().0,
+        );
+    }
+}
+
+/// Asserts that all messages expected with
+/// [`test_utils::expect_message`](test_utils/fn.expect_message.html) were logged.
+#[macro_export]
+macro_rules! g_test_assert_expected_messages {
+    () => {
+        $crate::test_utils::assert_expected_messages(file!(), line!(), module_path!())
+    };
+}
+
+/// Asserts that the last subprocess of
+/// [`test_utils::trap_subprocess`](test_utils/fn.trap_subprocess.html) passed.
+#[macro_export]
+macro_rules! g_test_trap_assert_passed {
+    () => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_PASSED,
+            None,
+        )
+    };
+}
+
+/// Asserts that the last subprocess of
+/// [`test_utils::trap_subprocess`](test_utils/fn.trap_subprocess.html) failed.
+#[macro_export]
+macro_rules! g_test_trap_assert_failed {
+    () => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_FAILED,
+            None,
+        )
+    };
+}
+
+/// Asserts that the stdout of the last subprocess matches `pattern`, with `*` and `?` as
+/// wildcards.
+#[macro_export]
+macro_rules! g_test_trap_assert_stdout {
+    ($pattern:expr) => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_STDOUT,
+            Some($pattern),
+        )
+    };
+}
+
+/// Asserts that the stdout of the last subprocess does not match `pattern`, with `*` and `?` as
+/// wildcards.
+#[macro_export]
+macro_rules! g_test_trap_assert_stdout_unmatched {
+    ($pattern:expr) => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_STDOUT_UNMATCHED,
+            Some($pattern),
+        )
+    };
+}
+
+/// Asserts that the stderr of the last subprocess matches `pattern`, with `*` and `?` as
+/// wildcards.
+#[macro_export]
+macro_rules! g_test_trap_assert_stderr {
+    ($pattern:expr) => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_STDERR,
+            Some($pattern),
+        )
+    };
+}
+
+/// Asserts that the stderr of the last subprocess does not match `pattern`, with `*` and `?` as
+/// wildcards.
+#[macro_export]
+macro_rules! g_test_trap_assert_stderr_unmatched {
+    ($pattern:expr) => {
+        $crate::test_utils::trap_assertions(
+            file!(),
+            line!(),
+            module_path!(),
+            $crate::test_utils::TRAP_ASSERT_STDERR_UNMATCHED,
+            Some($pattern),
+        )
+    };
+}
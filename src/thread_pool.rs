@@ -6,7 +6,9 @@ use glib_sys;
 use translate::*;
 
 use futures_channel::oneshot;
+use once_cell::sync::Lazy;
 use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
 
 #[derive(Debug)]
@@ -169,6 +171,37 @@ impl Drop for ThreadPool {
     }
 }
 
+static BLOCKING_POOL: Lazy<ThreadPool> =
+    Lazy::new(|| ThreadPool::new_shared(None).expect("Failed to create shared thread pool"));
+
+/// Runs `func` on a thread pool shared by the whole process and returns a `Future` that
+/// resolves to its return value.
+///
+/// This allows offloading blocking or CPU-heavy work from a main context without blocking it.
+/// The `Future` can be awaited from any executor, e.g. from a future spawned on a main context.
+///
+/// If `func` panics, the panic is propagated when polling the returned `Future`.
+pub fn spawn_blocking<T, F>(func: F) -> impl Future<Output = T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    use futures_util::future::FutureExt;
+
+    let (sender, receiver) = oneshot::channel();
+
+    BLOCKING_POOL
+        .push(move || {
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(func)));
+        })
+        .expect("Failed to push to shared thread pool");
+
+    receiver.map(|res| match res.expect("Dropped before executing") {
+        Ok(res) => res,
+        Err(panic) => panic::resume_unwind(panic),
+    })
+}
+
 unsafe extern "C" fn spawn_func(func: glib_sys::gpointer, _data: glib_sys::gpointer) {
     let func: Box<Box<dyn FnOnce()>> = Box::from_raw(func as *mut _);
     func()
@@ -203,4 +236,12 @@ mod tests {
         let res = c.block_on(fut);
         assert!(res);
     }
+
+    #[test]
+    fn test_spawn_blocking() {
+        let c = ::MainContext::new();
+
+        let fut = spawn_blocking(|| (0..100u32).sum::<u32>());
+        assert_eq!(c.block_on(fut), 4950);
+    }
 }
@@ -0,0 +1,240 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Timestamps in the clock domains used by GLib.
+//!
+//! GLib APIs take and return times as microseconds in one of two clocks: the monotonic clock of
+//! [`get_monotonic_time`](../fn.get_monotonic_time.html), e.g. for source ready times or frame
+//! clocks, and the wall-clock time of [`get_real_time`](../fn.get_real_time.html).
+//! [`MonotonicTime`](struct.MonotonicTime.html) and [`RealTime`](struct.RealTime.html) wrap
+//! these so they can't be mixed up and support `Duration` arithmetic.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::MonotonicTime;
+//! use std::time::Duration;
+//!
+//! let start = MonotonicTime::now();
+//! let deadline = start + Duration::from_millis(100);
+//! assert_eq!(deadline.as_micros() - start.as_micros(), 100_000);
+//! assert_eq!(deadline - start, Duration::from_millis(100));
+//! ```
+
+use glib_sys;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use translate::*;
+
+macro_rules! define_time {
+    ($(#[$attr:meta])* $name:ident, $now:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(i64);
+
+        impl $name {
+            /// Returns the current time of this clock.
+            pub fn now() -> Self {
+                $name(unsafe { glib_sys::$now() })
+            }
+
+            /// Creates a timestamp from microseconds as used by the C API.
+            pub fn from_micros(micros: i64) -> Self {
+                $name(micros)
+            }
+
+            /// Returns the microseconds as used by the C API.
+            pub fn as_micros(self) -> i64 {
+                self.0
+            }
+
+            /// Returns the time elapsed since `earlier`, or a zero duration if `earlier` is
+            /// later than `self`.
+            pub fn duration_since(self, earlier: $name) -> Duration {
+                if self.0 > earlier.0 {
+                    Duration::from_micros(self.0.wrapping_sub(earlier.0) as u64)
+                } else {
+                    Duration::from_secs(0)
+                }
+            }
+
+            /// Returns the time elapsed since `self`, or a zero duration if `self` is in the
+            /// future.
+            pub fn elapsed(self) -> Duration {
+                $name::now().duration_since(self)
+            }
+
+            /// Returns `self + duration`, or `None` on overflow.
+            pub fn checked_add(self, duration: Duration) -> Option<$name> {
+                let micros = duration_to_micros(duration)?;
+                self.0.checked_add(micros).map($name)
+            }
+
+            /// Returns `self - duration`, or `None` on overflow.
+            pub fn checked_sub(self, duration: Duration) -> Option<$name> {
+                let micros = duration_to_micros(duration)?;
+                self.0.checked_sub(micros).map($name)
+            }
+        }
+
+        impl Add<Duration> for $name {
+            type Output = $name;
+
+            fn add(self, duration: Duration) -> $name {
+                self.checked_add(duration)
+                    .expect("overflow when adding duration to timestamp")
+            }
+        }
+
+        impl AddAssign<Duration> for $name {
+            fn add_assign(&mut self, duration: Duration) {
+                *self = *self + duration;
+            }
+        }
+
+        impl Sub<Duration> for $name {
+            type Output = $name;
+
+            fn sub(self, duration: Duration) -> $name {
+                self.checked_sub(duration)
+                    .expect("overflow when subtracting duration from timestamp")
+            }
+        }
+
+        impl SubAssign<Duration> for $name {
+            fn sub_assign(&mut self, duration: Duration) {
+                *self = *self - duration;
+            }
+        }
+
+        impl Sub<$name> for $name {
+            type Output = Duration;
+
+            fn sub(self, earlier: $name) -> Duration {
+                self.duration_since(earlier)
+            }
+        }
+
+        impl From<i64> for $name {
+            fn from(micros: i64) -> $name {
+                $name(micros)
+            }
+        }
+
+        impl From<$name> for i64 {
+            fn from(time: $name) -> i64 {
+                time.0
+            }
+        }
+
+        #[doc(hidden)]
+        impl ToGlib for $name {
+            type GlibType = i64;
+
+            fn to_glib(&self) -> i64 {
+                self.0
+            }
+        }
+
+        #[doc(hidden)]
+        impl FromGlib<i64> for $name {
+            fn from_glib(value: i64) -> Self {
+                $name(value)
+            }
+        }
+    };
+}
+
+fn duration_to_micros(duration: Duration) -> Option<i64> {
+    use std::convert::TryFrom;
+
+    i64::try_from(duration.as_micros()).ok()
+}
+
+define_time!(
+    /// A point in time of the monotonic clock used by the main loop, in microseconds.
+    ///
+    /// This is the clock domain of e.g.
+    /// [`Source::set_ready_time()`](struct.Source.html#method.set_ready_time) and
+    /// [`Source::get_time()`](struct.Source.html#method.get_time), whose microseconds convert
+    /// from and into this type with `From`. Its epoch is unspecified, so only differences
+    /// between two values are meaningful.
+    MonotonicTime,
+    g_get_monotonic_time
+);
+
+define_time!(
+    /// A point in wall-clock time, in microseconds since January 1, 1970 UTC.
+    ///
+    /// Unlike [`MonotonicTime`](struct.MonotonicTime.html) this can jump when the system clock
+    /// is changed, so it should not be used for measuring intervals.
+    RealTime,
+    g_get_real_time
+);
+
+impl From<RealTime> for SystemTime {
+    fn from(time: RealTime) -> SystemTime {
+        if time.0 >= 0 {
+            UNIX_EPOCH + Duration::from_micros(time.0 as u64)
+        } else {
+            UNIX_EPOCH - Duration::from_micros(time.0.wrapping_neg() as u64)
+        }
+    }
+}
+
+impl From<SystemTime> for RealTime {
+    /// Converts `time` to `RealTime`, saturating at the representable range.
+    fn from(time: SystemTime) -> RealTime {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => RealTime(duration_to_micros(since).unwrap_or(::std::i64::MAX)),
+            Err(err) => RealTime(
+                duration_to_micros(err.duration())
+                    .map(|micros| -micros)
+                    .unwrap_or(::std::i64::MIN),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotonic() {
+        let start = MonotonicTime::now();
+        let later = start + Duration::from_millis(1500);
+        assert_eq!(later.as_micros() - start.as_micros(), 1_500_000);
+        assert_eq!(later - start, Duration::from_millis(1500));
+        assert_eq!(start - later, Duration::from_secs(0));
+        assert_eq!(later - Duration::from_millis(1500), start);
+        assert!(start <= MonotonicTime::now());
+        assert!(later.elapsed() < Duration::from_millis(1500));
+
+        let mut time = MonotonicTime::from_micros(10);
+        time += Duration::from_micros(5);
+        time -= Duration::from_micros(3);
+        assert_eq!(time.to_glib(), 12);
+        assert_eq!(i64::from(time), 12);
+        assert_eq!(MonotonicTime::from(12), time);
+        assert_eq!(
+            MonotonicTime::from_micros(::std::i64::MAX).checked_add(Duration::from_micros(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn real() {
+        let now = RealTime::now();
+        let system_now = RealTime::from(SystemTime::now());
+        assert!(system_now - now < Duration::from_secs(10));
+
+        let time = RealTime::from_micros(-1_500_000);
+        assert_eq!(
+            SystemTime::from(time),
+            UNIX_EPOCH - Duration::from_millis(1500)
+        );
+        assert_eq!(RealTime::from(SystemTime::from(time)), time);
+    }
+}
@@ -70,13 +70,18 @@ use libc::{c_char, size_t};
 use std::char;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::hash::Hash;
 use std::mem;
+use std::num;
 #[cfg(not(windows))]
 use std::os::unix::prelude::*;
 use std::path::{Path, PathBuf};
 use std::ptr;
+use std::time::Duration;
 
 /// A pointer
 pub trait Ptr: Copy + 'static {
@@ -226,6 +231,76 @@ impl<T> std::ops::Deref for Borrowed<T> {
     }
 }
 
+unsafe fn slice_to_array<T: Copy>(t: &[T]) -> *mut glib_sys::GArray {
+    let arr = glib_sys::g_array_sized_new(
+        glib_sys::GFALSE,
+        glib_sys::GFALSE,
+        mem::size_of::<T>().into_glib(),
+        t.len().into_glib(),
+    );
+    glib_sys::g_array_append_vals(arr, t.as_ptr() as *const _, t.len().into_glib());
+    arr
+}
+
+macro_rules! impl_to_glib_container_from_slice_array {
+    ($name:ty) => {
+        impl<'a> ToGlibContainerFromSlice<'a, *mut glib_sys::GArray> for $name {
+            type Storage = Option<::Array<$name>>;
+
+            fn to_glib_none_from_slice(t: &'a [$name]) -> (*mut glib_sys::GArray, Self::Storage) {
+                let arr = unsafe { slice_to_array(t) };
+                (arr, Some(unsafe { from_glib_full(arr) }))
+            }
+
+            fn to_glib_container_from_slice(
+                t: &'a [$name],
+            ) -> (*mut glib_sys::GArray, Self::Storage) {
+                (unsafe { slice_to_array(t) }, None)
+            }
+
+            fn to_glib_full_from_slice(t: &[$name]) -> *mut glib_sys::GArray {
+                unsafe { slice_to_array(t) }
+            }
+        }
+    };
+}
+
+impl_to_glib_container_from_slice_array!(u8);
+impl_to_glib_container_from_slice_array!(i8);
+impl_to_glib_container_from_slice_array!(u16);
+impl_to_glib_container_from_slice_array!(i16);
+impl_to_glib_container_from_slice_array!(u32);
+impl_to_glib_container_from_slice_array!(i32);
+impl_to_glib_container_from_slice_array!(u64);
+impl_to_glib_container_from_slice_array!(i64);
+impl_to_glib_container_from_slice_array!(f32);
+impl_to_glib_container_from_slice_array!(f64);
+
+unsafe fn slice_to_byte_array(t: &[u8]) -> *mut glib_sys::GByteArray {
+    let arr = glib_sys::g_byte_array_sized_new(t.len().into_glib());
+    glib_sys::g_byte_array_append(arr, t.as_ptr(), t.len().into_glib());
+    arr
+}
+
+impl<'a> ToGlibContainerFromSlice<'a, *mut glib_sys::GByteArray> for u8 {
+    type Storage = Option<::ByteArray>;
+
+    fn to_glib_none_from_slice(t: &'a [u8]) -> (*mut glib_sys::GByteArray, Self::Storage) {
+        unsafe {
+            let arr = slice_to_byte_array(t);
+            (arr, Some(from_glib_full(arr)))
+        }
+    }
+
+    fn to_glib_container_from_slice(t: &'a [u8]) -> (*mut glib_sys::GByteArray, Self::Storage) {
+        (unsafe { slice_to_byte_array(t) }, None)
+    }
+
+    fn to_glib_full_from_slice(t: &[u8]) -> *mut glib_sys::GByteArray {
+        unsafe { slice_to_byte_array(t) }
+    }
+}
+
 /// Translate a simple type.
 pub trait ToGlib {
     type GlibType;
@@ -277,6 +352,37 @@ impl ToGlib for Ordering {
     }
 }
 
+macro_rules! impl_to_glib_non_zero {
+    ($name:ty, $glib:ty) => {
+        impl ToGlib for $name {
+            type GlibType = $glib;
+
+            #[inline]
+            fn to_glib(&self) -> $glib {
+                self.get()
+            }
+        }
+
+        impl ToGlib for Option<$name> {
+            type GlibType = $glib;
+
+            #[inline]
+            fn to_glib(&self) -> $glib {
+                self.map(<$name>::get).unwrap_or(0)
+            }
+        }
+    };
+}
+
+impl_to_glib_non_zero!(num::NonZeroU8, u8);
+impl_to_glib_non_zero!(num::NonZeroI8, i8);
+impl_to_glib_non_zero!(num::NonZeroU16, u16);
+impl_to_glib_non_zero!(num::NonZeroI16, i16);
+impl_to_glib_non_zero!(num::NonZeroU32, u32);
+impl_to_glib_non_zero!(num::NonZeroI32, i32);
+impl_to_glib_non_zero!(num::NonZeroU64, u64);
+impl_to_glib_non_zero!(num::NonZeroI64, i64);
+
 /// Provides the default pointer type to be used in some container conversions.
 ///
 /// It's `*mut c_char` for `String`, `*mut GtkButton` for `gtk::Button`, etc.
@@ -370,6 +476,33 @@ impl<'a, P: Ptr, T: ?Sized + ToGlibPtr<'a, P>> ToGlibPtr<'a, P> for &'a T {
     }
 }
 
+/// Maximum length of strings copied to the stack by [`with_c_str`](fn.with_c_str.html).
+const MAX_STACK_ALLOCATION: usize = 384;
+
+/// Calls `func` with a NUL-terminated copy of `s`.
+///
+/// Unlike `s.to_glib_none()`, strings shorter than a few hundred bytes are copied into a
+/// buffer on the stack and don't require a heap allocation. This can't be done as part of the
+/// `Stash` storage because the stash is moved after the pointer into it was created.
+///
+/// # Panics
+///
+/// Panics if `s` contains a NUL byte.
+pub fn with_c_str<R, F: FnOnce(*const c_char) -> R>(s: &str, func: F) -> R {
+    if s.len() < MAX_STACK_ALLOCATION {
+        assert!(
+            !s.as_bytes().contains(&0),
+            "str::with_c_str: unexpected '\\0' character"
+        );
+        let mut buf = [0u8; MAX_STACK_ALLOCATION];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        func(buf.as_ptr() as *const c_char)
+    } else {
+        let tmp = CString::new(s).expect("str::with_c_str: unexpected '\\0' character");
+        func(tmp.as_ptr())
+    }
+}
+
 impl<'a> ToGlibPtr<'a, *const c_char> for str {
     type Storage = CString;
 
@@ -525,6 +658,11 @@ impl<'a> ToGlibPtr<'a, *const c_char> for Path {
         let tmp = path_to_c(self);
         Stash(tmp.as_ptr(), tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *const c_char {
+        let tmp = path_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) as *const c_char }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *mut c_char> for Path {
@@ -535,6 +673,11 @@ impl<'a> ToGlibPtr<'a, *mut c_char> for Path {
         let tmp = path_to_c(self);
         Stash(tmp.as_ptr() as *mut c_char, tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *mut c_char {
+        let tmp = path_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *const c_char> for PathBuf {
@@ -545,6 +688,11 @@ impl<'a> ToGlibPtr<'a, *const c_char> for PathBuf {
         let tmp = path_to_c(self);
         Stash(tmp.as_ptr(), tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *const c_char {
+        let tmp = path_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) as *const c_char }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *mut c_char> for PathBuf {
@@ -555,6 +703,11 @@ impl<'a> ToGlibPtr<'a, *mut c_char> for PathBuf {
         let tmp = path_to_c(self);
         Stash(tmp.as_ptr() as *mut c_char, tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *mut c_char {
+        let tmp = path_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) }
+    }
 }
 
 impl GlibPtrDefault for Path {
@@ -573,6 +726,11 @@ impl<'a> ToGlibPtr<'a, *const c_char> for OsStr {
         let tmp = os_str_to_c(self);
         Stash(tmp.as_ptr(), tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *const c_char {
+        let tmp = os_str_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) as *const c_char }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *mut c_char> for OsStr {
@@ -583,6 +741,11 @@ impl<'a> ToGlibPtr<'a, *mut c_char> for OsStr {
         let tmp = os_str_to_c(self);
         Stash(tmp.as_ptr() as *mut c_char, tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *mut c_char {
+        let tmp = os_str_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *const c_char> for OsString {
@@ -593,6 +756,11 @@ impl<'a> ToGlibPtr<'a, *const c_char> for OsString {
         let tmp = os_str_to_c(self);
         Stash(tmp.as_ptr(), tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *const c_char {
+        let tmp = os_str_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) as *const c_char }
+    }
 }
 
 impl<'a> ToGlibPtr<'a, *mut c_char> for OsString {
@@ -603,6 +771,11 @@ impl<'a> ToGlibPtr<'a, *mut c_char> for OsString {
         let tmp = os_str_to_c(self);
         Stash(tmp.as_ptr() as *mut c_char, tmp)
     }
+    #[inline]
+    fn to_glib_full(&self) -> *mut c_char {
+        let tmp = os_str_to_c(self);
+        unsafe { glib_sys::g_strdup(tmp.as_ptr()) }
+    }
 }
 
 impl GlibPtrDefault for OsStr {
@@ -944,8 +1117,38 @@ impl<'a, P: Ptr, T: ToGlibContainerFromSlice<'a, P>> ToGlibPtr<'a, P> for [T] {
     }
 }
 
+/// Types that can be stored as keys or values of a `GHashTable`.
+pub trait HashTableValue: GlibPtrDefault {
+    /// Function used by the hash table to free the items it owns.
+    const DESTROY_FUNC: glib_sys::GDestroyNotify;
+}
+
+/// Types that can be used as keys of a `GHashTable`.
+///
+/// The hash and equality functions have to be consistent with the `Hash` and `Eq`
+/// implementations of the Rust type.
+pub trait HashTableKey: HashTableValue + Eq + Hash {
+    /// Function used by the hash table to hash keys.
+    const HASH_FUNC: glib_sys::GHashFunc;
+    /// Function used by the hash table to compare keys.
+    const EQUAL_FUNC: glib_sys::GEqualFunc;
+}
+
+impl HashTableValue for String {
+    const DESTROY_FUNC: glib_sys::GDestroyNotify = Some(glib_sys::g_free);
+}
+
+impl HashTableKey for String {
+    const HASH_FUNC: glib_sys::GHashFunc = Some(glib_sys::g_str_hash);
+    const EQUAL_FUNC: glib_sys::GEqualFunc = Some(glib_sys::g_str_equal);
+}
+
 #[allow(clippy::implicit_hasher)]
-impl<'a> ToGlibPtr<'a, *mut glib_sys::GHashTable> for HashMap<String, String> {
+impl<'a, K, V> ToGlibPtr<'a, *mut glib_sys::GHashTable> for HashMap<K, V>
+where
+    K: HashTableKey + ToGlibPtr<'a, <K as GlibPtrDefault>::GlibType>,
+    V: HashTableValue + ToGlibPtr<'a, <V as GlibPtrDefault>::GlibType>,
+{
     type Storage = HashTable;
 
     #[inline]
@@ -958,15 +1161,15 @@ impl<'a> ToGlibPtr<'a, *mut glib_sys::GHashTable> for HashMap<String, String> {
     fn to_glib_full(&self) -> *mut glib_sys::GHashTable {
         unsafe {
             let ptr = glib_sys::g_hash_table_new_full(
-                Some(glib_sys::g_str_hash),
-                Some(glib_sys::g_str_equal),
-                Some(glib_sys::g_free),
-                Some(glib_sys::g_free),
+                K::HASH_FUNC,
+                K::EQUAL_FUNC,
+                K::DESTROY_FUNC,
+                V::DESTROY_FUNC,
             );
             for (k, v) in self {
-                let k: *mut c_char = k.to_glib_full();
-                let v: *mut c_char = v.to_glib_full();
-                glib_sys::g_hash_table_insert(ptr, k as *mut _, v as *mut _);
+                let k: <K as GlibPtrDefault>::GlibType = k.to_glib_full();
+                let v: <V as GlibPtrDefault>::GlibType = v.to_glib_full();
+                glib_sys::g_hash_table_insert(ptr, k.to(), v.to());
             }
             ptr
         }
@@ -1003,7 +1206,7 @@ where
     #[inline]
     fn to_glib_none_from_slice(t: &'a [T]) -> (*mut glib_sys::GPtrArray, Self::Storage) {
         let stash_vec: Vec<_> = t.iter().map(ToGlibPtr::to_glib_none).collect();
-        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len() as _) };
+        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len().into_glib()) };
         unsafe {
             for stash in &stash_vec {
                 glib_sys::g_ptr_array_add(arr, Ptr::to(stash.0));
@@ -1015,7 +1218,7 @@ where
     #[inline]
     fn to_glib_container_from_slice(t: &'a [T]) -> (*mut glib_sys::GPtrArray, Self::Storage) {
         let stash_vec: Vec<_> = t.iter().map(ToGlibPtr::to_glib_none).collect();
-        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len() as _) };
+        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len().into_glib()) };
         unsafe {
             for stash in &stash_vec {
                 glib_sys::g_ptr_array_add(arr, Ptr::to(stash.0));
@@ -1026,7 +1229,7 @@ where
 
     #[inline]
     fn to_glib_full_from_slice(t: &[T]) -> *mut glib_sys::GPtrArray {
-        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len() as _) };
+        let arr = unsafe { glib_sys::g_ptr_array_sized_new(t.len().into_glib()) };
         unsafe {
             for ptr in t.iter().map(ToGlibPtr::to_glib_full) {
                 glib_sys::g_ptr_array_add(arr, Ptr::to(ptr));
@@ -1074,6 +1277,90 @@ pub fn from_glib<G, T: FromGlib<G>>(val: G) -> T {
     FromGlib::from_glib(val)
 }
 
+/// Checked conversion of an integer to a GLib integer type like `gsize`, `gssize`, `guint`
+/// or `gulong`.
+///
+/// Unlike an `as` cast, this never silently truncates, e.g. a `usize` length that doesn't fit
+/// into a `guint` on 64-bit platforms.
+pub trait IntoGlib<G> {
+    /// Converts `self` to `G`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not representable as `G`.
+    fn into_glib(self) -> G;
+}
+
+impl<T: Copy + fmt::Display, G: TryFrom<T>> IntoGlib<G> for T {
+    #[inline]
+    fn into_glib(self) -> G {
+        match G::try_from(self) {
+            Ok(val) => val,
+            Err(_) => panic!("Integer {} out of range for the GLib type", self),
+        }
+    }
+}
+
+/// Conversion of a `Duration` to the integer time units taken by GLib APIs.
+pub trait DurationIntoGlib {
+    /// Converts to milliseconds, as taken by e.g. `g_timeout_add()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of milliseconds is not representable as `G`.
+    fn into_glib_millis<G: TryFrom<u128>>(self) -> G;
+
+    /// Converts to microseconds, as taken by e.g. `g_usleep()` or as a `GTimeSpan`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of microseconds is not representable as `G`.
+    fn into_glib_micros<G: TryFrom<u128>>(self) -> G;
+}
+
+impl DurationIntoGlib for Duration {
+    #[inline]
+    fn into_glib_millis<G: TryFrom<u128>>(self) -> G {
+        self.as_millis().into_glib()
+    }
+
+    #[inline]
+    fn into_glib_micros<G: TryFrom<u128>>(self) -> G {
+        self.as_micros().into_glib()
+    }
+}
+
+/// Checked conversion of a GLib integer type like `gsize`, `gssize`, `guint` or `gulong`
+/// to a Rust integer.
+pub trait TryFromGlib<G>: Sized {
+    type Error;
+
+    /// Converts `val` to `Self`, failing if it is not representable as `Self`.
+    fn try_from_glib(val: G) -> Result<Self, Self::Error>;
+}
+
+impl<G, T: TryFrom<G>> TryFromGlib<G> for T {
+    type Error = <T as TryFrom<G>>::Error;
+
+    #[inline]
+    fn try_from_glib(val: G) -> Result<T, Self::Error> {
+        T::try_from(val)
+    }
+}
+
+// Converts a container length or element size coming from GLib, panicking instead of
+// truncating if it doesn't fit into a `usize`
+#[inline]
+fn len_from_glib<G: Copy + fmt::Display>(len: G) -> usize
+where
+    usize: TryFromGlib<G>,
+{
+    match usize::try_from_glib(len) {
+        Ok(len) => len,
+        Err(_) => panic!("GLib length {} out of range for usize", len),
+    }
+}
+
 impl FromGlib<glib_sys::gboolean> for bool {
     #[inline]
     fn from_glib(val: glib_sys::gboolean) -> bool {
@@ -1105,6 +1392,34 @@ impl FromGlib<u32> for Option<char> {
     }
 }
 
+macro_rules! impl_from_glib_non_zero {
+    ($name:ty, $glib:ty) => {
+        /// Panics if `val` is zero.
+        impl FromGlib<$glib> for $name {
+            #[inline]
+            fn from_glib(val: $glib) -> $name {
+                <$name>::new(val).expect("unexpected zero value")
+            }
+        }
+
+        impl FromGlib<$glib> for Option<$name> {
+            #[inline]
+            fn from_glib(val: $glib) -> Option<$name> {
+                <$name>::new(val)
+            }
+        }
+    };
+}
+
+impl_from_glib_non_zero!(num::NonZeroU8, u8);
+impl_from_glib_non_zero!(num::NonZeroI8, i8);
+impl_from_glib_non_zero!(num::NonZeroU16, u16);
+impl_from_glib_non_zero!(num::NonZeroI16, i16);
+impl_from_glib_non_zero!(num::NonZeroU32, u32);
+impl_from_glib_non_zero!(num::NonZeroI32, i32);
+impl_from_glib_non_zero!(num::NonZeroU64, u64);
+impl_from_glib_non_zero!(num::NonZeroI64, i64);
+
 impl FromGlib<i32> for Option<u32> {
     #[inline]
     fn from_glib(val: i32) -> Option<u32> {
@@ -1239,6 +1554,52 @@ pub unsafe fn from_glib_borrow<P: Ptr, T: FromGlibPtrBorrow<P>>(ptr: P) -> Borro
     FromGlibPtrBorrow::from_glib_borrow(ptr)
 }
 
+/// Stores a copy of `err` in the `GError **` out parameter `err_ptr`.
+///
+/// This is meant for C functions implemented in Rust. Like `g_propagate_error()`, nothing is
+/// stored if `err_ptr` is `NULL` and `*err_ptr` must not already contain an error.
+///
+/// # Safety
+///
+/// `err_ptr` must be `NULL` or point to a valid `GError *`.
+pub unsafe fn set_error(err_ptr: *mut *mut glib_sys::GError, err: &::Error) {
+    let err: *const glib_sys::GError = err.to_glib_full();
+    glib_sys::g_propagate_error(err_ptr, mut_override(err));
+}
+
+/// Runs `func` and converts its result for a C function with a `GError **` out parameter.
+///
+/// If `func` returns an error, it is stored in `err_ptr` with [`set_error`] and
+/// `failure_value` is returned instead.
+///
+/// ```ignore
+/// unsafe extern "C" fn load_trampoline(path: *const c_char, error: *mut *mut GError) -> gboolean {
+///     try_block_to_gerror(error, glib_sys::GFALSE, || {
+///         load(&PathBuf::from_glib_none(path))?;
+///         Ok(glib_sys::GTRUE)
+///     })
+/// }
+/// ```
+///
+/// # Safety
+///
+/// `err_ptr` must be `NULL` or point to a valid `GError *`.
+///
+/// [`set_error`]: fn.set_error.html
+pub unsafe fn try_block_to_gerror<T, F: FnOnce() -> Result<T, ::Error>>(
+    err_ptr: *mut *mut glib_sys::GError,
+    failure_value: T,
+    func: F,
+) -> T {
+    match func() {
+        Ok(value) => value,
+        Err(err) => {
+            set_error(err_ptr, &err);
+            failure_value
+        }
+    }
+}
+
 impl<P: Ptr, T: FromGlibPtrNone<P>> FromGlibPtrNone<P> for Option<T> {
     #[inline]
     unsafe fn from_glib_none(ptr: P) -> Option<T> {
@@ -1979,7 +2340,12 @@ where
 }
 
 #[allow(clippy::implicit_hasher)]
-impl FromGlibContainer<*const c_char, *mut glib_sys::GHashTable> for HashMap<String, String> {
+impl<K, V> FromGlibContainer<<K as GlibPtrDefault>::GlibType, *mut glib_sys::GHashTable>
+    for HashMap<K, V>
+where
+    K: HashTableKey + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>,
+    V: HashTableValue + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>,
+{
     unsafe fn from_glib_none_num(ptr: *mut glib_sys::GHashTable, _: usize) -> Self {
         FromGlibPtrContainer::from_glib_none(ptr)
     }
@@ -1994,24 +2360,31 @@ impl FromGlibContainer<*const c_char, *mut glib_sys::GHashTable> for HashMap<Str
 }
 
 #[allow(clippy::implicit_hasher)]
-impl FromGlibPtrContainer<*const c_char, *mut glib_sys::GHashTable> for HashMap<String, String> {
+impl<K, V> FromGlibPtrContainer<<K as GlibPtrDefault>::GlibType, *mut glib_sys::GHashTable>
+    for HashMap<K, V>
+where
+    K: HashTableKey + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>,
+    V: HashTableValue + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>,
+{
     unsafe fn from_glib_none(ptr: *mut glib_sys::GHashTable) -> Self {
-        unsafe extern "C" fn read_string_hash_table(
+        unsafe extern "C" fn read_hash_table<K, V>(
             key: glib_sys::gpointer,
             value: glib_sys::gpointer,
             hash_map: glib_sys::gpointer,
-        ) {
-            let key: String = from_glib_none(key as *const c_char);
-            let value: String = from_glib_none(value as *const c_char);
-            let hash_map: &mut HashMap<String, String> =
-                &mut *(hash_map as *mut HashMap<String, String>);
+        ) where
+            K: HashTableKey + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>,
+            V: HashTableValue + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>,
+        {
+            let key = from_glib_none::<<K as GlibPtrDefault>::GlibType, K>(Ptr::from(key));
+            let value = from_glib_none::<<V as GlibPtrDefault>::GlibType, V>(Ptr::from(value));
+            let hash_map: &mut HashMap<K, V> = &mut *(hash_map as *mut HashMap<K, V>);
             hash_map.insert(key, value);
         }
-        let mut map = HashMap::new();
+        let mut map = HashMap::with_capacity(len_from_glib(glib_sys::g_hash_table_size(ptr)));
         glib_sys::g_hash_table_foreach(
             ptr,
-            Some(read_string_hash_table),
-            &mut map as *mut HashMap<String, String> as *mut _,
+            Some(read_hash_table::<K, V>),
+            &mut map as *mut HashMap<K, V> as *mut _,
         );
         map
     }
@@ -2038,7 +2411,7 @@ where
             return Vec::new();
         }
         let pdata = (*ptr).pdata;
-        assert!((*ptr).len as usize >= num);
+        assert!(len_from_glib((*ptr).len) >= num);
         let mut res = Vec::with_capacity(num);
         for i in 0..num {
             let item_ptr: <T as GlibPtrDefault>::GlibType = Ptr::from(ptr::read(pdata.add(i)));
@@ -2062,7 +2435,7 @@ where
             return Vec::new();
         }
         let pdata = (*ptr).pdata;
-        assert!((*ptr).len as usize >= num);
+        assert!(len_from_glib((*ptr).len) >= num);
         let mut res = Vec::with_capacity(num);
         for i in 0..num {
             let item_ptr: <T as GlibPtrDefault>::GlibType = Ptr::from(ptr::read(pdata.add(i)));
@@ -2083,17 +2456,17 @@ where
         + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
 {
     unsafe fn from_glib_none_as_vec(ptr: *mut glib_sys::GPtrArray) -> Vec<T> {
-        let num = (*ptr).len as usize;
+        let num = len_from_glib((*ptr).len);
         FromGlibContainer::from_glib_none_num(ptr, num)
     }
 
     unsafe fn from_glib_container_as_vec(ptr: *mut glib_sys::GPtrArray) -> Vec<T> {
-        let num = (*ptr).len as usize;
+        let num = len_from_glib((*ptr).len);
         FromGlibContainer::from_glib_container_num(ptr, num)
     }
 
     unsafe fn from_glib_full_as_vec(ptr: *mut glib_sys::GPtrArray) -> Vec<T> {
-        let num = (*ptr).len as usize;
+        let num = len_from_glib((*ptr).len);
         FromGlibContainer::from_glib_full_num(ptr, num)
     }
 }
@@ -2141,6 +2514,139 @@ where
     }
 }
 
+macro_rules! impl_from_glib_container_as_vec_array {
+    ($name:ty) => {
+        impl FromGlibContainerAsVec<*mut $name, *mut glib_sys::GArray> for $name {
+            unsafe fn from_glib_none_num_as_vec(
+                ptr: *mut glib_sys::GArray,
+                num: usize,
+            ) -> Vec<Self> {
+                if num == 0 || ptr.is_null() {
+                    return Vec::new();
+                }
+                assert_eq!(
+                    len_from_glib(glib_sys::g_array_get_element_size(ptr)),
+                    mem::size_of::<$name>()
+                );
+                assert!(len_from_glib((*ptr).len) >= num);
+                FromGlibContainerAsVec::from_glib_none_num_as_vec((*ptr).data as *const $name, num)
+            }
+
+            unsafe fn from_glib_container_num_as_vec(
+                ptr: *mut glib_sys::GArray,
+                num: usize,
+            ) -> Vec<Self> {
+                let res = FromGlibContainerAsVec::from_glib_none_num_as_vec(ptr, num);
+                if !ptr.is_null() {
+                    glib_sys::g_array_unref(ptr);
+                }
+                res
+            }
+
+            unsafe fn from_glib_full_num_as_vec(
+                ptr: *mut glib_sys::GArray,
+                num: usize,
+            ) -> Vec<Self> {
+                FromGlibContainerAsVec::from_glib_container_num_as_vec(ptr, num)
+            }
+        }
+
+        impl FromGlibPtrArrayContainerAsVec<*mut $name, *mut glib_sys::GArray> for $name {
+            unsafe fn from_glib_none_as_vec(ptr: *mut glib_sys::GArray) -> Vec<Self> {
+                let num = if ptr.is_null() {
+                    0
+                } else {
+                    len_from_glib((*ptr).len)
+                };
+                FromGlibContainerAsVec::from_glib_none_num_as_vec(ptr, num)
+            }
+
+            unsafe fn from_glib_container_as_vec(ptr: *mut glib_sys::GArray) -> Vec<Self> {
+                let num = if ptr.is_null() {
+                    0
+                } else {
+                    len_from_glib((*ptr).len)
+                };
+                FromGlibContainerAsVec::from_glib_container_num_as_vec(ptr, num)
+            }
+
+            unsafe fn from_glib_full_as_vec(ptr: *mut glib_sys::GArray) -> Vec<Self> {
+                let num = if ptr.is_null() {
+                    0
+                } else {
+                    len_from_glib((*ptr).len)
+                };
+                FromGlibContainerAsVec::from_glib_full_num_as_vec(ptr, num)
+            }
+        }
+    };
+}
+
+impl_from_glib_container_as_vec_array!(u8);
+impl_from_glib_container_as_vec_array!(i8);
+impl_from_glib_container_as_vec_array!(u16);
+impl_from_glib_container_as_vec_array!(i16);
+impl_from_glib_container_as_vec_array!(u32);
+impl_from_glib_container_as_vec_array!(i32);
+impl_from_glib_container_as_vec_array!(u64);
+impl_from_glib_container_as_vec_array!(i64);
+impl_from_glib_container_as_vec_array!(f32);
+impl_from_glib_container_as_vec_array!(f64);
+
+impl FromGlibContainerAsVec<*mut u8, *mut glib_sys::GByteArray> for u8 {
+    unsafe fn from_glib_none_num_as_vec(ptr: *mut glib_sys::GByteArray, num: usize) -> Vec<Self> {
+        if num == 0 || ptr.is_null() {
+            return Vec::new();
+        }
+        assert!(len_from_glib((*ptr).len) >= num);
+        FromGlibContainerAsVec::from_glib_none_num_as_vec((*ptr).data as *const u8, num)
+    }
+
+    unsafe fn from_glib_container_num_as_vec(
+        ptr: *mut glib_sys::GByteArray,
+        num: usize,
+    ) -> Vec<Self> {
+        let res = FromGlibContainerAsVec::from_glib_none_num_as_vec(ptr, num);
+        if !ptr.is_null() {
+            glib_sys::g_byte_array_unref(ptr);
+        }
+        res
+    }
+
+    unsafe fn from_glib_full_num_as_vec(ptr: *mut glib_sys::GByteArray, num: usize) -> Vec<Self> {
+        FromGlibContainerAsVec::from_glib_container_num_as_vec(ptr, num)
+    }
+}
+
+impl FromGlibPtrArrayContainerAsVec<*mut u8, *mut glib_sys::GByteArray> for u8 {
+    unsafe fn from_glib_none_as_vec(ptr: *mut glib_sys::GByteArray) -> Vec<Self> {
+        let num = if ptr.is_null() {
+            0
+        } else {
+            len_from_glib((*ptr).len)
+        };
+        FromGlibContainerAsVec::from_glib_none_num_as_vec(ptr, num)
+    }
+
+    unsafe fn from_glib_container_as_vec(ptr: *mut glib_sys::GByteArray) -> Vec<Self> {
+        let num = if ptr.is_null() {
+            0
+        } else {
+            len_from_glib((*ptr).len)
+        };
+        FromGlibContainerAsVec::from_glib_container_num_as_vec(ptr, num)
+    }
+
+    unsafe fn from_glib_full_as_vec(ptr: *mut glib_sys::GByteArray) -> Vec<Self> {
+        let num = if ptr.is_null() {
+            0
+        } else {
+            len_from_glib((*ptr).len)
+        };
+        FromGlibContainerAsVec::from_glib_full_num_as_vec(ptr, num)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate tempfile;
@@ -2154,17 +2660,43 @@ mod tests {
 
     #[test]
     fn string_hash_map() {
-        let mut map = HashMap::new();
+        let mut map: HashMap<String, String> = HashMap::new();
         map.insert("A".into(), "1".into());
         map.insert("B".into(), "2".into());
         map.insert("C".into(), "3".into());
         let ptr: *mut glib_sys::GHashTable = map.to_glib_full();
-        let map = unsafe { HashMap::from_glib_full(ptr) };
+        let map: HashMap<String, String> = unsafe { HashMap::from_glib_full(ptr) };
         assert_eq!(map.get("A"), Some(&"1".into()));
         assert_eq!(map.get("B"), Some(&"2".into()));
         assert_eq!(map.get("C"), Some(&"3".into()));
     }
 
+    #[test]
+    fn object_hash_map() {
+        use object::{ObjectExt, ObjectType};
+        use StaticType;
+
+        let a = ::Object::new(::Object::static_type(), &[]).unwrap();
+        let b = ::Object::new(::Object::static_type(), &[]).unwrap();
+
+        let mut orig: HashMap<::Object, GString> = HashMap::new();
+        orig.insert(a.clone(), GString::from("a"));
+        orig.insert(b.clone(), GString::from("b"));
+        let stash: Stash<*mut glib_sys::GHashTable, _> = orig.to_glib_none();
+        unsafe {
+            assert_eq!(glib_sys::g_hash_table_size(stash.0), 2);
+            assert!(glib_sys::g_hash_table_contains(stash.0, a.as_ptr() as *const _) != 0);
+        }
+
+        let map: HashMap<::Object, GString> = unsafe { HashMap::from_glib_none(stash.0) };
+        assert_eq!(map[&a], "a");
+        assert_eq!(map[&b], "b");
+
+        drop(stash);
+        drop(orig);
+        assert_eq!(a.ref_count(), 2);
+    }
+
     #[test]
     fn string_array() {
         let v = vec!["A".to_string(), "B".to_string(), "C".to_string()];
@@ -2196,6 +2728,143 @@ mod tests {
         assert_eq!(&v, strings);
     }
 
+    #[test]
+    fn checked_integers() {
+        let len: u32 = 42usize.into_glib();
+        assert_eq!(len, 42);
+        let len: i64 = 42u32.into_glib();
+        assert_eq!(len, 42);
+
+        assert_eq!(usize::try_from_glib(42u64), Ok(42));
+        assert!(u32::try_from_glib(-1i64).is_err());
+        assert!(u32::try_from_glib(u64::from(::std::u32::MAX) + 1).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn checked_integers_overflow() {
+        let _: i32 = ::std::u32::MAX.into_glib();
+    }
+
+    #[test]
+    fn non_zero() {
+        let id: num::NonZeroU32 = from_glib(42u32);
+        assert_eq!(id.to_glib(), 42);
+        assert_eq!(from_glib::<_, Option<num::NonZeroI64>>(0i64), None);
+        assert_eq!(None::<num::NonZeroU8>.to_glib(), 0);
+        assert_eq!(mem::size_of::<Option<::SourceId>>(), mem::size_of::<u32>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_zero_from_zero() {
+        let _: num::NonZeroU16 = from_glib(0u16);
+    }
+
+    #[test]
+    fn duration() {
+        let millis: u32 = Duration::from_micros(1500).into_glib_millis();
+        assert_eq!(millis, 1);
+        let micros: i64 = Duration::from_millis(1500).into_glib_micros();
+        assert_eq!(micros, 1_500_000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duration_overflow() {
+        let _: u32 = Duration::from_secs(u64::from(::std::u32::MAX)).into_glib_millis();
+    }
+
+    #[test]
+    fn c_str() {
+        let short = "short";
+        let len = with_c_str(short, |ptr| unsafe { CStr::from_ptr(ptr).to_bytes().len() });
+        assert_eq!(len, short.len());
+
+        let long = "a".repeat(MAX_STACK_ALLOCATION);
+        let s = with_c_str(&long, |ptr| unsafe {
+            CStr::from_ptr(ptr).to_str().unwrap().to_owned()
+        });
+        assert_eq!(s, long);
+
+        let empty = with_c_str("", |ptr| unsafe { *ptr });
+        assert_eq!(empty, 0);
+    }
+
+    #[test]
+    fn gerror_out_param() {
+        let mut error = ptr::null_mut();
+        let res = unsafe {
+            try_block_to_gerror(&mut error, glib_sys::GFALSE, || {
+                Err(::Error::new(::FileError::Failed, "Failed"))
+            })
+        };
+        assert_eq!(res, glib_sys::GFALSE);
+        assert!(!error.is_null());
+        let error: ::Error = unsafe { from_glib_full(error) };
+        assert!(error.is::<::FileError>());
+        assert_eq!(error.to_string(), "Failed");
+
+        let mut error = ptr::null_mut();
+        let res =
+            unsafe { try_block_to_gerror(&mut error, glib_sys::GFALSE, || Ok(glib_sys::GTRUE)) };
+        assert_eq!(res, glib_sys::GTRUE);
+        assert!(error.is_null());
+
+        let err = ::Error::new(::FileError::Failed, "Failed");
+        unsafe { set_error(ptr::null_mut(), &err) };
+    }
+
+    #[test]
+    fn array() {
+        let values = [1u32, 2, 3];
+        let (ptr, _stash) =
+            ToGlibContainerFromSlice::<*mut glib_sys::GArray>::to_glib_none_from_slice(&values[..]);
+        let v: Vec<u32> = unsafe { FromGlibPtrContainer::from_glib_none(ptr) };
+        assert_eq!(&v, &values);
+
+        let ptr =
+            ToGlibContainerFromSlice::<*mut glib_sys::GArray>::to_glib_full_from_slice(&values[..]);
+        let v: Vec<u32> = unsafe { FromGlibContainer::from_glib_full_num(ptr, 2) };
+        assert_eq!(&v, &values[..2]);
+    }
+
+    #[test]
+    fn byte_array() {
+        let bytes = b"abcd";
+        let ptr = ToGlibContainerFromSlice::<*mut glib_sys::GByteArray>::to_glib_full_from_slice(
+            &bytes[..],
+        );
+        unsafe {
+            assert_eq!((*ptr).len, 4);
+        }
+        let v: Vec<u8> = unsafe { FromGlibPtrContainer::from_glib_full(ptr) };
+        assert_eq!(&v, bytes);
+    }
+
+    #[test]
+    fn path_full() {
+        let paths = [PathBuf::from("abcd"), PathBuf::from("øäöü")];
+        let ptr: *mut *mut c_char = ToGlibContainerFromSlice::to_glib_full_from_slice(&paths);
+        let v: Vec<PathBuf> = unsafe { FromGlibContainer::from_glib_full_num(ptr, paths.len()) };
+        assert_eq!(&v, &paths);
+
+        let ptr: *mut c_char = OsStr::new("øäöü").to_glib_full();
+        let s: OsString = unsafe { from_glib_full(ptr) };
+        assert_eq!(s, "øäöü");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn path_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"abc\xffd"));
+        let ptr: *mut c_char = path.to_glib_full();
+        let path_buf: PathBuf = unsafe { from_glib_full(ptr) };
+        assert_eq!(path_buf, path);
+    }
+
     #[test]
     #[cfg(not(target_os = "macos"))]
     fn test_paths() {
@@ -0,0 +1,304 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Binding for the `GTree` sorted map.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::Tree;
+//!
+//! let mut tree = Tree::new();
+//! tree.insert(String::from("b"), String::from("2"));
+//! tree.insert(String::from("a"), String::from("1"));
+//! assert_eq!(tree.lookup(&String::from("a")).as_deref(), Some("1"));
+//! ```
+
+use glib_sys;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+use translate::*;
+use Continue;
+
+/// A sorted map backed by a `GTree`.
+///
+/// Keys and values are stored in their C representation as returned by `to_glib_full()`, and
+/// accessing them returns a new reference (or copy). Trees created from Rust free their keys
+/// and values when they are removed.
+pub struct Tree<K, V> {
+    ptr: ptr::NonNull<glib_sys::GTree>,
+    phantom: PhantomData<(K, V)>,
+}
+
+unsafe extern "C" fn compare_func_trampoline<K>(
+    a: glib_sys::gconstpointer,
+    b: glib_sys::gconstpointer,
+    func: glib_sys::gpointer,
+) -> i32
+where
+    K: GlibPtrDefault + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>,
+{
+    let func: fn(&K, &K) -> Ordering = mem::transmute(func);
+    let a =
+        from_glib_none::<<K as GlibPtrDefault>::GlibType, K>(Ptr::from(a as glib_sys::gpointer));
+    let b =
+        from_glib_none::<<K as GlibPtrDefault>::GlibType, K>(Ptr::from(b as glib_sys::gpointer));
+    func(&a, &b).to_glib()
+}
+
+unsafe extern "C" fn destroy_trampoline<T>(ptr: glib_sys::gpointer)
+where
+    T: GlibPtrDefault + FromGlibPtrFull<<T as GlibPtrDefault>::GlibType>,
+{
+    let _ = from_glib_full::<<T as GlibPtrDefault>::GlibType, T>(Ptr::from(ptr));
+}
+
+impl<K, V> Tree<K, V>
+where
+    K: GlibPtrDefault
+        + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<K as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <K as GlibPtrDefault>::GlibType>,
+    V: GlibPtrDefault
+        + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<V as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <V as GlibPtrDefault>::GlibType>,
+{
+    /// Creates a new tree that is sorted by the `Ord` implementation of the keys.
+    pub fn new() -> Self
+    where
+        K: Ord,
+    {
+        Self::with_compare_func(K::cmp)
+    }
+
+    /// Creates a new tree that is sorted by `func`.
+    ///
+    /// `func` must implement a total order on the keys.
+    pub fn with_compare_func(func: fn(&K, &K) -> Ordering) -> Self {
+        unsafe {
+            from_glib_full(glib_sys::g_tree_new_full(
+                Some(compare_func_trampoline::<K>),
+                func as glib_sys::gpointer,
+                Some(destroy_trampoline::<K>),
+                Some(destroy_trampoline::<V>),
+            ))
+        }
+    }
+
+    /// Inserts `value` for `key`, replacing any previous value for an equal key.
+    pub fn insert(&mut self, key: K, value: V) {
+        unsafe {
+            let key: <K as GlibPtrDefault>::GlibType = key.to_glib_full();
+            let value: <V as GlibPtrDefault>::GlibType = value.to_glib_full();
+            glib_sys::g_tree_insert(self.ptr.as_ptr(), Ptr::to(key), Ptr::to(value));
+        }
+    }
+
+    /// Returns the value for `key`, if any.
+    pub fn lookup(&self, key: &K) -> Option<V> {
+        unsafe {
+            let key: Stash<<K as GlibPtrDefault>::GlibType, K> = key.to_glib_none();
+            let value = glib_sys::g_tree_lookup(self.ptr.as_ptr(), Ptr::to(key.0));
+            if value.is_null() {
+                None
+            } else {
+                Some(from_glib_none::<<V as GlibPtrDefault>::GlibType, V>(
+                    Ptr::from(value),
+                ))
+            }
+        }
+    }
+
+    /// Returns `true` if the tree contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        unsafe {
+            let key: Stash<<K as GlibPtrDefault>::GlibType, K> = key.to_glib_none();
+            from_glib(glib_sys::g_tree_lookup_extended(
+                self.ptr.as_ptr(),
+                Ptr::to(key.0),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ))
+        }
+    }
+
+    /// Removes the value for `key`.
+    ///
+    /// Returns `true` if the tree contained a value for `key`.
+    pub fn remove(&mut self, key: &K) -> bool {
+        unsafe {
+            let key: Stash<<K as GlibPtrDefault>::GlibType, K> = key.to_glib_none();
+            from_glib(glib_sys::g_tree_remove(self.ptr.as_ptr(), Ptr::to(key.0)))
+        }
+    }
+
+    /// Returns the number of entries of the tree.
+    pub fn len(&self) -> usize {
+        unsafe { glib_sys::g_tree_nnodes(self.ptr.as_ptr()) as usize }
+    }
+
+    /// Returns `true` if the tree has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the height of the tree.
+    pub fn height(&self) -> usize {
+        unsafe { glib_sys::g_tree_height(self.ptr.as_ptr()) as usize }
+    }
+
+    /// Calls `func` for all entries in sorted order until it returns `Continue(false)`.
+    pub fn foreach<F: FnMut(K, V) -> Continue>(&self, mut func: F) {
+        unsafe extern "C" fn foreach_trampoline<K, V, F>(
+            key: glib_sys::gpointer,
+            value: glib_sys::gpointer,
+            func: glib_sys::gpointer,
+        ) -> glib_sys::gboolean
+        where
+            K: GlibPtrDefault + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>,
+            V: GlibPtrDefault + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>,
+            F: FnMut(K, V) -> Continue,
+        {
+            let func = &mut *(func as *mut F);
+            let key = from_glib_none::<<K as GlibPtrDefault>::GlibType, K>(Ptr::from(key));
+            let value = from_glib_none::<<V as GlibPtrDefault>::GlibType, V>(Ptr::from(value));
+            // Returning `TRUE` stops the traversal
+            (!func(key, value).0).to_glib()
+        }
+
+        unsafe {
+            glib_sys::g_tree_foreach(
+                self.ptr.as_ptr(),
+                Some(foreach_trampoline::<K, V, F>),
+                &mut func as *mut F as glib_sys::gpointer,
+            );
+        }
+    }
+
+    /// Returns all entries in sorted order.
+    pub fn to_vec(&self) -> Vec<(K, V)> {
+        let mut v = Vec::with_capacity(self.len());
+        self.foreach(|key, value| {
+            v.push((key, value));
+            Continue(true)
+        });
+        v
+    }
+}
+
+impl<K, V> Default for Tree<K, V>
+where
+    K: GlibPtrDefault
+        + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<K as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <K as GlibPtrDefault>::GlibType>
+        + Ord,
+    V: GlibPtrDefault
+        + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<V as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <V as GlibPtrDefault>::GlibType>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Tree<K, V> {
+    fn drop(&mut self) {
+        unsafe { glib_sys::g_tree_unref(self.ptr.as_ptr()) }
+    }
+}
+
+impl<K, V> fmt::Debug for Tree<K, V>
+where
+    K: GlibPtrDefault
+        + FromGlibPtrNone<<K as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<K as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <K as GlibPtrDefault>::GlibType>
+        + fmt::Debug,
+    V: GlibPtrDefault
+        + FromGlibPtrNone<<V as GlibPtrDefault>::GlibType>
+        + FromGlibPtrFull<<V as GlibPtrDefault>::GlibType>
+        + for<'a> ToGlibPtr<'a, <V as GlibPtrDefault>::GlibType>
+        + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.to_vec()).finish()
+    }
+}
+
+#[doc(hidden)]
+impl<'a, K, V> ToGlibPtr<'a, *mut glib_sys::GTree> for Tree<K, V> {
+    type Storage = &'a Self;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut glib_sys::GTree, Self> {
+        Stash(self.ptr.as_ptr(), self)
+    }
+}
+
+#[doc(hidden)]
+impl<K, V> FromGlibPtrFull<*mut glib_sys::GTree> for Tree<K, V> {
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut glib_sys::GTree) -> Self {
+        Tree {
+            ptr: ptr::NonNull::new(ptr).expect("NULL tree"),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<K, V> FromGlibPtrNone<*mut glib_sys::GTree> for Tree<K, V> {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut glib_sys::GTree) -> Self {
+        assert!(!ptr.is_null());
+        from_glib_full(glib_sys::g_tree_ref(ptr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree() {
+        let mut tree = Tree::new();
+        for &(k, v) in &[("b", "2"), ("c", "3"), ("a", "1")] {
+            tree.insert(k.to_string(), v.to_string());
+        }
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.lookup(&"b".to_string()), Some("2".to_string()));
+        assert_eq!(tree.lookup(&"d".to_string()), None);
+        assert!(tree.contains_key(&"c".to_string()));
+
+        let keys: Vec<String> = tree.to_vec().into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["a", "b", "c"]);
+
+        assert!(tree.remove(&"b".to_string()));
+        assert!(!tree.remove(&"b".to_string()));
+        assert_eq!(tree.len(), 2);
+
+        tree.insert("a".to_string(), "4".to_string());
+        assert_eq!(tree.lookup(&"a".to_string()), Some("4".to_string()));
+    }
+
+    #[test]
+    fn test_tree_compare_func() {
+        let mut tree: Tree<String, String> = Tree::with_compare_func(|a, b| b.cmp(a));
+        tree.insert("a".to_string(), "1".to_string());
+        tree.insert("b".to_string(), "2".to_string());
+
+        let mut values = Vec::new();
+        tree.foreach(|_, v| {
+            values.push(v);
+            Continue(false)
+        });
+        assert_eq!(values, ["2"]);
+    }
+}
@@ -7,8 +7,8 @@
 use glib_sys;
 use gobject_sys;
 use translate::{
-    from_glib, from_glib_none, FromGlib, FromGlibContainerAsVec, ToGlib, ToGlibContainerFromSlice,
-    ToGlibPtr, ToGlibPtrMut,
+    from_glib, from_glib_none, with_c_str, FromGlib, FromGlibContainerAsVec, ToGlib,
+    ToGlibContainerFromSlice, ToGlibPtr, ToGlibPtrMut,
 };
 use value::{FromValue, FromValueOptional, SetValue, Value};
 
@@ -127,9 +127,38 @@ impl Type {
         }
     }
 
+    /// Attaches `value` to the type under `key`.
+    ///
+    /// Types are never unregistered, so values set with this are never freed, including a
+    /// value previously set for `key` that is replaced.
+    ///
+    /// # Safety
+    ///
+    /// This function doesn't store type information
+    pub unsafe fn set_qdata<QD: Send + Sync + 'static>(&self, key: ::Quark, value: QD) {
+        assert_ne!(*self, Type::Invalid);
+        let ptr = Box::into_raw(Box::new(value)) as glib_sys::gpointer;
+        gobject_sys::g_type_set_qdata(self.to_glib(), key.to_glib(), ptr);
+    }
+
+    /// # Safety
+    ///
+    /// The caller is responsible for ensuring the returned value is of a suitable type
+    pub unsafe fn get_qdata<QD: Send + Sync + 'static>(&self, key: ::Quark) -> Option<&'static QD> {
+        if *self == Type::Invalid {
+            return None;
+        }
+        let ptr = gobject_sys::g_type_get_qdata(self.to_glib(), key.to_glib());
+        if ptr.is_null() {
+            None
+        } else {
+            Some(&*(ptr as *const QD))
+        }
+    }
+
     pub fn from_name<'a, P: Into<&'a str>>(name: P) -> Option<Self> {
         unsafe {
-            let type_ = gobject_sys::g_type_from_name(name.into().to_glib_none().0);
+            let type_ = with_c_str(name.into(), |name| gobject_sys::g_type_from_name(name));
             if type_ == gobject_sys::G_TYPE_INVALID {
                 None
             } else {
@@ -390,4 +419,19 @@ mod tests {
         assert_eq!(invalid.interface_prerequisites(), vec![]);
         dbg!(&invalid);
     }
+
+    #[test]
+    fn qdata() {
+        let key = ::Quark::from_string("glib-rs-test-type-qdata");
+        unsafe {
+            assert_eq!(Type::String.get_qdata::<Vec<u32>>(key), None);
+            Type::String.set_qdata(key, vec![1u32, 2, 3]);
+            assert_eq!(
+                Type::String.get_qdata::<Vec<u32>>(key),
+                Some(&vec![1, 2, 3])
+            );
+            assert_eq!(Type::I32.get_qdata::<Vec<u32>>(key), None);
+            assert_eq!(Type::Invalid.get_qdata::<Vec<u32>>(key), None);
+        }
+    }
 }
@@ -0,0 +1,379 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <https://opensource.org/licenses/MIT>
+
+//! Unicode string and character functions with GLib semantics.
+//!
+//! These follow the rules GLib and GTK use (e.g. for case-insensitive matching), which can differ
+//! from the corresponding methods of the Rust standard library.
+
+use glib_sys;
+use libc::c_char;
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use translate::*;
+use GString;
+use NormalizeMode;
+use UnicodeScript;
+use UnicodeType;
+
+/// Converts `s` into a form that is independent of case, for use in case-insensitive
+/// comparisons.
+///
+/// The result should only be used for comparisons, e.g. together with
+/// [`utf8_normalize`](fn.utf8_normalize.html), and not be displayed.
+pub fn utf8_casefold(s: &str) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_casefold(
+            s.as_ptr() as *const _,
+            s.len().into_glib(),
+        ))
+    }
+}
+
+/// Converts `s` into a canonical normalized form according to `mode`.
+pub fn utf8_normalize(s: &str, mode: NormalizeMode) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_normalize(
+            s.as_ptr() as *const _,
+            s.len().into_glib(),
+            mode.to_glib(),
+        ))
+    }
+}
+
+/// Converts all characters of `s` to uppercase.
+pub fn utf8_strup(s: &str) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_strup(
+            s.as_ptr() as *const _,
+            s.len().into_glib(),
+        ))
+    }
+}
+
+/// Converts all characters of `s` to lowercase.
+pub fn utf8_strdown(s: &str) -> GString {
+    unsafe {
+        from_glib_full(glib_sys::g_utf8_strdown(
+            s.as_ptr() as *const _,
+            s.len().into_glib(),
+        ))
+    }
+}
+
+macro_rules! unichar_ext {
+    ($($(#[$attr:meta])* fn $name:ident => $method:ident, $ffi_name:ident;)*) => {
+        /// Character classification and conversion with GLib semantics.
+        pub trait GUnicharExt: Copy {
+            $(
+                $(#[$attr])*
+                fn $method(self) -> bool;
+            )*
+
+            /// Converts the character to uppercase, or returns it unchanged if it has no
+            /// uppercase form.
+            fn to_upper(self) -> char;
+
+            /// Converts the character to lowercase, or returns it unchanged if it has no
+            /// lowercase form.
+            fn to_lower(self) -> char;
+
+            /// Converts the character to titlecase, or returns it unchanged if it has no
+            /// titlecase form.
+            fn to_title(self) -> char;
+
+            /// Returns the numeric value of the character if it is a decimal digit.
+            fn digit_value(self) -> Option<u32>;
+
+            /// Returns the numeric value of the character if it is a hexadecimal digit.
+            fn xdigit_value(self) -> Option<u32>;
+
+            /// Returns the general category of the character.
+            fn unichar_type(self) -> UnicodeType;
+
+            /// Returns the script of the character.
+            ///
+            /// Characters used by several scripts, like digits and punctuation, return
+            /// `UnicodeScript::Common`.
+            fn script(self) -> UnicodeScript;
+        }
+
+        impl GUnicharExt for char {
+            $(
+                fn $method(self) -> bool {
+                    unsafe { from_glib(glib_sys::$ffi_name(self.to_glib())) }
+                }
+            )*
+
+            fn to_upper(self) -> char {
+                unsafe { from_glib(glib_sys::g_unichar_toupper(self.to_glib())) }
+            }
+
+            fn to_lower(self) -> char {
+                unsafe { from_glib(glib_sys::g_unichar_tolower(self.to_glib())) }
+            }
+
+            fn to_title(self) -> char {
+                unsafe { from_glib(glib_sys::g_unichar_totitle(self.to_glib())) }
+            }
+
+            fn digit_value(self) -> Option<u32> {
+                match unsafe { glib_sys::g_unichar_digit_value(self.to_glib()) } {
+                    -1 => None,
+                    value => Some(value as u32),
+                }
+            }
+
+            fn xdigit_value(self) -> Option<u32> {
+                match unsafe { glib_sys::g_unichar_xdigit_value(self.to_glib()) } {
+                    -1 => None,
+                    value => Some(value as u32),
+                }
+            }
+
+            fn unichar_type(self) -> UnicodeType {
+                unsafe { from_glib(glib_sys::g_unichar_type(self.to_glib())) }
+            }
+
+            fn script(self) -> UnicodeScript {
+                unsafe { from_glib(glib_sys::g_unichar_get_script(self.to_glib())) }
+            }
+        }
+
+        $(
+            $(#[$attr])*
+            pub fn $name(c: char) -> bool {
+                c.$method()
+            }
+        )*
+    };
+}
+
+unichar_ext! {
+    /// Returns `true` if the character is a letter or a number.
+    fn unichar_isalnum => is_alnum, g_unichar_isalnum;
+    /// Returns `true` if the character is a letter.
+    fn unichar_isalpha => is_alpha, g_unichar_isalpha;
+    /// Returns `true` if the character is a control character.
+    fn unichar_iscntrl => is_cntrl, g_unichar_iscntrl;
+    /// Returns `true` if the character is assigned in the Unicode version of GLib's tables.
+    fn unichar_isdefined => is_defined, g_unichar_isdefined;
+    /// Returns `true` if the character is a decimal digit.
+    // Not `is_digit`, which would be shadowed by `char::is_digit`
+    fn unichar_isdigit => is_decimal_digit, g_unichar_isdigit;
+    /// Returns `true` if the character is printable and not a space.
+    fn unichar_isgraph => is_graph, g_unichar_isgraph;
+    /// Returns `true` if the character is a lowercase letter.
+    fn unichar_islower => is_lower, g_unichar_islower;
+    /// Returns `true` if the character is a combining mark that is drawn together with the
+    /// previous character.
+    fn unichar_ismark => is_mark, g_unichar_ismark;
+    /// Returns `true` if the character is printable, including spaces.
+    fn unichar_isprint => is_print, g_unichar_isprint;
+    /// Returns `true` if the character is a punctuation character or a symbol.
+    fn unichar_ispunct => is_punct, g_unichar_ispunct;
+    /// Returns `true` if the character is whitespace, including line and paragraph separators.
+    fn unichar_isspace => is_space, g_unichar_isspace;
+    /// Returns `true` if the character is a titlecase letter.
+    fn unichar_istitle => is_title, g_unichar_istitle;
+    /// Returns `true` if the character is an uppercase letter.
+    fn unichar_isupper => is_upper, g_unichar_isupper;
+    /// Returns `true` if the character is double-width when displayed in a terminal.
+    fn unichar_iswide => is_wide, g_unichar_iswide;
+    /// Returns `true` if the character is double-width in legacy East Asian contexts, which
+    /// also includes characters of ambiguous width.
+    fn unichar_iswide_cjk => is_wide_cjk, g_unichar_iswide_cjk;
+    /// Returns `true` if the character is a hexadecimal digit.
+    fn unichar_isxdigit => is_xdigit, g_unichar_isxdigit;
+    /// Returns `true` if the character takes no space when displayed, e.g. combining marks and
+    /// zero-width spaces.
+    fn unichar_iszerowidth => is_zerowidth, g_unichar_iszerowidth;
+}
+
+/// Converts `c` to uppercase, or returns it unchanged if it has no uppercase form.
+pub fn unichar_toupper(c: char) -> char {
+    c.to_upper()
+}
+
+/// Converts `c` to lowercase, or returns it unchanged if it has no lowercase form.
+pub fn unichar_tolower(c: char) -> char {
+    c.to_lower()
+}
+
+/// Converts `c` to titlecase, or returns it unchanged if it has no titlecase form.
+pub fn unichar_totitle(c: char) -> char {
+    c.to_title()
+}
+
+/// Returns the numeric value of `c` if it is a decimal digit.
+pub fn unichar_digit_value(c: char) -> Option<u32> {
+    c.digit_value()
+}
+
+/// Returns the numeric value of `c` if it is a hexadecimal digit.
+pub fn unichar_xdigit_value(c: char) -> Option<u32> {
+    c.xdigit_value()
+}
+
+/// Returns the general category of `c`.
+pub fn unichar_type(c: char) -> UnicodeType {
+    c.unichar_type()
+}
+
+/// Returns the script of `c`.
+///
+/// Characters used by several scripts, like digits and punctuation, return
+/// `UnicodeScript::Common`.
+pub fn unichar_get_script(c: char) -> UnicodeScript {
+    c.script()
+}
+
+/// Compares `a` and `b` according to the collation rules of the current locale.
+///
+/// When sorting many strings it is more efficient to compare the keys returned by
+/// [`collate_key`](fn.collate_key.html) instead.
+pub fn collate(a: &str, b: &str) -> Ordering {
+    unsafe {
+        with_c_str(a, |a| {
+            with_c_str(b, |b| from_glib(glib_sys::g_utf8_collate(a, b)))
+        })
+    }
+}
+
+// Collation keys are not necessarily valid UTF-8
+unsafe fn collation_key_to_vec(ptr: *mut c_char) -> Vec<u8> {
+    assert!(!ptr.is_null());
+    let key = CStr::from_ptr(ptr).to_bytes().to_vec();
+    glib_sys::g_free(ptr as *mut _);
+    key
+}
+
+/// A key for sorting strings according to the collation rules of the current locale, as
+/// returned by [`collate_key`](fn.collate_key.html).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CollationKey(Vec<u8>);
+
+/// Returns a key for `s` such that comparing keys is equivalent to comparing the strings with
+/// [`collate`](fn.collate.html).
+pub fn collate_key(s: &str) -> CollationKey {
+    unsafe {
+        CollationKey(collation_key_to_vec(glib_sys::g_utf8_collate_key(
+            s.as_ptr() as *const _,
+            s.len().into_glib(),
+        )))
+    }
+}
+
+/// A key for sorting filenames, as returned by
+/// [`collate_key_for_filename`](fn.collate_key_for_filename.html).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FilenameCollationKey(Vec<u8>);
+
+/// Returns a key for sorting the filename `s` like file managers do.
+///
+/// Unlike [`collate_key`](fn.collate_key.html) this sorts dots specially and orders numbers in
+/// the filename by their numeric value, so that `file10` sorts after `file9`.
+pub fn collate_key_for_filename(s: &str) -> FilenameCollationKey {
+    unsafe {
+        FilenameCollationKey(collation_key_to_vec(
+            glib_sys::g_utf8_collate_key_for_filename(s.as_ptr() as *const _, s.len().into_glib()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case() {
+        assert_eq!(utf8_strup("straße"), "STRASSE");
+        assert_eq!(utf8_strdown("ÄÖÜ"), "äöü");
+        assert_eq!(utf8_casefold("Straße"), utf8_casefold("STRASSE"));
+        assert_eq!(unichar_toupper('ä'), 'Ä');
+        assert_eq!(unichar_tolower('Ä'), 'ä');
+        assert_eq!(unichar_totitle('ǆ'), 'ǅ');
+        assert_eq!(unichar_toupper('1'), '1');
+    }
+
+    #[test]
+    fn classification() {
+        assert!(unichar_isalpha('ß'));
+        assert!(!unichar_isalpha('1'));
+        assert!(unichar_isalnum('٣'));
+        assert!(unichar_isdigit('٣'));
+        assert!(unichar_isupper('Ä'));
+        assert!(unichar_islower('ä'));
+        assert!(unichar_istitle('ǅ'));
+        assert!(unichar_isspace('\u{2029}'));
+        assert!(unichar_iscntrl('\u{7f}'));
+        assert!(unichar_ispunct('¿'));
+        assert!(unichar_isprint(' '));
+        assert!(!unichar_isgraph(' '));
+        assert!(unichar_isxdigit('F'));
+        assert!(unichar_ismark('\u{308}'));
+        assert!(unichar_iszerowidth('\u{200b}'));
+        assert!(!unichar_iszerowidth('a'));
+        assert!(unichar_iswide('日'));
+        assert!(!unichar_iswide('a'));
+        assert!(unichar_iswide_cjk('日'));
+        assert!(unichar_isdefined('a'));
+        assert!(!unichar_isdefined('\u{10ffff}'));
+
+        assert_eq!(unichar_digit_value('٣'), Some(3));
+        assert_eq!(unichar_digit_value('a'), None);
+        assert_eq!(unichar_xdigit_value('b'), Some(11));
+        assert_eq!(unichar_xdigit_value('g'), None);
+
+        assert_eq!(unichar_type('a'), UnicodeType::LowercaseLetter);
+        assert_eq!(unichar_type('\u{308}'), UnicodeType::NonSpacingMark);
+        assert_eq!(unichar_type('€'), UnicodeType::CurrencySymbol);
+        assert_eq!(unichar_get_script('a'), UnicodeScript::Latin);
+        assert_eq!(unichar_get_script('Ж'), UnicodeScript::Cyrillic);
+        assert_eq!(unichar_get_script('日'), UnicodeScript::Han);
+        assert_eq!(unichar_get_script('1'), UnicodeScript::Common);
+    }
+
+    #[test]
+    fn char_ext() {
+        assert!('ß'.is_alpha());
+        assert!('٣'.is_decimal_digit());
+        assert!(!'a'.is_decimal_digit());
+        assert!('ǅ'.is_title());
+        assert!('\u{200b}'.is_zerowidth());
+        assert_eq!('ä'.to_upper(), 'Ä');
+        assert_eq!('ǆ'.to_title(), 'ǅ');
+        assert_eq!('b'.xdigit_value(), Some(11));
+        assert_eq!('€'.unichar_type(), UnicodeType::CurrencySymbol);
+        assert_eq!('Ж'.script(), UnicodeScript::Cyrillic);
+    }
+
+    #[test]
+    fn normalize() {
+        let composed = "\u{e4}";
+        let decomposed = "a\u{308}";
+        assert_eq!(utf8_normalize(composed, NormalizeMode::Default), decomposed);
+        assert_eq!(
+            utf8_normalize(decomposed, NormalizeMode::DefaultCompose),
+            composed
+        );
+        assert_eq!(utf8_normalize("\u{fb01}", NormalizeMode::All), "fi");
+        assert_eq!(
+            utf8_normalize("\u{fb01}", NormalizeMode::Default),
+            "\u{fb01}"
+        );
+    }
+
+    #[test]
+    fn collation() {
+        assert_eq!(collate("a", "b"), Ordering::Less);
+        assert_eq!(collate("b", "b"), Ordering::Equal);
+        assert!(collate_key("a") < collate_key("b"));
+        assert_eq!(collate_key("b"), collate_key("b"));
+
+        let mut files = vec!["file10.txt", "file9.txt", "file1.txt"];
+        files.sort_by_key(|s| collate_key_for_filename(s));
+        assert_eq!(files, ["file1.txt", "file9.txt", "file10.txt"]);
+    }
+}
@@ -5,12 +5,14 @@
 use error::BoolError;
 use glib_sys;
 use gstring::GString;
+use libc::c_char;
 use std;
-use std::ffi::{OsStr, OsString};
+use std::ffi::{CStr, OsStr, OsString};
 use std::path::{Path, PathBuf};
 use std::ptr;
 use translate::*;
 use Error;
+use StrV;
 
 /// Same as [`get_prgname()`].
 ///
@@ -83,6 +85,62 @@ pub fn environ_getenv<K: AsRef<OsStr>>(envp: &[OsString], variable: K) -> Option
     }
 }
 
+/// Returns a snapshot of the environment of the current process, as seen by GLib.
+///
+/// Unlike [`get_environ`](fn.get_environ.html) this doesn't copy the entries, which are not
+/// guaranteed to be valid UTF-8. Modifying the returned array with
+/// [`environ_setenv`](fn.environ_setenv.html) and [`environ_unsetenv`](fn.environ_unsetenv.html)
+/// doesn't affect the process, which makes it suitable for building the environment of child
+/// processes without racing other threads.
+pub fn environ() -> StrV {
+    unsafe { from_glib_full(glib_sys::g_get_environ()) }
+}
+
+/// Returns the value of `variable` in the environment `envp` as returned by
+/// [`environ`](fn.environ.html).
+pub fn environ_strv_getenv<'a, K: AsRef<OsStr>>(envp: &'a StrV, variable: K) -> Option<&'a CStr> {
+    unsafe {
+        let ptr = glib_sys::g_environ_getenv(
+            ToGlibPtr::<*mut *mut c_char>::to_glib_none(envp).0,
+            variable.as_ref().to_glib_none().0,
+        );
+        if ptr.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(ptr))
+        }
+    }
+}
+
+/// Sets `variable` to `value` in the environment `envp`, and returns the modified environment.
+///
+/// If the variable is already set, its value is only replaced if `overwrite` is `true`.
+pub fn environ_setenv<K: AsRef<OsStr>, V: AsRef<OsStr>>(
+    envp: StrV,
+    variable: K,
+    value: V,
+    overwrite: bool,
+) -> StrV {
+    unsafe {
+        from_glib_full(glib_sys::g_environ_setenv(
+            envp.to_glib_full(),
+            variable.as_ref().to_glib_none().0,
+            value.as_ref().to_glib_none().0,
+            overwrite.to_glib(),
+        ))
+    }
+}
+
+/// Removes `variable` from the environment `envp`, and returns the modified environment.
+pub fn environ_unsetenv<K: AsRef<OsStr>>(envp: StrV, variable: K) -> StrV {
+    unsafe {
+        from_glib_full(glib_sys::g_environ_unsetenv(
+            envp.to_glib_full(),
+            variable.as_ref().to_glib_none().0,
+        ))
+    }
+}
+
 pub fn get_user_name() -> Option<OsString> {
     #[cfg(not(all(windows, target_arch = "x86")))]
     use glib_sys::g_get_user_name;
@@ -176,6 +234,70 @@ pub fn get_tmp_dir() -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(g_get_tmp_dir()) }
 }
 
+/// Joins `elements` with the platform's directory separator, like `g_build_filename()`.
+///
+/// Unlike [`Path::join`], absolute elements don't replace the path built so far, and redundant
+/// separators between elements are collapsed while leading and trailing ones are kept.
+///
+/// [`Path::join`]: https://doc.rust-lang.org/std/path/struct.Path.html#method.join
+pub fn build_filename<P: AsRef<Path>>(elements: &[P]) -> PathBuf {
+    let elements: Vec<&Path> = elements.iter().map(AsRef::as_ref).collect();
+    ::build_filenamev(&elements)
+}
+
+/// Joins `elements` with `separator`, like `g_build_path()`.
+///
+/// Redundant separators between elements are collapsed while leading and trailing ones are
+/// kept.
+pub fn build_path<P: AsRef<Path>>(separator: &str, elements: &[P]) -> PathBuf {
+    let elements: Vec<&Path> = elements.iter().map(AsRef::as_ref).collect();
+    ::build_pathv(separator, &elements)
+}
+
+/// A named flag for [`parse_debug_string`](fn.parse_debug_string.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DebugKey<'a> {
+    pub name: &'a str,
+    pub flag: u32,
+}
+
+/// Parses a string of debug options like the value of `G_DEBUG` into the bitwise OR of the
+/// flags of all matching `keys`.
+///
+/// Options are separated by `:`, `;`, `,` or whitespace and matched case-insensitively, with
+/// `-` and `_` treated as the same. `all` enables all flags except the ones of any other options
+/// listed together with it. `help` prints the available keys to stderr.
+///
+/// ```
+/// use glib::{parse_debug_string, DebugKey};
+///
+/// const KEYS: &[DebugKey] = &[
+///     DebugKey { name: "layout", flag: 1 << 0 },
+///     DebugKey { name: "input-events", flag: 1 << 1 },
+///     DebugKey { name: "rendering", flag: 1 << 2 },
+/// ];
+///
+/// assert_eq!(parse_debug_string(Some("layout,input_events"), KEYS), 0b011);
+/// assert_eq!(parse_debug_string(Some("all"), KEYS), 0b111);
+/// assert_eq!(parse_debug_string(Some("all,rendering"), KEYS), 0b011);
+/// assert_eq!(parse_debug_string(None, KEYS), 0);
+/// ```
+pub fn parse_debug_string(string: Option<&str>, keys: &[DebugKey]) -> u32 {
+    let names: Vec<Stash<*const c_char, str>> =
+        keys.iter().map(|key| key.name.to_glib_none()).collect();
+    let keys: Vec<glib_sys::GDebugKey> = keys
+        .iter()
+        .zip(names.iter())
+        .map(|(key, name)| glib_sys::GDebugKey {
+            key: name.0,
+            value: key.flag,
+        })
+        .collect();
+    unsafe {
+        glib_sys::g_parse_debug_string(string.to_glib_none().0, keys.as_ptr(), keys.len() as u32)
+    }
+}
+
 pub fn mkstemp<P: AsRef<std::path::Path>>(tmpl: P) -> i32 {
     #[cfg(not(windows))]
     use glib_sys::g_mkstemp;
@@ -188,6 +310,7 @@ pub fn mkstemp<P: AsRef<std::path::Path>>(tmpl: P) -> i32 {
 #[cfg(test)]
 mod tests {
     use std::env;
+    use std::ffi::CStr;
     use std::sync::Mutex;
 
     //Mutex to prevent run environment tests parallel
@@ -204,6 +327,12 @@ mod tests {
 
         let environ = ::get_environ();
         assert_eq!(::environ_getenv(&environ, VAR_NAME), Some(val.into()));
+
+        let environ = ::environ();
+        assert_eq!(
+            ::environ_strv_getenv(&environ, VAR_NAME).map(CStr::to_bytes),
+            Some(val.as_bytes())
+        );
     }
 
     fn check_setenv(val: &str) {
@@ -225,6 +354,106 @@ mod tests {
         check_setenv("Тест"); // "Test" in Russian
     }
 
+    #[test]
+    fn environ_snapshot() {
+        let environ = ::environ();
+        let environ = ::environ_setenv(environ, "GLIB_RS_SNAPSHOT_TEST", "a", true);
+        let environ = ::environ_setenv(environ, "GLIB_RS_SNAPSHOT_TEST", "b", false);
+        assert_eq!(
+            ::environ_strv_getenv(&environ, "GLIB_RS_SNAPSHOT_TEST").map(CStr::to_bytes),
+            Some(&b"a"[..])
+        );
+        assert_eq!(::getenv("GLIB_RS_SNAPSHOT_TEST"), None);
+
+        let environ = ::environ_unsetenv(environ, "GLIB_RS_SNAPSHOT_TEST");
+        assert_eq!(
+            ::environ_strv_getenv(&environ, "GLIB_RS_SNAPSHOT_TEST"),
+            None
+        );
+        assert!(!environ
+            .iter()
+            .any(|entry| entry.to_bytes().starts_with(b"GLIB_RS_SNAPSHOT_TEST=")));
+    }
+
+    #[test]
+    fn user_special_dir() {
+        use UserDirectory;
+
+        ::reload_user_special_dirs_cache();
+        // Only the desktop directory has a fallback if it is not configured
+        let desktop = ::get_user_special_dir(UserDirectory::Desktop).unwrap();
+        assert!(desktop.is_absolute());
+        for &directory in &[
+            UserDirectory::Documents,
+            UserDirectory::Downloads,
+            UserDirectory::Music,
+            UserDirectory::Pictures,
+            UserDirectory::PublicShare,
+            UserDirectory::Templates,
+            UserDirectory::Videos,
+        ] {
+            if let Some(path) = ::get_user_special_dir(directory) {
+                assert!(path.is_absolute());
+            }
+        }
+    }
+
+    #[test]
+    fn hostname() {
+        assert!(!::get_host_name().is_empty());
+
+        assert!(::hostname_is_non_ascii("bücher.example"));
+        assert!(!::hostname_is_non_ascii("xn--bcher-kva.example"));
+        assert!(::hostname_is_ascii_encoded("xn--bcher-kva.example"));
+        assert!(!::hostname_is_ascii_encoded("example.com"));
+        assert_eq!(
+            ::hostname_to_ascii("bücher.example").unwrap(),
+            "xn--bcher-kva.example"
+        );
+        assert_eq!(
+            ::hostname_to_unicode("xn--bcher-kva.example").unwrap(),
+            "bücher.example"
+        );
+        // Labels are limited to 63 characters after encoding
+        let long_label = format!("ü{}.example", "a".repeat(70));
+        assert_eq!(::hostname_to_ascii(&long_label), None);
+
+        assert!(::hostname_is_ip_address("127.0.0.1"));
+        assert!(::hostname_is_ip_address("::1"));
+        assert!(!::hostname_is_ip_address("256.0.0.1"));
+        assert!(!::hostname_is_ip_address("localhost"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn paths() {
+        use std::path::PathBuf;
+
+        assert_eq!(
+            ::build_filename(&["/usr/", "/share", "doc/"]),
+            PathBuf::from("/usr/share/doc/")
+        );
+        assert_eq!(::build_path(":", &["a:", ":b"]), PathBuf::from("a:b"));
+        assert_eq!(::path_get_basename("/usr/lib/"), PathBuf::from("lib"));
+        assert_eq!(::path_get_basename("/"), PathBuf::from("/"));
+        assert_eq!(::path_get_dirname("/usr/lib/"), PathBuf::from("/usr/lib"));
+        assert_eq!(::path_get_dirname("lib"), PathBuf::from("."));
+        assert_eq!(::path_skip_root("/usr/lib"), Some(PathBuf::from("usr/lib")));
+        assert_eq!(::path_skip_root("usr/lib"), None);
+        assert!(::path_is_absolute("/usr"));
+        assert!(!::path_is_absolute("usr"));
+        #[cfg(any(feature = "v2_58", feature = "dox"))]
+        {
+            use std::path::Path;
+
+            assert_eq!(
+                ::canonicalize_filename("../lib//./x/", Some(Path::new("/usr/share"))),
+                PathBuf::from("/usr/lib/x")
+            );
+            assert!(::canonicalize_filename("x", None::<&Path>).is_absolute());
+        }
+    }
+
     #[test]
     fn test_filename_from_uri() {
         use gstring::GString;
@@ -94,6 +94,9 @@ use std::cmp::{Eq, Ordering, PartialEq, PartialOrd};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::{BuildHasher, Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::Deref;
 use std::slice;
 use std::str;
 use translate::*;
@@ -172,6 +175,32 @@ impl Variant {
         T::from_variant(self)
     }
 
+    /// Tries to downcast to a `TypedVariant`.
+    ///
+    /// Returns `Ok(TypedVariant<T>)` if the variant's type corresponds to `T`
+    /// and `Err(self)` otherwise.
+    pub fn downcast<T: FromVariant + ToVariant>(self) -> Result<TypedVariant<T>, Self> {
+        if self.is::<T>() {
+            Ok(TypedVariant(self, PhantomData))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Tries to downcast to a `&TypedVariant`.
+    ///
+    /// Returns `Some(&TypedVariant<T>)` if the variant's type corresponds to `T`
+    /// and `None` otherwise.
+    pub fn downcast_ref<T: FromVariant + ToVariant>(&self) -> Option<&TypedVariant<T>> {
+        if self.is::<T>() {
+            // This cast is safe because Variant and TypedVariant have the same
+            // representation: the only difference is the zero-sized phantom data
+            unsafe { Some(&*(self as *const Variant as *const TypedVariant<T>)) }
+        } else {
+            None
+        }
+    }
+
     /// Boxes value.
     #[inline]
     pub fn variant(value: &Variant) -> Self {
@@ -331,6 +360,48 @@ impl Variant {
 unsafe impl Send for Variant {}
 unsafe impl Sync for Variant {}
 
+/// Collects the items of an iterator into a GVariant array.
+///
+/// Collecting [`DictEntry`](struct.DictEntry.html) items creates a dictionary, while tuples
+/// create an array of tuples:
+///
+/// ```
+/// use glib::variant::DictEntry;
+/// use glib::Variant;
+///
+/// let array = vec![1u32, 2, 3].iter().collect::<Variant>();
+/// assert_eq!(array.type_().to_str(), "au");
+///
+/// let dict = vec![("a", 1u32), ("b", 2)]
+///     .into_iter()
+///     .map(|(key, value)| DictEntry::new(key, value))
+///     .collect::<Variant>();
+/// assert_eq!(dict.type_().to_str(), "a{su}");
+/// ```
+impl<T: StaticVariantType + ToVariant> FromIterator<T> for Variant {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let children = iter
+            .into_iter()
+            .map(|child| child.to_variant())
+            .collect::<Vec<_>>();
+        Variant::array::<T>(&children)
+    }
+}
+
+impl<'a> IntoIterator for &'a Variant {
+    type Item = Variant;
+    type IntoIter = VariantIter;
+
+    /// Create an iterator over items in the variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is not a container.
+    fn into_iter(self) -> VariantIter {
+        self.iter()
+    }
+}
+
 impl fmt::Debug for Variant {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Variant")
@@ -395,6 +466,120 @@ impl Hash for Variant {
     }
 }
 
+/// A `Variant` whose type is statically known to correspond to `T`.
+///
+/// This can be used in signatures to express that a variant of a specific
+/// shape is expected, without having to validate its type at every call site.
+/// It dereferences to `Variant` so it can be used everywhere `Variant`
+/// references are accepted.
+///
+/// ```
+/// use glib::{ToVariant, TypedVariant};
+///
+/// let typed = TypedVariant::new(&(1u32, String::from("hello")));
+/// assert_eq!(typed.type_().to_str(), "(us)");
+/// assert_eq!(typed.get(), (1u32, String::from("hello")));
+///
+/// let variant = 10i32.to_variant();
+/// assert!(variant.clone().downcast::<u32>().is_err());
+/// assert_eq!(variant.downcast::<i32>().unwrap().get(), 10);
+/// ```
+#[repr(transparent)]
+pub struct TypedVariant<T>(Variant, PhantomData<*const T>);
+
+impl<T: FromVariant + ToVariant> TypedVariant<T> {
+    /// Creates a new `TypedVariant` carrying `value`.
+    pub fn new(value: &T) -> Self {
+        TypedVariant(value.to_variant(), PhantomData)
+    }
+
+    /// Returns the value.
+    ///
+    /// This never fails as the variant's type is guaranteed to match `T`.
+    pub fn get(&self) -> T {
+        T::from_variant(&self.0).expect("TypedVariant type mismatch")
+    }
+
+    /// Converts into the untyped `Variant`.
+    pub fn into_inner(self) -> Variant {
+        self.0
+    }
+}
+
+impl<T> Clone for TypedVariant<T> {
+    fn clone(&self) -> Self {
+        TypedVariant(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T> fmt::Debug for TypedVariant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedVariant").field(&self.0).finish()
+    }
+}
+
+impl<T> fmt::Display for TypedVariant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T> PartialEq for TypedVariant<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for TypedVariant<T> {}
+
+impl<T> Hash for TypedVariant<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T> Deref for TypedVariant<T> {
+    type Target = Variant;
+
+    fn deref(&self) -> &Variant {
+        &self.0
+    }
+}
+
+impl<T> AsRef<Variant> for TypedVariant<T> {
+    fn as_ref(&self) -> &Variant {
+        &self.0
+    }
+}
+
+impl<T> From<TypedVariant<T>> for Variant {
+    fn from(value: TypedVariant<T>) -> Self {
+        value.0
+    }
+}
+
+// A Variant is immutable and thread-safe, the type marker carries no data.
+unsafe impl<T> Send for TypedVariant<T> {}
+unsafe impl<T> Sync for TypedVariant<T> {}
+
+impl<T: StaticVariantType> StaticVariantType for TypedVariant<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        T::static_variant_type()
+    }
+}
+
+impl<T> ToVariant for TypedVariant<T> {
+    fn to_variant(&self) -> Variant {
+        self.0.clone()
+    }
+}
+
+impl<T: FromVariant + ToVariant> FromVariant for TypedVariant<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        variant.clone().downcast().ok()
+    }
+}
+
 /// Converts to `Variant`.
 pub trait ToVariant {
     /// Returns a `Variant` clone of `self`.
@@ -916,6 +1101,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_typed_variant() {
+        let v = TypedVariant::new(&vec![1u32, 2, 3]);
+        assert_eq!(v.type_().to_str(), "au");
+        assert_eq!(v.get(), vec![1, 2, 3]);
+
+        let untyped = (1u8, "a").to_variant();
+        assert!(untyped.downcast_ref::<(u8, u8)>().is_none());
+        let typed = untyped.downcast_ref::<(u8, String)>().unwrap();
+        assert_eq!(typed.get(), (1, String::from("a")));
+
+        let untyped = typed.to_variant();
+        assert!(untyped.get::<TypedVariant<(u8, String)>>().is_some());
+        assert!(untyped.get::<TypedVariant<u8>>().is_none());
+    }
+
     #[test]
     fn test_array() {
         // Test just the signature for now.
@@ -925,4 +1126,38 @@ mod tests {
             "a(syu)"
         );
     }
+
+    #[test]
+    fn test_from_iter() {
+        let v = vec![1u32, 2, 3].iter().collect::<Variant>();
+        assert_eq!(v.type_().to_str(), "au");
+        assert_eq!(v.get::<Vec<u32>>(), Some(vec![1, 2, 3]));
+
+        let v = Vec::<&str>::new().into_iter().collect::<Variant>();
+        assert_eq!(v.type_().to_str(), "as");
+        assert_eq!(v.n_children(), 0);
+
+        let v = vec![("a", 1u8), ("b", 2)]
+            .into_iter()
+            .map(|(key, value)| DictEntry::new(key, value))
+            .collect::<Variant>();
+        assert_eq!(v.type_().to_str(), "a{sy}");
+        let map = v.get::<HashMap<String, u8>>().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 1);
+        assert_eq!(map["b"], 2);
+
+        let v = vec![("a", 1u8)].into_iter().collect::<Variant>();
+        assert_eq!(v.type_().to_str(), "a(sy)");
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let v = vec!["a", "b"].to_variant();
+        let mut items = Vec::new();
+        for item in &v {
+            items.push(item.get::<String>().unwrap());
+        }
+        assert_eq!(items, ["a", "b"]);
+    }
 }
@@ -22,7 +22,7 @@
 ///
 /// This creates a wrapper named `$name` around the foreign type
 /// `$foreign` of `$kind` — one of [`Boxed`][#boxed],
-/// [`Shared`][#shared], or [`Object`][#object].
+/// [`BoxedInline`][#boxedinline], [`Shared`][#shared], or [`Object`][#object].
 ///
 /// Inside the `match fn` block there are closure-like expressions to
 /// provide ways of copying/freeing, or referencing/unreferencing the
@@ -31,7 +31,7 @@
 /// functions from an FFI crate.
 ///
 /// What follows is a description of each of the possible `$kind`:
-/// [`Boxed`][#boxed], [`Shared`][#shared], and [`Object`][#object];
+/// [`Boxed`][#boxed], [`BoxedInline`][#boxedinline], [`Shared`][#shared], and [`Object`][#object];
 /// note that each supports different sets of `$fn_name` inside the
 /// `match fn` block.  Also, `Object` may require you to specify
 /// things like the class struct to wrap, plus any interfaces that the
@@ -77,6 +77,55 @@
 /// `get_type`: `|| -> glib_ffi::GType` (optional) returns the
 /// `glib_ffi::GType` that corresponds to the foreign struct.
 ///
+/// ### BoxedInline
+///
+/// Boxed records that are stored by value in the wrapper instead of behind a
+/// heap allocation, e.g. small structs like `GdkRGBA`.
+///
+/// For plain structs that can be copied bitwise, and whose foreign type
+/// implements `Copy`:
+///
+/// ```ignore
+/// glib_wrapper! {
+///     /// A color
+///     pub struct RGBA(BoxedInline<ffi::GdkRGBA>);
+///
+///     match fn {
+///         get_type => || ffi::gdk_rgba_get_type(),
+///     }
+/// }
+/// ```
+///
+/// The wrapper then implements `Copy` as well. The `match fn` block is
+/// optional if there is no registered `glib_ffi::GType`.
+///
+/// For structs that own other memory:
+///
+/// ```ignore
+/// glib_wrapper! {
+///     /// A 3D matrix
+///     pub struct Matrix(BoxedInline<ffi::GrapheneMatrix>);
+///
+///     match fn {
+///         init      => |ptr|       init_matrix(ptr),
+///         copy_into => |dest, src| copy_matrix_into(dest, src),
+///         clear     => |ptr|       clear_matrix(ptr),
+///         get_type  => ||          ffi::graphene_matrix_get_type(),
+///     }
+/// }
+/// ```
+///
+/// `init`: `|*mut $foreign|` initializes zeroed memory.
+///
+/// `copy_into`: `|*mut $foreign, *const $foreign|` copies the value into uninitialized memory.
+///
+/// `clear`: `|*mut $foreign|` frees all memory owned by the value, but not the value itself.
+///
+/// `get_type`: `|| -> glib_ffi::GType` (optional) returns the
+/// `glib_ffi::GType` that corresponds to the foreign struct.
+///
+/// Values passed with full transfer from or to C are heap allocated with `g_malloc()`.
+///
 /// ### Shared
 ///
 /// Records with reference-counted, shared ownership.
@@ -229,7 +278,41 @@
 /// }
 /// ```
 ///
+/// ### Raw pointers
+///
+/// All wrappers get the inherent methods `into_glib_ptr()`, `from_glib_ptr_full()` and
+/// `from_glib_ptr_none()` for transferring ownership through raw pointers, e.g. the
+/// `user_data` of C callbacks:
+///
+/// ```ignore
+/// let user_data = button.into_glib_ptr() as glib_ffi::gpointer;
+/// // ... and in the callback
+/// let button = Button::from_glib_ptr_full(user_data as *mut ffi::GtkButton);
+/// ```
+///
+/// #### Wrapper options
+///
+/// Object, subclass and interface wrappers implement `Debug` by printing the
+/// wrapped object reference. Options can be given before the `struct` to
+/// change which formatting traits are implemented:
+///
+/// ```ignore
+/// glib_wrapper! {
+///     /// Your documentation goes here
+///     @display
+///     @no_debug
+///     pub struct Button(Object<ffi::GtkButton, ButtonClass>) @extends Bin, Container, Widget;
+///     ...
+/// }
+/// ```
+///
+/// `@display` implements `Display` with the output of `g_strdup_value_contents()`.
+///
+/// `@no_debug` does not implement `Debug`, so that a custom implementation can
+/// be provided instead. Note that object types always have to implement `Debug`.
+///
 /// [#boxed]: #boxed
+/// [#boxedinline]: #boxedinline
 /// [#shared]: #shared
 /// [#object]: #object
 /// [#non-derivable-classes]: #non-derivable-classes
@@ -297,6 +380,57 @@ macro_rules! glib_wrapper {
             @get_type $get_type_expr);
     };
 
+    // BoxedInline
+
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(BoxedInline<$ffi_name:ty>);
+    ) => {
+        $crate::glib_boxed_inline_wrapper!([$($attr)*] $name, $ffi_name);
+    };
+
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(BoxedInline<$ffi_name:ty>);
+
+        match fn {
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        $crate::glib_boxed_inline_wrapper!([$($attr)*] $name, $ffi_name, @get_type $get_type_expr);
+    };
+
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(BoxedInline<$ffi_name:ty>);
+
+        match fn {
+            init => |$init_arg:ident| $init_expr:expr,
+            copy_into => |$copy_into_dest_arg:ident, $copy_into_src_arg:ident| $copy_into_expr:expr,
+            clear => |$clear_arg:ident| $clear_expr:expr,
+        }
+    ) => {
+        $crate::glib_boxed_inline_wrapper!([$($attr)*] $name, $ffi_name, @init $init_arg $init_expr,
+            @copy_into $copy_into_dest_arg $copy_into_src_arg $copy_into_expr,
+            @clear $clear_arg $clear_expr);
+    };
+
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident(BoxedInline<$ffi_name:ty>);
+
+        match fn {
+            init => |$init_arg:ident| $init_expr:expr,
+            copy_into => |$copy_into_dest_arg:ident, $copy_into_src_arg:ident| $copy_into_expr:expr,
+            clear => |$clear_arg:ident| $clear_expr:expr,
+            get_type => || $get_type_expr:expr,
+        }
+    ) => {
+        $crate::glib_boxed_inline_wrapper!([$($attr)*] $name, $ffi_name, @init $init_arg $init_expr,
+            @copy_into $copy_into_dest_arg $copy_into_src_arg $copy_into_expr,
+            @clear $clear_arg $clear_expr, @get_type $get_type_expr);
+    };
+
     // Shared
 
     (
@@ -329,30 +463,33 @@ macro_rules! glib_wrapper {
     // Object, no class struct, no parents or interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $rust_class_name:ident>);
 
         match fn {
             get_type => || $get_type_expr:expr,
         }
     ) => {
-        $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, ::std::os::raw::c_void, $rust_class_name, @get_type $get_type_expr, @extends [], @implements []);
+        $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, ::std::os::raw::c_void, $rust_class_name, @get_type $get_type_expr, @extends [], @implements [], @options [$($opt)*]);
     };
 
     // Object, class struct, no parents or interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident>);
 
         match fn {
             get_type => || $get_type_expr:expr,
         }
     ) => {
-        $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name, @get_type $get_type_expr, @extends [], @implements []);
+        $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name, @get_type $get_type_expr, @extends [], @implements [], @options [$($opt)*]);
     };
 
     // Object, no class struct, parents, no interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $rust_class_name:ident>) @extends $($extends:path),+;
 
         match fn {
@@ -360,12 +497,13 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, ::std::os::raw::c_void, $rust_class_name,
-            @get_type $get_type_expr, @extends [$($extends),+], @implements []);
+            @get_type $get_type_expr, @extends [$($extends),+], @implements [], @options [$($opt)*]);
     };
 
     // Object, class struct, parents, no interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident>) @extends $($extends:path),+;
 
         match fn {
@@ -373,12 +511,13 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
-            @get_type $get_type_expr, @extends [$($extends),+], @implements []);
+            @get_type $get_type_expr, @extends [$($extends),+], @implements [], @options [$($opt)*]);
     };
 
     // Object, no class struct, no parents, interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $rust_class_name:ident>) @implements $($implements:path),+;
 
         match fn {
@@ -386,12 +525,13 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, ::std::os::raw::c_void, $rust_class_name,
-            @get_type $get_type_expr, @extends [], @implements [$($implements),+]);
+            @get_type $get_type_expr, @extends [], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // Object, class struct, no parents, interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident>) @implements $($implements:path),+;
 
         match fn {
@@ -399,12 +539,13 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
-            @get_type $get_type_expr, @extends [], @implements [$($implements),+]);
+            @get_type $get_type_expr, @extends [], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // Object, no class struct, parents and interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $rust_class_name:ident>) @extends $($extends:path),+, @implements $($implements:path),+;
 
         match fn {
@@ -412,12 +553,13 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, ::std::os::raw::c_void, $rust_class_name,
-            @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+]);
+            @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // Object, class struct, parents and interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Object<$ffi_name:ty, $ffi_class_name:ty, $rust_class_name:ident>) @extends $($extends:path),+, @implements $($implements:path),+;
 
         match fn {
@@ -425,71 +567,118 @@ macro_rules! glib_wrapper {
         }
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, $ffi_name, $ffi_class_name, $rust_class_name,
-            @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+]);
+            @get_type $get_type_expr, @extends [$($extends),+], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // ObjectSubclass, no parents or interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(ObjectSubclass<$subclass:ty, $rust_class_name:ident>);
     ) => {
-        use glib::translate::ToGlib;
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, <$subclass as $crate::subclass::types::ObjectSubclass>::Instance, <$subclass as $crate::subclass::types::ObjectSubclass>::Class, $rust_class_name,
             @get_type $crate::translate::ToGlib::to_glib(&<$subclass as $crate::subclass::types::ObjectSubclass>::get_type()),
-            @extends [], @implements []);
+            @extends [], @implements [], @options [$($opt)*]);
     };
 
     // ObjectSubclass, no parents, interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(ObjectSubclass<$subclass:ty, $rust_class_name:ident>) @implements $($implements:path),+;
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, <$subclass as $crate::subclass::types::ObjectSubclass>::Instance, <$subclass as $crate::subclass::types::ObjectSubclass>::Class, $rust_class_name,
             @get_type $crate::translate::ToGlib::to_glib(&<$subclass as $crate::subclass::types::ObjectSubclass>::get_type()),
-            @extends [], @implements [$($implements),+]);
+            @extends [], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // ObjectSubclass, parents, no interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(ObjectSubclass<$subclass:ty, $rust_class_name:ident>) @extends $($extends:path),+;
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, <$subclass as $crate::subclass::types::ObjectSubclass>::Instance, <$subclass as $crate::subclass::types::ObjectSubclass>::Class, $rust_class_name,
             @get_type $crate::translate::ToGlib::to_glib(&<$subclass as $crate::subclass::types::ObjectSubclass>::get_type()),
-            @extends [$($extends),+], @implements []);
+            @extends [$($extends),+], @implements [], @options [$($opt)*]);
     };
 
     // ObjectSubclass, parents and interfaces
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(ObjectSubclass<$subclass:ty, $rust_class_name:ident>) @extends $($extends:path),+, @implements $($implements:path),+;
     ) => {
         $crate::glib_object_wrapper!(@object [$($attr)*] $name, <$subclass as $crate::subclass::types::ObjectSubclass>::Instance, <$subclass as $crate::subclass::types::ObjectSubclass>::Class, $rust_class_name,
             @get_type $crate::translate::ToGlib::to_glib(&<$subclass as $crate::subclass::types::ObjectSubclass>::get_type()),
-            @extends [$($extends),+], @implements [$($implements),+]);
+            @extends [$($extends),+], @implements [$($implements),+], @options [$($opt)*]);
     };
 
     // Interface, no prerequisites
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Interface<$ffi_name:ty>);
 
         match fn {
             get_type => || $get_type_expr:expr,
         }
     ) => {
-        $crate::glib_object_wrapper!(@interface [$($attr)*] $name, $ffi_name, @get_type $get_type_expr, @requires []);
+        $crate::glib_object_wrapper!(@interface [$($attr)*] $name, $ffi_name, @get_type $get_type_expr, @requires [], @options [$($opt)*]);
     };
 
     // Interface, prerequisites
     (
         $(#[$attr:meta])*
+        $(@$opt:ident)*
         pub struct $name:ident(Interface<$ffi_name:ty>) @requires $($requires:path),+;
 
         match fn {
             get_type => || $get_type_expr:expr,
         }
     ) => {
-        $crate::glib_object_wrapper!(@interface [$($attr)*] $name, $ffi_name, @get_type $get_type_expr, @requires [$($requires),+]);
+        $crate::glib_object_wrapper!(@interface [$($attr)*] $name, $ffi_name, @get_type $get_type_expr, @requires [$($requires),+], @options [$($opt)*]);
+    };
+}
+
+/// Inherent methods for passing ownership of wrappers through raw pointers. See `glib_wrapper!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! glib_wrapper_raw_ptr_impl {
+    ($name:ident, $ffi_name:ty, |$this:ident| $into_expr:expr) => {
+        impl $name {
+            /// Consumes the wrapper and returns an owned pointer to the underlying value, e.g.
+            /// for passing it as `user_data` to a C callback.
+            ///
+            /// Ownership can be taken back with [`from_glib_ptr_full`](#method.from_glib_ptr_full),
+            /// otherwise the value is leaked.
+            #[inline]
+            pub fn into_glib_ptr(self) -> *mut $ffi_name {
+                let $this = self;
+                $into_expr
+            }
+
+            /// Creates a wrapper that takes ownership of `ptr`, e.g. of a pointer returned by
+            /// [`into_glib_ptr`](#method.into_glib_ptr).
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be a valid, non-`NULL` pointer whose ownership is transferred to the
+            /// wrapper.
+            #[inline]
+            pub unsafe fn from_glib_ptr_full(ptr: *mut $ffi_name) -> Self {
+                $crate::translate::from_glib_full(ptr)
+            }
+
+            /// Creates a wrapper from `ptr`, which stays owned by the caller.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be a valid, non-`NULL` pointer.
+            #[inline]
+            pub unsafe fn from_glib_ptr_none(ptr: *mut $ffi_name) -> Self {
+                $crate::translate::from_glib_none(ptr)
+            }
+        }
     };
 }
@@ -58,6 +58,38 @@ fn subfields_renaming() {
     Foo { v: Rc::new(0) }.foo();
 }
 
+#[test]
+fn subfields_renaming_weak() {
+    struct Inner {
+        state: Rc<RefCell<State>>,
+    }
+
+    struct Foo {
+        inner: Inner,
+    }
+
+    impl Foo {
+        fn foo(&self) -> impl Fn() -> bool {
+            clone!(@weak self.inner.state as state, @weak-allow-none self.inner.state as maybe_state => @default-return false, move || {
+                state.borrow_mut().count += 1;
+                maybe_state.is_some()
+            })
+        }
+    }
+
+    let foo = Foo {
+        inner: Inner {
+            state: Rc::new(RefCell::new(State::new())),
+        },
+    };
+    let closure = foo.foo();
+    assert!(closure());
+    assert_eq!(foo.inner.state.borrow().count, 1);
+
+    drop(foo);
+    assert!(!closure());
+}
+
 #[test]
 fn renaming() {
     let state = Rc::new(RefCell::new(State::new()));
@@ -119,6 +151,55 @@ fn clone_default_value() {
     assert_eq!(42, closure(50));
 }
 
+#[test]
+fn clone_default_value_non_unit() {
+    use glib::signal::Inhibit;
+
+    let state = Rc::new(RefCell::new(State::new()));
+    let closure = clone!(@weak state as s => @default-return Inhibit(false), move || {
+        s.borrow_mut().count += 1;
+        Inhibit(true)
+    });
+
+    assert_eq!(closure(), Inhibit(true));
+    assert_eq!(state.borrow().count, 1);
+
+    drop(state);
+    assert_eq!(closure(), Inhibit(false));
+}
+
+#[test]
+fn clone_weak_allow_none() {
+    let state = Rc::new(RefCell::new(State::new()));
+    let other = Rc::new(RefCell::new(State::new()));
+    let closure = clone!(@weak-allow-none state, @weak other => @default-return None, move || {
+        other.borrow_mut().count += 1;
+        Some(state.map(|state| state.borrow().count))
+    });
+
+    assert_eq!(closure(), Some(Some(0)));
+
+    // The closure still runs after the `@weak-allow-none` capture is gone.
+    drop(state);
+    assert_eq!(closure(), Some(None));
+    assert_eq!(other.borrow().count, 2);
+
+    // But not after the `@weak` one is gone.
+    drop(other);
+    assert_eq!(closure(), None);
+}
+
+#[test]
+fn clone_default_panic() {
+    let closure = {
+        let state = Rc::new(RefCell::new(State::new()));
+        clone!(@weak state => @default-panic, move || state.borrow().count)
+    };
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| closure()));
+    assert!(result.is_err());
+}
+
 #[test]
 fn clone_panic() {
     let state = Arc::new(Mutex::new(State::new()));
@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate glib;
+
+use glib::test_utils::{self, TestSubprocessFlags};
+use glib::LogLevel;
+
+fn main() {
+    test_utils::init();
+
+    test_utils::add_func("/glib-rs/expect-message", || {
+        test_utils::expect_message(Some("glib-rs-test"), LogLevel::Warning, "*expected*");
+        g_warning!("glib-rs-test", "an expected warning");
+        g_test_assert_expected_messages!();
+        assert!(!test_utils::failed());
+    });
+
+    test_utils::add_func("/glib-rs/trap-passed", || {
+        if test_utils::subprocess() {
+            eprintln!("all good");
+            return;
+        }
+        test_utils::trap_subprocess(None, 0, TestSubprocessFlags::empty());
+        assert!(test_utils::trap_has_passed());
+        assert!(!test_utils::trap_reached_timeout());
+        g_test_trap_assert_passed!();
+        g_test_trap_assert_stderr!("*all good*");
+        g_test_trap_assert_stdout_unmatched!("*all good*");
+    });
+
+    test_utils::add_func("/glib-rs/trap-warning", || {
+        if test_utils::subprocess() {
+            // Warnings are fatal in tests
+            g_warning!("glib-rs-test", "an unexpected warning");
+            return;
+        }
+        test_utils::trap_subprocess(None, 0, TestSubprocessFlags::empty());
+        g_test_trap_assert_failed!();
+        g_test_trap_assert_stderr!("*an unexpected warning*");
+    });
+
+    test_utils::add_func("/glib-rs/trap-panic", || {
+        if test_utils::subprocess() {
+            panic!("a panic");
+        }
+        test_utils::trap_subprocess(None, 0, TestSubprocessFlags::empty());
+        g_test_trap_assert_failed!();
+        g_test_trap_assert_stderr!("*a panic*");
+    });
+
+    std::process::exit(test_utils::run());
+}